@@ -24,6 +24,11 @@ impl Generator<&ligen_idl::TypeDefinition, anchor_lang_idl_spec::IdlTypeDef> for
             return Err(anyhow::anyhow!("Type definition is not an account: {}", name).into());
         }
 
+        // Borsh, Anchor's serialization, has no untagged unions.
+        if let KindDefinition::Union(_) = &input.definition {
+            return Err(anyhow::anyhow!("Unions can't be Anchor accounts: {}", name).into());
+        }
+
         let docs = input.attributes.get_documentation();
 
         let serialization = IdlSerialization::Borsh;
@@ -22,6 +22,7 @@ impl Transformer<Idl, Module> for ModuleParser {
             .iter()
             .map(|constant| {
                 Ok(Object {
+                    attributes: Default::default(),
                     visibility,
                     mutability: Mutability::Constant,
                     identifier: Identifier::new(constant.name.clone()),
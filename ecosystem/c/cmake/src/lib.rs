@@ -18,7 +18,7 @@ impl FileGenerator for CMakeGenerator {
         "c".into()
     }
 
-    fn generate_files(&self, library: &Library, file_set: &mut FileSet) -> Result<()> {
+    fn generate_files(&self, library: &Library, file_set: &mut FileSet, _config: &Config) -> Result<()> {
         let generator_version = env!("CARGO_PKG_VERSION");
         let library_name = SnakeCase::try_from(library.name.clone())?.to_string();
 
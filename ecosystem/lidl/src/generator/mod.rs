@@ -0,0 +1,532 @@
+//! Textual IDL generator.
+
+use ligen_idl::{
+    Attribute, Attributes, Field, Function, Generics, Identifier, Import, Interface, KindDefinition, Library,
+    Literal, Method, Module, Mutability, Object, Parameter, Path, Receiver, Synchrony, Type,
+    TypeDefinition, Visibility,
+};
+use ligen_transformer::prelude::*;
+
+/// Textual IDL generator. Pretty-prints a `Library` in the syntax `LidlParser` reads.
+#[derive(Default)]
+pub struct LidlGenerator;
+
+impl LidlGenerator {
+    /// Creates a new generator.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Generator<&Library, String> for LidlGenerator {
+    fn generate(&self, library: &Library, _config: &Config) -> Result<String> {
+        let mut printer = Printer::default();
+        printer.library(library);
+        Ok(printer.output)
+    }
+}
+
+#[derive(Default)]
+struct Printer {
+    output: String,
+    indentation: usize,
+}
+
+impl Printer {
+    fn line(&mut self, line: impl AsRef<str>) {
+        let line = line.as_ref();
+        if !line.is_empty() {
+            self.output.push_str(&"    ".repeat(self.indentation));
+            self.output.push_str(line);
+        }
+        self.output.push('\n');
+    }
+
+    fn library(&mut self, library: &Library) {
+        if !library.metadata.summary.is_empty() {
+            for line in library.metadata.summary.lines() {
+                self.line(format!("/// {line}"));
+            }
+        }
+        self.attributes(&library.root_module.attributes);
+        self.line(format!("library {}@{};", library.identifier, library.metadata.version));
+        if !library.root_module.is_empty() || !library.root_module.imports.is_empty() {
+            self.line("");
+            self.module_items(&library.root_module);
+        }
+    }
+
+    fn module_items(&mut self, module: &Module) {
+        let mut sections = Vec::new();
+        if !module.imports.is_empty() {
+            sections.push(Section::Imports(&module.imports));
+        }
+        if !module.objects.is_empty() {
+            sections.push(Section::Objects(&module.objects));
+        }
+        for type_ in &module.types {
+            sections.push(Section::Type(type_));
+        }
+        if !module.functions.is_empty() {
+            sections.push(Section::Functions(&module.functions));
+        }
+        for interface in &module.interfaces {
+            sections.push(Section::Interface(interface));
+        }
+        for module in &module.modules {
+            sections.push(Section::Module(module));
+        }
+        for (index, section) in sections.into_iter().enumerate() {
+            if index > 0 {
+                self.line("");
+            }
+            match section {
+                Section::Imports(imports) => {
+                    for import in imports {
+                        self.import(import);
+                    }
+                },
+                Section::Objects(objects) => self.objects(objects),
+                Section::Type(type_) => self.type_definition(type_),
+                Section::Functions(functions) => {
+                    for function in functions {
+                        self.function(function);
+                    }
+                },
+                Section::Interface(interface) => self.interface(interface),
+                Section::Module(module) => {
+                    self.attributes(&module.attributes);
+                    self.line(format!("{}module {} {{", visibility(&module.visibility), module.identifier));
+                    self.indentation += 1;
+                    self.module_items(module);
+                    self.indentation -= 1;
+                    self.line("}");
+                },
+            }
+        }
+    }
+
+    fn import(&mut self, import: &Import) {
+        self.attributes(&import.attributes);
+        let renaming = import
+            .renaming
+            .as_ref()
+            .map(|renaming| format!(" as {renaming}"))
+            .unwrap_or_default();
+        self.line(format!("{}use {}{renaming};", visibility(&import.visibility), path(&import.path)));
+    }
+
+    fn objects(&mut self, objects: &[Object]) {
+        for object in objects {
+            self.attributes(&object.attributes);
+            let keyword = match object.mutability {
+                Mutability::Constant => "const",
+                Mutability::Mutable => "var",
+            };
+            self.line(format!(
+                "{}{keyword} {}: {} = {};",
+                visibility(&object.visibility),
+                object.identifier,
+                type_(&object.type_),
+                literal(&object.literal)
+            ));
+        }
+    }
+
+    fn type_definition(&mut self, definition: &TypeDefinition) {
+        self.attributes(&definition.attributes);
+        let mut header = format!(
+            "{}{} {}{}",
+            visibility(&definition.visibility),
+            match definition.definition {
+                KindDefinition::Structure(_) => "record",
+                KindDefinition::Enumeration(_) => "enum",
+                KindDefinition::TypeAlias(_) => "type",
+                KindDefinition::Union(_) => "union",
+            },
+            definition.identifier,
+            generics(&definition.generics)
+        );
+        if !definition.interfaces.is_empty() {
+            header.push_str(": ");
+            header.push_str(&definition.interfaces.iter().map(path).collect::<Vec<_>>().join(" + "));
+        }
+        match &definition.definition {
+            KindDefinition::Structure(structure) => {
+                if structure.fields.is_empty() {
+                    self.line(format!("{header};"));
+                } else if structure.fields.iter().all(|field| field.identifier.is_some()) {
+                    self.named_fields(header, &structure.fields);
+                } else {
+                    let fields = structure
+                        .fields
+                        .iter()
+                        .map(|field| format!("{}{}{}", inline_attributes(&field.attributes), visibility(&field.visibility), type_(&field.type_)))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    self.line(format!("{header}({fields});"));
+                }
+            },
+            KindDefinition::Enumeration(enumeration) => {
+                if enumeration.variants.is_empty() {
+                    self.line(format!("{header} {{}}"));
+                } else {
+                    self.line(format!("{header} {{"));
+                    self.indentation += 1;
+                    for variant in &enumeration.variants {
+                        self.attributes(&variant.attributes);
+                        self.line(format!("{},", variant.identifier));
+                    }
+                    self.indentation -= 1;
+                    self.line("}");
+                }
+            },
+            KindDefinition::TypeAlias(alias) if alias.type_ == Type::opaque() => self.line(format!("{header};")),
+            KindDefinition::TypeAlias(alias) => self.line(format!("{header} = {};", type_(&alias.type_))),
+            KindDefinition::Union(union) => self.named_fields(header, &union.fields),
+        }
+    }
+
+    fn named_fields(&mut self, header: String, fields: &[Field]) {
+        self.line(format!("{header} {{"));
+        self.indentation += 1;
+        for field in fields {
+            self.attributes(&field.attributes);
+            let identifier = field.identifier.as_ref().map(ToString::to_string).unwrap_or_default();
+            self.line(format!("{}{identifier}: {},", visibility(&field.visibility), type_(&field.type_)));
+        }
+        self.indentation -= 1;
+        self.line("}");
+    }
+
+    fn function(&mut self, function: &Function) {
+        self.attributes(&function.attributes);
+        let inputs = parameters(&function.inputs);
+        self.signature(&function.visibility, &function.synchrony, &function.identifier, inputs, function.output.as_ref());
+    }
+
+    fn method(&mut self, method: &Method) {
+        self.attributes(&method.attributes);
+        let mutability = match method.mutability {
+            Mutability::Constant => "",
+            Mutability::Mutable => "mut ",
+        };
+        let receiver = match &method.receiver {
+            Receiver::Shared | Receiver::Mutable => format!("{mutability}self"),
+            Receiver::Owned => format!("move {mutability}self"),
+            Receiver::Boxed(pointer) => format!("{mutability}self: {}", type_(pointer)),
+        };
+        let inputs = std::iter::once(receiver)
+            .chain(method.inputs.iter().map(parameter))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.signature(&method.visibility, &method.synchrony, &method.identifier, inputs, method.output.as_ref());
+    }
+
+    fn signature(&mut self, visibility_: &Visibility, synchrony: &Synchrony, identifier: &Identifier, inputs: String, output: Option<&Type>) {
+        let synchrony = match synchrony {
+            Synchrony::Synchronous => "",
+            Synchrony::Asynchronous => "async ",
+        };
+        let output = output
+            .map(|output| format!(" -> {}", type_(output)))
+            .unwrap_or_default();
+        self.line(format!("{}{synchrony}func {identifier}({inputs}){output};", visibility(visibility_)));
+    }
+
+    fn interface(&mut self, interface: &Interface) {
+        self.attributes(&interface.attributes);
+        let mut header = format!("{}interface {}", visibility(&interface.visibility), interface.identifier);
+        if !interface.interfaces.is_empty() {
+            header.push_str(": ");
+            header.push_str(&interface.interfaces.iter().map(path).collect::<Vec<_>>().join(" + "));
+        }
+        self.line(format!("{header} {{"));
+        self.indentation += 1;
+        self.objects(&interface.objects);
+        for definition in &interface.types {
+            self.type_definition(definition);
+        }
+        for function in &interface.functions {
+            self.function(function);
+        }
+        for method in &interface.methods {
+            self.method(method);
+        }
+        self.indentation -= 1;
+        self.line("}");
+    }
+
+    /// Prints `doc` attributes as `///` comments and the others as `#[...]`, keeping their order.
+    fn attributes(&mut self, attributes: &Attributes) {
+        let mut pending = Vec::new();
+        for attribute in attributes.iter() {
+            match documentation(attribute) {
+                Some(documentation) => {
+                    if !pending.is_empty() {
+                        self.line(format!("#[{}]", std::mem::take(&mut pending).join(", ")));
+                    }
+                    self.line(format!("///{documentation}"));
+                },
+                None => pending.push(self::attribute(attribute)),
+            }
+        }
+        if !pending.is_empty() {
+            self.line(format!("#[{}]", pending.join(", ")));
+        }
+    }
+}
+
+enum Section<'a> {
+    Imports(&'a [Import]),
+    Objects(&'a [Object]),
+    Type(&'a TypeDefinition),
+    Functions(&'a [Function]),
+    Interface(&'a Interface),
+    Module(&'a Module),
+}
+
+/// The text of a single-line `doc` attribute.
+fn documentation(attribute: &Attribute) -> Option<&str> {
+    attribute
+        .as_named()
+        .filter(|named| named.path == Path::from("doc"))
+        .and_then(|named| named.literal.as_string())
+        .filter(|documentation| !documentation.contains('\n'))
+        .map(String::as_str)
+}
+
+fn inline_attributes(attributes: &Attributes) -> String {
+    if attributes.is_empty() {
+        Default::default()
+    } else {
+        format!("#[{}] ", attributes.iter().map(attribute).collect::<Vec<_>>().join(", "))
+    }
+}
+
+fn attribute(attribute: &Attribute) -> String {
+    match attribute {
+        Attribute::Literal(value) => literal(value),
+        Attribute::Named(named) => format!("{} = {}", path(&named.path), literal(&named.literal)),
+        Attribute::Group(group) if group.attributes.is_empty() => path(&group.path),
+        Attribute::Group(group) => format!(
+            "{}({})",
+            path(&group.path),
+            group.attributes.iter().map(self::attribute).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+fn literal(value: &Literal) -> String {
+    let list = |values: &[Literal]| values.iter().map(literal).collect::<Vec<_>>().join(", ");
+    match value {
+        Literal::String(value) => format!("{value:?}"),
+        Literal::Boolean(value) => value.to_string(),
+        Literal::Character(value) => format!("{value:?}"),
+        Literal::Integer(value) => value.to_string(),
+        Literal::UnsignedInteger(value) => format!("{value}u64"),
+        Literal::Float(value) => format!("{value:?}"),
+        Literal::Tuple(values) => format!("({})", list(values)),
+        Literal::Array(values) => format!("[{}]", list(values)),
+        Literal::None => "none".into(),
+        Literal::Unknown(value) => format!("unknown({value:?})"),
+    }
+}
+
+fn visibility(visibility: &Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "",
+        Visibility::Private => "private ",
+    }
+}
+
+fn parameters(parameters: &[Parameter]) -> String {
+    parameters.iter().map(parameter).collect::<Vec<_>>().join(", ")
+}
+
+fn parameter(parameter: &Parameter) -> String {
+    let default_value = parameter
+        .default_value
+        .as_ref()
+        .map(|value| format!(" = {}", literal(value)))
+        .unwrap_or_default();
+    format!("{}{}: {}{default_value}", inline_attributes(&parameter.attributes), parameter.identifier, type_(&parameter.type_))
+}
+
+fn generics(generics: &Generics) -> String {
+    if generics.types.is_empty() {
+        Default::default()
+    } else {
+        format!("<{}>", types(&generics.types))
+    }
+}
+
+fn types(types: &[Type]) -> String {
+    types.iter().map(type_).collect::<Vec<_>>().join(", ")
+}
+
+fn path(path: &Path) -> String {
+    path
+        .segments
+        .iter()
+        .map(|segment| format!("{}{}", segment.identifier, generics(&segment.generics)))
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Prints the `Type` constructors with their dedicated syntax.
+fn type_(type_: &Type) -> String {
+    if let [segment] = type_.path.segments.as_slice() {
+        let identifier = &segment.identifier;
+        match segment.generics.types.as_slice() {
+            [inner] if *identifier == Identifier::option() => return format!("option<{}>", self::type_(inner)),
+            [inner] if *identifier == Identifier::vector() => return format!("list<{}>", self::type_(inner)),
+            [inner] if *identifier == Identifier::constant_reference() => return format!("&{}", self::type_(inner)),
+            [inner] if *identifier == Identifier::mutable_reference() => return format!("&mut {}", self::type_(inner)),
+            [inputs, output] if *identifier == Identifier::function() && inputs.is_tuple() && inputs.path.segments.len() == 1 => {
+                let inputs = types(&inputs.path.last().generics.types);
+                let output = if *output == Type::void() {
+                    Default::default()
+                } else {
+                    format!(" -> {}", self::type_(output))
+                };
+                return format!("func({inputs}){output}");
+            },
+            inner if *identifier == Identifier::tuple() => return format!("tuple<{}>", types(inner)),
+            _ => (),
+        }
+    }
+    path(&type_.path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LidlParser;
+    use ligen_idl::{Enumeration, Named, Parameter, PathSegment, Structure, TypeAlias, Union, Variant, Version};
+
+    fn library() -> Library {
+        let documentation = |text: &str| Attribute::from(Named::new("doc", format!(" {text}")));
+        let point = TypeDefinition {
+            attributes: vec![documentation("A point."), Named::new("name", "point").into()].into(),
+            identifier: "Point".into(),
+            generics: Type::from("T").into(),
+            interfaces: vec!["Clone".into()],
+            definition: KindDefinition::Structure(Structure { fields: vec![
+                Field { identifier: Some("x".into()), type_: "T".into(), ..Default::default() },
+                Field { identifier: Some("y".into()), type_: "T".into(), visibility: Visibility::Private, ..Default::default() },
+            ] }),
+            ..Default::default()
+        };
+        let pair = TypeDefinition {
+            identifier: "Pair".into(),
+            definition: KindDefinition::Structure(Structure { fields: vec![
+                Field { type_: Type::i32(), ..Default::default() },
+                Field { type_: Type::tuple(vec![Type::f32(), Type::string()]), ..Default::default() },
+            ] }),
+            ..Default::default()
+        };
+        let color = TypeDefinition {
+            identifier: "Color".into(),
+            definition: KindDefinition::Enumeration(Enumeration { variants: vec![
+                Variant { identifier: "Red".into(), ..Default::default() },
+                Variant { identifier: "Green".into(), attributes: vec![documentation("Green.")].into() },
+            ] }),
+            ..Default::default()
+        };
+        let alias = TypeDefinition {
+            identifier: "Points".into(),
+            definition: KindDefinition::TypeAlias(TypeAlias { type_: Type::vector(Type::from("Point")) }),
+            ..Default::default()
+        };
+        let value = TypeDefinition {
+            identifier: "Value".into(),
+            definition: KindDefinition::Union(Union { fields: vec![
+                Field { identifier: Some("integer".into()), type_: Type::u32(), ..Default::default() },
+                Field { identifier: Some("float".into()), type_: Type::f32(), ..Default::default() },
+            ] }),
+            ..Default::default()
+        };
+        let distance = Function {
+            synchrony: Synchrony::Asynchronous,
+            identifier: "distance".into(),
+            inputs: vec![
+                Parameter { identifier: "a".into(), type_: Type::constant_reference(Type::from("Point")), ..Default::default() },
+                Parameter { identifier: "scale".into(), type_: Type::f32(), default_value: Some(Literal::Float(1.0)), ..Default::default() },
+            ],
+            output: Some(Type::option(Type::f32())),
+            ..Default::default()
+        };
+        let shape = Interface {
+            identifier: "Shape".into(),
+            interfaces: vec![Path::from("geometry::Drawable")],
+            objects: vec![Object { attributes: ligen_idl::Group::new("ligen", Named::new("name", "sides")).into(), mutability: Mutability::Constant, identifier: "SIDES".into(), type_: Type::u32(), literal: Literal::UnsignedInteger(4), ..Default::default() }],
+            types: vec![TypeDefinition {
+                identifier: "Vertex".into(),
+                interfaces: vec!["Clone".into()],
+                definition: KindDefinition::TypeAlias(TypeAlias { type_: Type::opaque() }),
+                ..Default::default()
+            }],
+            functions: vec![Function {
+                identifier: "new".into(),
+                inputs: vec![Parameter { identifier: "callback".into(), type_: Type::function(vec![Type::i32()], Type::boolean()), ..Default::default() }],
+                output: Some("Shape".into()),
+                ..Default::default()
+            }],
+            methods: vec![
+                Method { identifier: "area".into(), mutability: Mutability::Constant, output: Some(Type::f64()), ..Default::default() },
+                Method { identifier: "consume".into(), mutability: Mutability::Constant, receiver: Receiver::Owned, ..Default::default() },
+                Method { identifier: "share".into(), mutability: Mutability::Constant, receiver: Receiver::Boxed(Type::from(Path::from(PathSegment::new("Arc", Type::from("Shape"))))), ..Default::default() },
+                Method { identifier: "scale".into(), mutability: Mutability::Mutable, receiver: Receiver::Mutable, inputs: vec![Parameter { identifier: "factor".into(), type_: Type::f64(), ..Default::default() }], ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let internal = Module {
+            identifier: "internal".into(),
+            visibility: Visibility::Private,
+            objects: vec![Object { mutability: Mutability::Mutable, identifier: "NAME".into(), type_: Type::string(), literal: Literal::String("a \"name\"\n".into()), ..Default::default() }],
+            ..Default::default()
+        };
+        let root_module = Module {
+            identifier: "geometry".into(),
+            attributes: vec![Attribute::from(ligen_idl::Group::new("ligen", Attributes::from(Attribute::from(ligen_idl::Group::from("ignore")))))].into(),
+            imports: vec![Import { path: "std::fmt::Display".into(), ..Default::default() }],
+            types: vec![point, pair, color, alias, value],
+            functions: vec![distance],
+            interfaces: vec![shape],
+            modules: vec![internal],
+            ..Default::default()
+        };
+        let mut library = Library { identifier: "geometry".into(), root_module, ..Default::default() };
+        library.metadata.version = Version::new(1, 2, 3);
+        library.metadata.summary = "Geometry primitives.".into();
+        library
+    }
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        let library = library();
+        let text = LidlGenerator::new().generate(&library, &Config::default())?;
+        let parsed = LidlParser::new().parse(&text, &Config::default())?;
+        assert_eq!(parsed, library, "{text}");
+        assert_eq!(LidlGenerator::new().generate(&parsed, &Config::default())?, text);
+        Ok(())
+    }
+
+    #[test]
+    fn syntax() -> Result<()> {
+        let text = LidlGenerator::new().generate(&library(), &Config::default())?;
+        assert!(text.starts_with("/// Geometry primitives.\n#[ligen(ignore)]\nlibrary geometry@1.2.3;\n"));
+        assert!(text.contains("async func distance(a: &Point, scale: f32 = 1.0) -> option<f32>;"));
+        assert!(text.contains("record Pair(i32, tuple<f32, String>);"));
+        assert!(text.contains("    func new(callback: func(i32) -> Boolean) -> Shape;"));
+        assert!(text.contains("    func scale(mut self, factor: f64);"));
+        assert!(text.contains("    func consume(move self);"));
+        assert!(text.contains("    func share(self: Arc<Shape>);"));
+        assert!(text.contains("union Value {\n    integer: u32,\n    float: f32,\n}"));
+        assert!(text.contains("    type Vertex: Clone;"));
+        assert!(text.contains("    #[ligen(name = \"sides\")]\n    const SIDES: u32 = 4u64;"));
+        assert!(text.contains("private module internal {\n    var NAME: String = \"a \\\"name\\\"\\n\";\n}"));
+        Ok(())
+    }
+}
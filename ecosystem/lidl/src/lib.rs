@@ -0,0 +1,57 @@
+//! Ligen textual IDL (`.lidl`).
+//!
+//! A concise, hand-writable syntax for a `Library`, with a parser into the IDL and a generator
+//! that pretty-prints it back out.
+//!
+//! ```text
+//! /// Geometry primitives.
+//! library geometry@0.1.0;
+//!
+//! use std::fmt::Display;
+//!
+//! const ORIGIN: f32 = 0.0;
+//!
+//! /// A point in space.
+//! #[ligen(ignore), name = "point"]
+//! record Point<T>: Clone + Debug {
+//!     x: T,
+//!     private y: T,
+//! }
+//! record Pair(i32, f32);
+//! enum Color { Red, Green, Blue }
+//! type Points = list<Point<f32>>;
+//! union Value { integer: u32, float: f32 }
+//!
+//! async func distance(a: &Point<f32>, b: &Point<f32>, scale: f32 = 1.0) -> option<f32>;
+//!
+//! interface Shape: Drawable {
+//!     const SIDES: u32 = 4;
+//!     type Vertex: Clone;
+//!     func new(callback: func(i32) -> bool) -> Shape;
+//!     func area(self) -> f64;
+//!     func scale(mut self, factor: f64);
+//!     func into_points(move self) -> Points;
+//!     func share(self: Arc<Shape>);
+//! }
+//!
+//! private module internal {
+//!     var COUNTER: u64 = 0u64;
+//! }
+//! ```
+//!
+//! Items are public unless marked `private`. Attributes use the `Attributes` syntax and `///`
+//! comments are `doc` attributes. `option<T>`, `list<T>`, `tuple<A, B>`, `func(A) -> B`, `&T` and
+//! `&mut T` map to the corresponding `Type` constructors, and any other type is a `Path`.
+//! Functions taking `self` are methods and are only allowed in interfaces, and so are type aliases
+//! without a type, the associated types without a default. `self` is borrowed, `mut self` mutably
+//! borrowed, `move self` owned and `self: T` behind the pointer `T`. Unsuffixed integers are signed, a `u`
+//! suffix makes them unsigned.
+//!
+//! Of the library metadata, only the version and the summary (the header's documentation) are
+//! represented.
+
+pub mod parser;
+pub mod generator;
+
+pub use parser::*;
+pub use generator::*;
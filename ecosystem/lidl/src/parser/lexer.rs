@@ -0,0 +1,252 @@
+//! Tokenizer.
+
+use ligen_idl::Literal;
+use ligen_transformer::prelude::*;
+
+/// Multi-character punctuation comes first so that it wins over its prefixes.
+const PUNCTUATION: [&str; 17] = [
+    "::", "->", ";", ":", ",", "=", "<", ">", "(", ")", "{", "}", "[", "]", "#", "&", "+",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TokenKind {
+    Identifier(String),
+    Literal(Literal),
+    Punctuation(&'static str),
+    /// `@1.2.3`, without the `@`.
+    Version(String),
+    /// `/// text`, without the `///`.
+    Documentation(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+pub(crate) fn syntax_error(source: &str, start: usize, end: usize, message: impl Into<String>) -> Error {
+    Diagnostic::error("lidl-syntax", message)
+        .with_span(Span::from_offsets(source, start, end))
+        .into()
+}
+
+pub(crate) fn tokenize(source: &str) -> Result<Vec<Token>> {
+    Lexer { source, position: 0 }.tokenize()
+}
+
+struct Lexer<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl Lexer<'_> {
+    fn tokenize(mut self) -> Result<Vec<Token>> {
+        let mut tokens = Vec::new();
+        while let Some(character) = self.skip_trivia()? {
+            let start = self.position;
+            let kind = if self.rest().starts_with("///") && !self.rest().starts_with("////") {
+                self.position += 3;
+                TokenKind::Documentation(self.take_while(|character| character != '\n').to_string())
+            } else if character.is_alphabetic() || character == '_' {
+                match self.take_while(|character| character.is_alphanumeric() || character == '_') {
+                    "true" => TokenKind::Literal(Literal::Boolean(true)),
+                    "false" => TokenKind::Literal(Literal::Boolean(false)),
+                    "none" => TokenKind::Literal(Literal::None),
+                    identifier => TokenKind::Identifier(identifier.to_string()),
+                }
+            } else if character.is_ascii_digit() || (character == '-' && self.rest()[1..].starts_with(|character: char| character.is_ascii_digit())) {
+                TokenKind::Literal(self.number()?)
+            } else if character == '"' {
+                TokenKind::Literal(Literal::String(self.quoted('"')?))
+            } else if character == '\'' {
+                let value = self.quoted('\'')?;
+                let mut characters = value.chars();
+                match (characters.next(), characters.next()) {
+                    (Some(character), None) => TokenKind::Literal(Literal::Character(character)),
+                    _ => return Err(self.error(start, "Character literals must have exactly one character.")),
+                }
+            } else if character == '@' {
+                self.position += 1;
+                let version = self.take_while(|character| character.is_alphanumeric() || matches!(character, '.' | '+' | '-'));
+                TokenKind::Version(version.to_string())
+            } else if let Some(punctuation) = PUNCTUATION.iter().find(|punctuation| self.rest().starts_with(**punctuation)) {
+                self.position += punctuation.len();
+                TokenKind::Punctuation(*punctuation)
+            } else {
+                return Err(self.error(start, format!("Unexpected character `{character}`.")));
+            };
+            let end = self.position;
+            tokens.push(Token { kind, start, end });
+        }
+        Ok(tokens)
+    }
+
+    fn rest(&self) -> &str {
+        &self.source[self.position..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn error(&self, start: usize, message: impl Into<String>) -> Error {
+        syntax_error(self.source, start, self.position.max(start + 1).min(self.source.len()), message)
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &str {
+        let start = self.position;
+        while let Some(character) = self.peek() {
+            if !predicate(character) {
+                break;
+            }
+            self.position += character.len_utf8();
+        }
+        &self.source[start..self.position]
+    }
+
+    /// Skips whitespace and comments, returning the next character. Documentation comments aren't
+    /// trivia.
+    fn skip_trivia(&mut self) -> Result<Option<char>> {
+        loop {
+            self.take_while(char::is_whitespace);
+            let rest = self.rest();
+            if rest.starts_with("//") && !(rest.starts_with("///") && !rest.starts_with("////")) {
+                self.take_while(|character| character != '\n');
+            } else if rest.starts_with("/*") {
+                let start = self.position;
+                let end = rest
+                    .find("*/")
+                    .ok_or_else(|| self.error(start, "Unterminated block comment."))?;
+                self.position += end + 2;
+            } else {
+                return Ok(self.peek());
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Literal> {
+        let start = self.position;
+        let negative = self.peek() == Some('-');
+        if negative {
+            self.position += 1;
+        }
+        self.take_while(|character| character.is_ascii_digit() || character == '_');
+        let mut float = false;
+        if self.rest().starts_with('.') && self.rest()[1..].starts_with(|character: char| character.is_ascii_digit()) {
+            float = true;
+            self.position += 1;
+            self.take_while(|character| character.is_ascii_digit() || character == '_');
+        }
+        if self.rest().starts_with(['e', 'E']) {
+            let exponent = self.rest()[1..].trim_start_matches(['+', '-']);
+            if exponent.starts_with(|character: char| character.is_ascii_digit()) {
+                float = true;
+                self.position += self.rest().len() - exponent.len();
+                self.take_while(|character| character.is_ascii_digit());
+            }
+        }
+        let digits = self.source[start..self.position].replace('_', "");
+        let suffix = self.take_while(|character| character.is_alphanumeric() || character == '_');
+        let literal = match suffix.chars().next() {
+            Some('f') => digits.parse().ok().map(Literal::Float),
+            Some('u') if !float => digits.parse().ok().map(Literal::UnsignedInteger),
+            Some('i') | None if !float => digits.parse().ok().map(Literal::Integer),
+            None => digits.parse().ok().map(Literal::Float),
+            _ => None,
+        };
+        literal.ok_or_else(|| self.error(start, format!("Invalid number `{}`.", &self.source[start..self.position])))
+    }
+
+    fn quoted(&mut self, quote: char) -> Result<String> {
+        let start = self.position;
+        self.position += 1;
+        let mut value = String::new();
+        loop {
+            let character = self.peek().ok_or_else(|| self.error(start, "Unterminated literal."))?;
+            self.position += character.len_utf8();
+            match character {
+                character if character == quote => return Ok(value),
+                '\\' => {
+                    let escaped = self.peek().ok_or_else(|| self.error(start, "Unterminated literal."))?;
+                    self.position += escaped.len_utf8();
+                    let character = match escaped {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        '0' => '\0',
+                        '\\' | '"' | '\'' => escaped,
+                        'u' => self.unicode_escape(start)?,
+                        _ => return Err(self.error(start, format!("Unknown escape `\\{escaped}`."))),
+                    };
+                    value.push(character);
+                }
+                character => value.push(character),
+            }
+        }
+    }
+
+    /// `\u{1F600}`, after the `\u`.
+    fn unicode_escape(&mut self, start: usize) -> Result<char> {
+        let code = self
+            .rest()
+            .strip_prefix('{')
+            .and_then(|rest| rest.split_once('}'))
+            .map(|(code, _)| code.to_string())
+            .ok_or_else(|| self.error(start, "Invalid unicode escape."))?;
+        self.position += code.len() + 2;
+        u32::from_str_radix(&code, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error(start, "Invalid unicode escape."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source)
+            .unwrap()
+            .into_iter()
+            .map(|token| token.kind)
+            .collect()
+    }
+
+    #[test]
+    fn literals() {
+        assert_eq!(kinds(r#"1 -2 3u64 1.5 -2e3 "a\"b\u{e9}" 'c' true none"#), vec![
+            TokenKind::Literal(Literal::Integer(1)),
+            TokenKind::Literal(Literal::Integer(-2)),
+            TokenKind::Literal(Literal::UnsignedInteger(3)),
+            TokenKind::Literal(Literal::Float(1.5)),
+            TokenKind::Literal(Literal::Float(-2000.0)),
+            TokenKind::Literal(Literal::String("a\"b\u{e9}".into())),
+            TokenKind::Literal(Literal::Character('c')),
+            TokenKind::Literal(Literal::Boolean(true)),
+            TokenKind::Literal(Literal::None),
+        ]);
+    }
+
+    #[test]
+    fn comments() {
+        assert_eq!(kinds("// comment\n/// docs\n/* block */ a::b -> c@1.0.0"), vec![
+            TokenKind::Documentation(" docs".into()),
+            TokenKind::Identifier("a".into()),
+            TokenKind::Punctuation("::"),
+            TokenKind::Identifier("b".into()),
+            TokenKind::Punctuation("->"),
+            TokenKind::Identifier("c".into()),
+            TokenKind::Version("1.0.0".into()),
+        ]);
+    }
+
+    #[test]
+    fn error_span() {
+        let error = tokenize("a\n  $").unwrap_err();
+        let Error::Diagnostic(diagnostic) = error else { panic!("Expected a diagnostic.") };
+        assert_eq!(diagnostic.span, Some(Span::new(2, 2, 2, 3)));
+    }
+}
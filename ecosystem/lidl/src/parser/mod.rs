@@ -0,0 +1,694 @@
+//! Textual IDL parser.
+
+mod lexer;
+
+use lexer::{syntax_error, tokenize, Token, TokenKind};
+use ligen_idl::{
+    Attribute, Attributes, Enumeration, Field, Function, Generics, Group, Identifier, Import,
+    Interface, KindDefinition, Library, Literal, Method, Metadata, Module, Mutability, Named,
+    Object, Parameter, Path, PathSegment, Receiver, Structure, Synchrony, Type, TypeAlias, TypeDefinition,
+    Union, Variant, Version, Visibility,
+};
+use ligen_transformer::prelude::*;
+
+/// Textual IDL parser.
+#[derive(Default)]
+pub struct LidlParser;
+
+impl LidlParser {
+    /// Creates a new parser.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Parses a library. `identifier` is used if the source has no `library` header.
+    pub fn parse_library(&self, source: &str, identifier: Identifier) -> Result<Library> {
+        let tokens = tokenize(source)?;
+        Cursor { source, tokens, position: 0 }.library(identifier)
+    }
+}
+
+impl Parser<Library> for LidlParser {
+    fn parse(&self, input: impl AsRef<str>, _config: &Config) -> Result<Library> {
+        self.parse_library(input.as_ref(), Identifier::new("library"))
+    }
+}
+
+impl Transformer<&std::path::Path, Library> for LidlParser {
+    fn transform(&self, input: &std::path::Path, _config: &Config) -> Result<Library> {
+        let source = std::fs::read_to_string(input)?;
+        let identifier = input
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(Identifier::new)
+            .unwrap_or_else(|| Identifier::new("library"));
+        self.parse_library(&source, identifier).map_err(|error| match error {
+            Error::Diagnostic(mut diagnostic) => {
+                diagnostic.span = diagnostic.span.map(|span| span.with_file(input.display().to_string()));
+                Error::Diagnostic(diagnostic)
+            },
+            error => error,
+        })
+    }
+
+    fn name(&self) -> &str {
+        "Ligen IDL Parser"
+    }
+}
+
+/// Documentation, attributes and visibility preceding an item.
+struct Prefix {
+    attributes: Attributes,
+    visibility: Visibility,
+    start: usize,
+}
+
+/// An interface or module member.
+enum Member {
+    Import(Import),
+    Object(Object),
+    Type(TypeDefinition),
+    Function(Function),
+    Method(Method),
+    Interface(Interface),
+    Module(Module),
+}
+
+struct Cursor<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Cursor<'_> {
+    fn library(mut self, identifier: Identifier) -> Result<Library> {
+        let mut identifier = identifier;
+        let mut metadata = Metadata::default();
+        let mut root_module = Module::default();
+        let prefix = self.prefix()?;
+        let prefix = if self.is_identifier("library") {
+            self.advance();
+            identifier = self.identifier()?;
+            if let Some(TokenKind::Version(version)) = self.peek_kind() {
+                let version = version.clone();
+                self.advance();
+                metadata.version = Version::try_from(version.as_str())
+                    .map_err(|error| self.error_before(error.to_string()))?;
+            }
+            self.expect(";")?;
+            let (summary, attributes) = split_documentation(prefix.attributes);
+            metadata.summary = summary;
+            root_module.attributes = attributes;
+            None
+        } else if prefix.attributes.is_empty() && prefix.visibility == Visibility::Public {
+            None
+        } else {
+            Some(prefix)
+        };
+        root_module.identifier = identifier.clone();
+        self.module_body(&mut root_module, prefix, None)?;
+        Ok(Library { identifier, metadata, root_module })
+    }
+
+    fn module_body(&mut self, module: &mut Module, mut prefix: Option<Prefix>, terminator: Option<&str>) -> Result<()> {
+        loop {
+            if prefix.is_none() {
+                match (self.peek_kind(), terminator) {
+                    (None, None) => return Ok(()),
+                    (None, Some(terminator)) => return Err(self.error_here(format!("Expected `{terminator}`."))),
+                    (Some(TokenKind::Punctuation(punctuation)), Some(terminator)) if *punctuation == terminator => {
+                        self.advance();
+                        return Ok(());
+                    },
+                    _ => (),
+                }
+            }
+            let prefix = match prefix.take() {
+                Some(prefix) => prefix,
+                None => self.prefix()?,
+            };
+            let start = prefix.start;
+            match self.member(prefix)? {
+                Member::Import(import) => module.imports.push(import),
+                Member::Object(object) => module.objects.push(object),
+                Member::Type(TypeDefinition { definition: KindDefinition::TypeAlias(TypeAlias { type_ }), .. }) if type_ == Type::opaque() => return Err(syntax_error(self.source, start, self.previous_end(), "Type aliases without a type are only allowed in interfaces.")),
+                Member::Type(type_) => module.types.push(type_),
+                Member::Function(function) => module.functions.push(function),
+                Member::Interface(interface) => module.interfaces.push(interface),
+                Member::Module(sub_module) => module.modules.push(sub_module),
+                Member::Method(_) => return Err(syntax_error(self.source, start, self.previous_end(), "Functions taking `self` are only allowed in interfaces.")),
+            }
+        }
+    }
+
+    fn member(&mut self, prefix: Prefix) -> Result<Member> {
+        let keyword = self.identifier()?;
+        match keyword.name.as_str() {
+            "use" => self.import(prefix).map(Member::Import),
+            "const" => self.object(prefix, Mutability::Constant).map(Member::Object),
+            "var" => self.object(prefix, Mutability::Mutable).map(Member::Object),
+            "record" => self.record(prefix).map(Member::Type),
+            "enum" => self.enumeration(prefix).map(Member::Type),
+            "union" => self.union(prefix).map(Member::Type),
+            "type" => self.type_alias(prefix).map(Member::Type),
+            "func" => self.function(prefix, Synchrony::Synchronous),
+            "async" => {
+                self.expect_identifier("func")?;
+                self.function(prefix, Synchrony::Asynchronous)
+            },
+            "interface" => self.interface(prefix).map(Member::Interface),
+            "module" => {
+                let identifier = self.identifier()?;
+                self.expect("{")?;
+                let attributes = prefix.attributes;
+                let visibility = prefix.visibility;
+                let mut module = Module { attributes, visibility, identifier, ..Default::default() };
+                self.module_body(&mut module, None, Some("}"))?;
+                Ok(Member::Module(module))
+            },
+            _ => Err(self.error_before(format!("Expected an item, found `{keyword}`."))),
+        }
+    }
+
+    /// Documentation, attributes and visibility.
+    fn prefix(&mut self) -> Result<Prefix> {
+        let start = self.peek().map(|token| token.start).unwrap_or(self.source.len());
+        let mut attributes = Attributes::default();
+        loop {
+            match self.peek_kind() {
+                Some(TokenKind::Documentation(documentation)) => {
+                    let documentation = documentation.clone();
+                    self.advance();
+                    attributes.push(Named::new("doc", documentation).into());
+                },
+                Some(TokenKind::Punctuation("#")) => {
+                    self.advance();
+                    self.expect("[")?;
+                    attributes.extend(self.attributes("]")?.attributes);
+                },
+                _ => break,
+            }
+        }
+        let visibility = if self.is_identifier("private") && !self.is_punctuation_at(1, ":") {
+            self.advance();
+            Visibility::Private
+        } else {
+            Visibility::Public
+        };
+        Ok(Prefix { attributes, visibility, start })
+    }
+
+    /// Comma-separated attributes up to `terminator`.
+    fn attributes(&mut self, terminator: &str) -> Result<Attributes> {
+        let mut attributes = Attributes::default();
+        while !self.eat(terminator) {
+            attributes.push(self.attribute()?);
+            if !self.eat(",") {
+                self.expect(terminator)?;
+                break;
+            }
+        }
+        Ok(attributes)
+    }
+
+    fn attribute(&mut self) -> Result<Attribute> {
+        if let Some(TokenKind::Literal(_)) = self.peek_kind() {
+            return self.literal().map(Attribute::Literal);
+        }
+        let path = self.path(false)?;
+        if self.eat("=") {
+            Ok(Named::new(path, self.literal()?).into())
+        } else if self.eat("(") {
+            Ok(Group::new(path, self.attributes(")")?).into())
+        } else {
+            Ok(Group::from(path).into())
+        }
+    }
+
+    fn literal(&mut self) -> Result<Literal> {
+        match self.peek_kind() {
+            Some(TokenKind::Literal(literal)) => {
+                let literal = literal.clone();
+                self.advance();
+                Ok(literal)
+            },
+            Some(TokenKind::Punctuation("(")) => {
+                self.advance();
+                self.literals(")").map(Literal::Tuple)
+            },
+            Some(TokenKind::Punctuation("[")) => {
+                self.advance();
+                self.literals("]").map(Literal::Array)
+            },
+            Some(TokenKind::Identifier(identifier)) if identifier == "unknown" => {
+                self.advance();
+                self.expect("(")?;
+                let literal = self.literal()?;
+                self.expect(")")?;
+                match literal {
+                    Literal::String(value) => Ok(Literal::Unknown(value)),
+                    _ => Err(self.error_before("Expected a string.")),
+                }
+            },
+            _ => Err(self.error_here("Expected a literal.")),
+        }
+    }
+
+    fn literals(&mut self, terminator: &str) -> Result<Vec<Literal>> {
+        let mut literals = Vec::new();
+        while !self.eat(terminator) {
+            literals.push(self.literal()?);
+            if !self.eat(",") {
+                self.expect(terminator)?;
+                break;
+            }
+        }
+        Ok(literals)
+    }
+
+    fn import(&mut self, prefix: Prefix) -> Result<Import> {
+        let path = self.path(false)?;
+        let renaming = if self.eat_identifier("as") {
+            Some(self.identifier()?)
+        } else {
+            None
+        };
+        self.expect(";")?;
+        let attributes = prefix.attributes;
+        let visibility = prefix.visibility;
+        Ok(Import { attributes, visibility, path, renaming })
+    }
+
+    fn object(&mut self, prefix: Prefix, mutability: Mutability) -> Result<Object> {
+        let identifier = self.identifier()?;
+        self.expect(":")?;
+        let type_ = self.type_()?;
+        self.expect("=")?;
+        let literal = self.literal()?;
+        self.expect(";")?;
+        let attributes = prefix.attributes;
+        let visibility = prefix.visibility;
+        Ok(Object { attributes, visibility, mutability, identifier, type_, literal })
+    }
+
+    /// Generics and implemented interfaces of a type definition.
+    fn type_definition(&mut self, prefix: Prefix, definition: impl FnOnce(&mut Self) -> Result<KindDefinition>) -> Result<TypeDefinition> {
+        let identifier = self.identifier()?;
+        let generics = self.generics()?;
+        let interfaces = self.interfaces()?;
+        let definition = definition(self)?;
+        let attributes = prefix.attributes;
+        let visibility = prefix.visibility;
+        Ok(TypeDefinition { attributes, visibility, identifier, generics, interfaces, definition })
+    }
+
+    fn record(&mut self, prefix: Prefix) -> Result<TypeDefinition> {
+        self.type_definition(prefix, |cursor| {
+            let mut fields = Vec::new();
+            if cursor.is_punctuation_at(0, "{") {
+                fields = cursor.named_fields()?;
+            } else {
+                if cursor.eat("(") {
+                    while !cursor.eat(")") {
+                        let prefix = cursor.prefix()?;
+                        fields.push(cursor.field(prefix, None)?);
+                        if !cursor.eat(",") {
+                            cursor.expect(")")?;
+                            break;
+                        }
+                    }
+                }
+                cursor.expect(";")?;
+            }
+            Ok(KindDefinition::Structure(Structure { fields }))
+        })
+    }
+
+    fn union(&mut self, prefix: Prefix) -> Result<TypeDefinition> {
+        self.type_definition(prefix, |cursor| {
+            let fields = cursor.named_fields()?;
+            Ok(KindDefinition::Union(Union { fields }))
+        })
+    }
+
+    /// `{ a: A, b: B }`
+    fn named_fields(&mut self) -> Result<Vec<Field>> {
+        let mut fields = Vec::new();
+        self.expect("{")?;
+        while !self.eat("}") {
+            let prefix = self.prefix()?;
+            let identifier = Some(self.identifier()?);
+            self.expect(":")?;
+            fields.push(self.field(prefix, identifier)?);
+            if !self.eat(",") {
+                self.expect("}")?;
+                break;
+            }
+        }
+        Ok(fields)
+    }
+
+    fn field(&mut self, prefix: Prefix, identifier: Option<Identifier>) -> Result<Field> {
+        let type_ = self.type_()?;
+        let attributes = prefix.attributes;
+        let visibility = prefix.visibility;
+        Ok(Field { attributes, visibility, identifier, type_ })
+    }
+
+    fn enumeration(&mut self, prefix: Prefix) -> Result<TypeDefinition> {
+        self.type_definition(prefix, |cursor| {
+            let mut variants = Vec::new();
+            cursor.expect("{")?;
+            while !cursor.eat("}") {
+                let attributes = cursor.prefix()?.attributes;
+                let identifier = cursor.identifier()?;
+                variants.push(Variant { attributes, identifier });
+                if !cursor.eat(",") {
+                    cursor.expect("}")?;
+                    break;
+                }
+            }
+            Ok(KindDefinition::Enumeration(Enumeration { variants }))
+        })
+    }
+
+    /// `type A = B;`, or `type A;` for an associated type without a default, aliasing an opaque
+    /// type.
+    fn type_alias(&mut self, prefix: Prefix) -> Result<TypeDefinition> {
+        self.type_definition(prefix, |cursor| {
+            if cursor.eat(";") {
+                return Ok(KindDefinition::TypeAlias(TypeAlias { type_: Type::opaque() }));
+            }
+            cursor.expect("=")?;
+            let type_ = cursor.type_()?;
+            cursor.expect(";")?;
+            Ok(KindDefinition::TypeAlias(TypeAlias { type_ }))
+        })
+    }
+
+    fn function(&mut self, prefix: Prefix, synchrony: Synchrony) -> Result<Member> {
+        let identifier = self.identifier()?;
+        self.expect("(")?;
+        let receiver = self.receiver()?;
+        let mut inputs = Vec::new();
+        let mut open = receiver.is_none() || self.eat(",");
+        while open && !self.is_punctuation_at(0, ")") {
+            inputs.push(self.parameter()?);
+            open = self.eat(",");
+        }
+        self.expect(")")?;
+        let output = if self.eat("->") {
+            Some(self.type_()?)
+        } else {
+            None
+        };
+        self.expect(";")?;
+        let attributes = prefix.attributes;
+        let visibility = prefix.visibility;
+        let body = ();
+        Ok(match receiver {
+            Some((mutability, receiver)) => Member::Method(Method { attributes, visibility, synchrony, mutability, receiver, identifier, inputs, output, body }),
+            None => Member::Function(Function { attributes, visibility, synchrony, identifier, inputs, output, body }),
+        })
+    }
+
+    /// `self`, `mut self`, `move self`, `move mut self`, `self: T` or `mut self: T`, if a method's
+    /// parameters start with one.
+    fn receiver(&mut self) -> Result<Option<(Mutability, Receiver)>> {
+        let owned = self.is_identifier("move");
+        let mutable = self.is_identifier_at(usize::from(owned), "mut");
+        let offset = usize::from(owned) + usize::from(mutable);
+        if !self.is_identifier_at(offset, "self") {
+            return Ok(None);
+        }
+        for _ in 0..=offset {
+            self.advance();
+        }
+        let mutability = if mutable { Mutability::Mutable } else { Mutability::Constant };
+        let receiver = if owned {
+            Receiver::Owned
+        } else if self.eat(":") {
+            Receiver::Boxed(self.type_()?)
+        } else if mutable {
+            Receiver::Mutable
+        } else {
+            Receiver::Shared
+        };
+        Ok(Some((mutability, receiver)))
+    }
+
+    fn parameter(&mut self) -> Result<Parameter> {
+        let attributes = self.prefix()?.attributes;
+        let identifier = self.identifier()?;
+        self.expect(":")?;
+        let type_ = self.type_()?;
+        let default_value = if self.eat("=") {
+            Some(self.literal()?)
+        } else {
+            None
+        };
+        Ok(Parameter { attributes, identifier, type_, default_value })
+    }
+
+    fn interface(&mut self, prefix: Prefix) -> Result<Interface> {
+        let identifier = self.identifier()?;
+        let interfaces = self.interfaces()?;
+        self.expect("{")?;
+        let attributes = prefix.attributes;
+        let visibility = prefix.visibility;
+        let mut interface = Interface { attributes, visibility, identifier, interfaces, ..Default::default() };
+        while !self.eat("}") {
+            if self.peek().is_none() {
+                return Err(self.error_here("Expected `}`."));
+            }
+            let prefix = self.prefix()?;
+            let start = prefix.start;
+            match self.member(prefix)? {
+                Member::Object(object) => interface.objects.push(object),
+                Member::Type(type_) if matches!(type_.definition, KindDefinition::TypeAlias(_)) => interface.types.push(type_),
+                Member::Function(function) => interface.functions.push(function),
+                Member::Method(method) => interface.methods.push(method),
+                _ => return Err(syntax_error(self.source, start, self.previous_end(), "Interfaces can only have constants, variables, associated types and functions.")),
+            }
+        }
+        Ok(interface)
+    }
+
+    /// `: A + b::C`
+    fn interfaces(&mut self) -> Result<Vec<Path>> {
+        let mut interfaces = Vec::new();
+        if self.eat(":") {
+            interfaces.push(self.path(true)?);
+            while self.eat("+") {
+                interfaces.push(self.path(true)?);
+            }
+        }
+        Ok(interfaces)
+    }
+
+    fn generics(&mut self) -> Result<Generics> {
+        let mut types = Vec::new();
+        if self.eat("<") {
+            while !self.eat(">") {
+                types.push(self.type_()?);
+                if !self.eat(",") {
+                    self.expect(">")?;
+                    break;
+                }
+            }
+        }
+        Ok(Generics { types })
+    }
+
+    fn types(&mut self, terminator: &str) -> Result<Vec<Type>> {
+        let mut types = Vec::new();
+        while !self.eat(terminator) {
+            types.push(self.type_()?);
+            if !self.eat(",") {
+                self.expect(terminator)?;
+                break;
+            }
+        }
+        Ok(types)
+    }
+
+    fn type_(&mut self) -> Result<Type> {
+        if self.eat("&") {
+            let mutability = if self.eat_identifier("mut") {
+                Mutability::Mutable
+            } else {
+                Mutability::Constant
+            };
+            return Ok(Type::reference(mutability, self.type_()?));
+        }
+        if self.is_punctuation_at(1, "<") || self.is_punctuation_at(1, "(") {
+            let constructor = match self.peek_kind() {
+                Some(TokenKind::Identifier(identifier)) => identifier.clone(),
+                _ => Default::default(),
+            };
+            match constructor.as_str() {
+                "option" | "list" => {
+                    self.advance();
+                    let start = self.previous_end();
+                    self.expect("<")?;
+                    let mut types = self.types(">")?;
+                    if types.len() != 1 {
+                        return Err(syntax_error(self.source, start, self.previous_end(), format!("`{constructor}` takes exactly one type.")));
+                    }
+                    let type_ = types.remove(0);
+                    return Ok(if constructor == "option" { Type::option(type_) } else { Type::vector(type_) });
+                },
+                "tuple" => {
+                    self.advance();
+                    self.expect("<")?;
+                    return Ok(Type::tuple(self.types(">")?));
+                },
+                "func" => {
+                    self.advance();
+                    self.expect("(")?;
+                    let inputs = self.types(")")?;
+                    let output = if self.eat("->") {
+                        self.type_()?
+                    } else {
+                        Type::void()
+                    };
+                    return Ok(Type::function(inputs, output));
+                },
+                _ => (),
+            }
+        }
+        self.path(true).map(Type::from)
+    }
+
+    /// `a::b::C`, optionally with generics in each segment.
+    fn path(&mut self, generics: bool) -> Result<Path> {
+        let mut segments = Vec::new();
+        loop {
+            let identifier = self.identifier()?;
+            let generics = if generics {
+                self.generics()?
+            } else {
+                Default::default()
+            };
+            segments.push(PathSegment { identifier, generics });
+            if !self.eat("::") {
+                break;
+            }
+        }
+        Ok(Path { segments })
+    }
+
+    fn identifier(&mut self) -> Result<Identifier> {
+        match self.peek_kind() {
+            Some(TokenKind::Identifier(identifier)) => {
+                let identifier = Identifier::new(identifier.clone());
+                self.advance();
+                Ok(identifier)
+            },
+            _ => Err(self.error_here("Expected an identifier.")),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn peek_kind(&self) -> Option<&TokenKind> {
+        self.peek().map(|token| &token.kind)
+    }
+
+    fn advance(&mut self) {
+        self.position += 1;
+    }
+
+    fn previous_end(&self) -> usize {
+        self.position
+            .checked_sub(1)
+            .and_then(|position| self.tokens.get(position))
+            .map(|token| token.end)
+            .unwrap_or_default()
+    }
+
+    fn is_identifier(&self, name: &str) -> bool {
+        self.is_identifier_at(0, name)
+    }
+
+    fn is_identifier_at(&self, offset: usize, name: &str) -> bool {
+        matches!(self.tokens.get(self.position + offset), Some(Token { kind: TokenKind::Identifier(identifier), .. }) if identifier == name)
+    }
+
+    fn is_punctuation_at(&self, offset: usize, punctuation: &str) -> bool {
+        matches!(self.tokens.get(self.position + offset), Some(Token { kind: TokenKind::Punctuation(found), .. }) if *found == punctuation)
+    }
+
+    fn eat(&mut self, punctuation: &str) -> bool {
+        let found = self.is_punctuation_at(0, punctuation);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn eat_identifier(&mut self, name: &str) -> bool {
+        let found = self.is_identifier(name);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn expect(&mut self, punctuation: &str) -> Result<()> {
+        if self.eat(punctuation) {
+            Ok(())
+        } else {
+            Err(self.error_here(format!("Expected `{punctuation}`.")))
+        }
+    }
+
+    fn expect_identifier(&mut self, name: &str) -> Result<()> {
+        if self.eat_identifier(name) {
+            Ok(())
+        } else {
+            Err(self.error_here(format!("Expected `{name}`.")))
+        }
+    }
+
+    /// Error at the next token.
+    fn error_here(&self, message: impl Into<String>) -> Error {
+        match self.peek() {
+            Some(token) => syntax_error(self.source, token.start, token.end, message),
+            None => syntax_error(self.source, self.source.len(), self.source.len(), format!("{} Found the end of the input.", message.into())),
+        }
+    }
+
+    /// Error at the previous token.
+    fn error_before(&self, message: impl Into<String>) -> Error {
+        let start = self.position
+            .checked_sub(1)
+            .and_then(|position| self.tokens.get(position))
+            .map(|token| token.start)
+            .unwrap_or_default();
+        syntax_error(self.source, start, self.previous_end(), message)
+    }
+}
+
+/// Splits the leading `doc` attributes off, joining them into a single string.
+fn split_documentation(attributes: Attributes) -> (String, Attributes) {
+    let (documentation, attributes): (Vec<_>, Vec<_>) = attributes
+        .attributes
+        .into_iter()
+        .partition(|attribute| {
+            attribute
+                .as_named()
+                .map(|named| named.path == Path::from("doc"))
+                .unwrap_or(false)
+        });
+    let documentation = documentation
+        .iter()
+        .filter_map(|attribute| attribute.as_named())
+        .filter_map(|named| named.literal.as_string())
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    (documentation, Attributes { attributes })
+}
@@ -2,7 +2,7 @@ pub mod validator;
 
 use ligen::transformer::prelude::*;
 use rustpython_parser::ast::{StmtAsyncFunctionDef, StmtFunctionDef};
-use ligen::idl::{Method, Mutability, Function};
+use ligen::idl::{Method, Mutability, Receiver, Function};
 use crate::parser::PythonParser;
 use crate::prelude::*;
 
@@ -16,8 +16,9 @@ impl<Body: Default> Transformer<WithSource<StmtFunctionDef>, Method<Body>> for P
         let inputs = function.inputs;
         let output = function.output;
         let mutability = Mutability::Mutable;
+        let receiver = Receiver::Mutable;
         let body = Default::default();
-        Ok(Method { attributes, visibility, synchrony, mutability, identifier, inputs, output, body })
+        Ok(Method { attributes, visibility, synchrony, mutability, receiver, identifier, inputs, output, body })
     }
 }
 
@@ -31,7 +32,8 @@ impl<Body: Default> Transformer<WithSource<StmtAsyncFunctionDef>, Method<Body>>
         let inputs = function.inputs;
         let output = function.output;
         let mutability = Mutability::Mutable;
+        let receiver = Receiver::Mutable;
         let body = Default::default();
-        Ok(Method { attributes, visibility, synchrony, mutability, identifier, inputs, output, body })
+        Ok(Method { attributes, visibility, synchrony, mutability, receiver, identifier, inputs, output, body })
     }
 }
@@ -12,7 +12,8 @@ impl MethodValidator {
     }
 }
 
-impl Validator<Method> for MethodValidator {
+#[allow(deprecated)]
+impl ValidatorMut<Method> for MethodValidator {
     fn validate(&self, _method: &mut Method, _config: &Config) -> Result<()> {
         Ok(())
     }
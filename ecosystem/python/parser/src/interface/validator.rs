@@ -29,7 +29,8 @@ impl InterfaceValidator {
     }
 }
 
-impl Validator<Interface> for InterfaceValidator {
+#[allow(deprecated)]
+impl ValidatorMut<Interface> for InterfaceValidator {
     fn validate(&self, interface: &mut Interface, config: &Config) -> Result<()> {
         self.validate_constructor(interface, config)?;
         Ok(())
@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use ligen::idl::Module;
+use ligen::idl::{Merge, MergePolicy, Module};
 use rustpython_parser::ast::ModModule;
 use crate::parser::PythonParser;
 
@@ -57,14 +57,21 @@ impl Transformer<Directory<'_>, Module> for PythonParser {
                 .map(String::from)
                 .unwrap_or_default();
             if extension == "py" || extension == "pyi" || path.is_dir() {
-                if let Ok(module) = self.transform(SubPath(path.as_path()), config) {
-                    if let Some(existing) = modules
-                        .iter_mut()
-                        .find(|existing| existing.identifier == module.identifier)
-                    {
-                        existing.join(module)
-                    } else {
-                        modules.push(module);
+                match self.transform(SubPath(path.as_path()), config) {
+                    Ok(module) => {
+                        if let Some(existing) = modules
+                            .iter_mut()
+                            .find(|existing| existing.identifier == module.identifier)
+                        {
+                            existing.merge(module, MergePolicy::PreferLeft)?
+                        } else {
+                            modules.push(module);
+                        }
+                    },
+                    Err(error) => {
+                        let span = Span::new(1, 0, 1, 0).with_file(path.display().to_string());
+                        let diagnostic = Diagnostic::warning("skipped-module", error.to_string()).with_span(span);
+                        config.diagnostics().report(diagnostic);
                     }
                 }
             }
@@ -48,6 +48,7 @@ impl Transformer<&Expr, Object> for ObjectParser {
             let type_ = Default::default();
             let literal = Default::default();
             Ok(Object {
+                attributes: Default::default(),
                 visibility,
                 identifier,
                 mutability,
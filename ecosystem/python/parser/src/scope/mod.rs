@@ -1,7 +1,7 @@
 mod import_parser;
 mod scope_type;
 
-use rustpython_parser::ast::{Arguments, Expr, Stmt};
+use rustpython_parser::ast::{Arguments, Expr, Ranged, Stmt};
 use ligen::idl::{Interface, Object, Function, Method, Import, TypeDefinition};
 use crate::{prelude::*, parser::PythonParserConfig};
 
@@ -44,6 +44,13 @@ impl PythonParser {
         deduplicated_objects
     }
 
+    fn report_skipped(&self, statements: &WithSource<&[Stmt]>, statement: &Stmt, code: &str, symbol: Option<&str>, error: Error, config: &Config) {
+        let span = statements.span(statement.start().to_usize(), statement.end().to_usize());
+        let mut diagnostic = Diagnostic::warning(code, error.to_string()).with_span(span);
+        diagnostic.symbol = symbol.map(String::from);
+        config.diagnostics().report(diagnostic);
+    }
+
     fn parse_sub_scopes(&self, statements: &WithSource<&[Stmt]>, config: &Config) -> Result<Vec<Scope>> {
         let mut sub_scopes = Vec::new();
         for statement in statements.ast {
@@ -101,13 +108,15 @@ impl PythonParser {
             if self.is_static_method(statements.sub(statement)) {
                 match statement {
                     Stmt::FunctionDef(function) => {
-                        if let Ok(function) = self.function_parser.transform(statements.sub(function.clone()), config) {
-                            functions.push(function)
+                        match self.function_parser.transform(statements.sub(function.clone()), config) {
+                            Ok(function) => functions.push(function),
+                            Err(error) => self.report_skipped(statements, statement, "skipped-function", Some(function.name.as_str()), error, config)
                         }
                     },
                     Stmt::AsyncFunctionDef(function) => {
-                        if let Ok(function) = self.function_parser.transform(statements.sub(function.clone()), config) {
-                            functions.push(function)
+                        match self.function_parser.transform(statements.sub(function.clone()), config) {
+                            Ok(function) => functions.push(function),
+                            Err(error) => self.report_skipped(statements, statement, "skipped-function", Some(function.name.as_str()), error, config)
                         }
                     },
                     _ => (),
@@ -123,13 +132,15 @@ impl PythonParser {
             if !self.is_static_method(statements.sub(statement)) {
                 match statement {
                     Stmt::FunctionDef(function) => {
-                        if let Ok(function) = self.transform(statements.sub(function.clone()), config) {
-                            methods.push(function)
+                        match self.transform(statements.sub(function.clone()), config) {
+                            Ok(method) => methods.push(method),
+                            Err(error) => self.report_skipped(statements, statement, "skipped-method", Some(function.name.as_str()), error, config)
                         }
                     },
                     Stmt::AsyncFunctionDef(function) => {
-                        if let Ok(function) = self.transform(statements.sub(function.clone()), config) {
-                            methods.push(function)
+                        match self.transform(statements.sub(function.clone()), config) {
+                            Ok(method) => methods.push(method),
+                            Err(error) => self.report_skipped(statements, statement, "skipped-method", Some(function.name.as_str()), error, config)
                         }
                     },
                     _ => (),
@@ -145,7 +156,7 @@ impl PythonParser {
             if let Stmt::ClassDef(class) = statement {
                 match self.type_definition_parser.transform(statements.sub(class.clone()), config) {
                     Ok(type_definition) => types.push(type_definition),
-                    Err(error) => todo!("Failed to parse type definition: {:?}", error)
+                    Err(error) => self.report_skipped(statements, statement, "skipped-type", Some(class.name.as_str()), error, config)
                 }
             }
         }
@@ -156,8 +167,9 @@ impl PythonParser {
         let mut interfaces = Vec::new();
         for statement in statements.ast {
             if let Stmt::ClassDef(class) = statement {
-                if let Ok(interface) = self.transform(WithSource::new(&statements.source, class), config) {
-                    interfaces.push(interface)
+                match self.transform(WithSource::new(&statements.source, class), config) {
+                    Ok(interface) => interfaces.push(interface),
+                    Err(error) => self.report_skipped(statements, statement, "skipped-interface", Some(class.name.as_str()), error, config)
                 }
             }
         }
@@ -169,13 +181,15 @@ impl PythonParser {
         for statement in statements.ast {
             match statement {
                 Stmt::Import(import) => {
-                    if let Ok(parsed_imports) = self.transform(statements.sub(import), config) {
-                        imports.extend(parsed_imports);
+                    match self.transform(statements.sub(import), config) {
+                        Ok(parsed_imports) => imports.extend(parsed_imports),
+                        Err(error) => self.report_skipped(statements, statement, "skipped-import", None, error, config)
                     }
                 },
                 Stmt::ImportFrom(import) => {
-                    if let Ok(parsed_imports) = self.transform(statements.sub(import), config) {
-                        imports.extend(parsed_imports);
+                    match self.transform(statements.sub(import), config) {
+                        Ok(parsed_imports) => imports.extend(parsed_imports),
+                        Err(error) => self.report_skipped(statements, statement, "skipped-import", None, error, config)
                     }
                 },
                 _ => ()
@@ -191,18 +205,21 @@ impl PythonParser {
             for statement in statements.ast {
                 match statement {
                     Stmt::Assign(assign) => {
-                        if let Ok(more_objects) = self.object_parser.transform(assign, config) {
-                            objects.extend(more_objects)
+                        match self.object_parser.transform(assign, config) {
+                            Ok(more_objects) => objects.extend(more_objects),
+                            Err(error) => self.report_skipped(statements, statement, "skipped-object", None, error, config)
                         }
                     },
                     Stmt::AnnAssign(assign) => {
-                        if let Ok(object) = self.object_parser.transform(statements.sub(assign), config) {
-                            objects.push(object)
+                        match self.object_parser.transform(statements.sub(assign), config) {
+                            Ok(object) => objects.push(object),
+                            Err(error) => self.report_skipped(statements, statement, "skipped-object", None, error, config)
                         }
                     },
                     Stmt::AugAssign(assign) => {
-                        if let Ok(object) = self.object_parser.transform(assign, config) {
-                            objects.push(object)
+                        match self.object_parser.transform(assign, config) {
+                            Ok(object) => objects.push(object),
+                            Err(error) => self.report_skipped(statements, statement, "skipped-object", None, error, config)
                         }
                     },
                     _ => ()
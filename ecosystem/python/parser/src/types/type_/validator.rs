@@ -10,7 +10,8 @@ impl TypeValidator {
     }
 }
 
-impl Validator<Type> for TypeValidator {
+#[allow(deprecated)]
+impl ValidatorMut<Type> for TypeValidator {
     fn validate(&self, type_: &mut Type, config: &Config) -> Result<()> {
         let name = type_.path.last().identifier.name.as_str();
         if config.get(Path::from("ligen::python::as-opaque").join(name)).is_some() {
@@ -13,7 +13,11 @@ impl FileGenerator<&Library> for RustClientGenerator {
         PathBuf::from("rust-client")
     }
 
-    fn generate_files(&self, library: &Library, file_set: &mut FileSet) -> Result<()> {
+    fn validation(&self) -> Validation {
+        Validation::default().with(InvalidIdentifiers::rust())
+    }
+
+    fn generate_files(&self, library: &Library, file_set: &mut FileSet, _config: &Config) -> Result<()> {
         let folder = PathBuf::from(&library.identifier.to_kebab_case().to_string());
 
         // Cargo.toml
@@ -57,6 +57,9 @@ impl Generator<&TypeDefinition, String> for RustTypeDefinitionGenerator {
                 let type_ = self.generate_type(&alias.type_, config)?;
                 sections.push(format!("pub type {} = {};", identifier, type_));
             }
+            KindDefinition::Union(_) => {
+                return Err(Error::Message(format!("Unions can't be serialized by clients: {}", identifier)));
+            }
         }
         
         Ok(sections.join("\n"))
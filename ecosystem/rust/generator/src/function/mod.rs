@@ -0,0 +1,91 @@
+use ligen_transformer::prelude::*;
+use ligen_idl::{Function, Identifier, Method, Parameter, Receiver, Synchrony, Type, Visibility};
+
+use crate::source::{documentation, source};
+use crate::{RustIdentifierGenerator, RustTypeGenerator};
+
+/// Generates functions and methods with `todo!()` bodies.
+#[derive(Default)]
+pub struct RustFunctionGenerator {
+    identifier_generator: RustIdentifierGenerator,
+    type_generator: RustTypeGenerator,
+}
+
+impl RustFunctionGenerator {
+    fn parameters(&self, parameters: &[Parameter], config: &Config) -> Result<Vec<String>> {
+        parameters
+            .iter()
+            .map(|parameter| {
+                let identifier = self.identifier_generator.generate(&parameter.identifier, config)?;
+                let type_ = source(&self.type_generator.generate(&parameter.type_, config)?);
+                Ok(format!("{identifier}: {type_}"))
+            })
+            .collect()
+    }
+
+    fn output(&self, output: &Option<Type>, config: &Config) -> Result<String> {
+        match output {
+            Some(type_) => Ok(format!(" -> {}", source(&self.type_generator.generate(type_, config)?))),
+            None => Ok(String::new()),
+        }
+    }
+
+    fn receiver(&self, receiver: &Receiver, config: &Config) -> Result<String> {
+        match receiver {
+            Receiver::Shared => Ok("&self".to_string()),
+            Receiver::Mutable => Ok("&mut self".to_string()),
+            Receiver::Owned => Ok("self".to_string()),
+            Receiver::Boxed(type_) => Ok(format!("self: {}", source(&self.type_generator.generate(type_, config)?))),
+        }
+    }
+
+    fn declaration(&self, synchrony: &Synchrony, identifier: &Identifier, receiver: Option<&Receiver>, inputs: &[Parameter], output: &Option<Type>, config: &Config) -> Result<String> {
+        let synchrony = match synchrony {
+            Synchrony::Synchronous => "",
+            Synchrony::Asynchronous => "async ",
+        };
+        let identifier = self.identifier_generator.generate(identifier, config)?;
+        let mut parameters = receiver
+            .map(|receiver| self.receiver(receiver, config))
+            .transpose()?
+            .into_iter()
+            .collect::<Vec<_>>();
+        parameters.extend(self.parameters(inputs, config)?);
+        let output = self.output(output, config)?;
+        Ok(format!("{synchrony}fn {identifier}({}){output}", parameters.join(", ")))
+    }
+
+    fn definition(mut lines: Vec<String>, visibility: &Visibility, declaration: String) -> String {
+        lines.push(format!("{}{declaration} {{", crate::source::visibility(visibility)));
+        lines.push("    todo!()".to_string());
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    /// Generates the function's signature, as declared in traits.
+    pub fn signature(&self, function: &Function, config: &Config) -> Result<String> {
+        let mut lines = documentation(&function.attributes);
+        lines.push(format!("{};", self.declaration(&function.synchrony, &function.identifier, None, &function.inputs, &function.output, config)?));
+        Ok(lines.join("\n"))
+    }
+
+    /// Generates the method's signature, as declared in traits.
+    pub fn method_signature(&self, method: &Method, config: &Config) -> Result<String> {
+        let mut lines = documentation(&method.attributes);
+        lines.push(format!("{};", self.declaration(&method.synchrony, &method.identifier, Some(&method.receiver), &method.inputs, &method.output, config)?));
+        Ok(lines.join("\n"))
+    }
+
+    /// Generates the method with a `todo!()` body.
+    pub fn method(&self, method: &Method, config: &Config) -> Result<String> {
+        let declaration = self.declaration(&method.synchrony, &method.identifier, Some(&method.receiver), &method.inputs, &method.output, config)?;
+        Ok(Self::definition(documentation(&method.attributes), &method.visibility, declaration))
+    }
+}
+
+impl Generator<&Function, String> for RustFunctionGenerator {
+    fn generate(&self, function: &Function, config: &Config) -> Result<String> {
+        let declaration = self.declaration(&function.synchrony, &function.identifier, None, &function.inputs, &function.output, config)?;
+        Ok(Self::definition(documentation(&function.attributes), &function.visibility, declaration))
+    }
+}
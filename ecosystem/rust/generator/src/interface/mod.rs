@@ -0,0 +1,97 @@
+use ligen_transformer::prelude::*;
+use ligen_idl::{Generics, Interface, Visibility};
+
+use crate::source::{documentation, indent, source, visibility};
+use crate::{RustFunctionGenerator, RustIdentifierGenerator, RustObjectGenerator, RustPathGenerator, RustTypeDefinitionGenerator};
+
+/// Generates interfaces as traits, or as the `impl` blocks of the types they are named after.
+/// The module generator picks the latter when the type is defined in the same module.
+#[derive(Default)]
+pub struct RustInterfaceGenerator {
+    identifier_generator: RustIdentifierGenerator,
+    path_generator: RustPathGenerator,
+    object_generator: RustObjectGenerator,
+    type_definition_generator: RustTypeDefinitionGenerator,
+    function_generator: RustFunctionGenerator,
+}
+
+impl RustInterfaceGenerator {
+    fn block(header: String, items: Vec<String>) -> String {
+        if items.is_empty() {
+            format!("{header} {{}}")
+        } else {
+            format!("{header} {{\n{}\n}}", indent(&items.join("\n\n")))
+        }
+    }
+
+    /// Generates the interface as an `impl` block of a type with the given generics: the
+    /// implementation of the trait in its `interfaces`, or the inherent one if it has none. The
+    /// items of a trait implementation have no visibility, and associated types are left out of
+    /// inherent ones, since inherent ones aren't stable Rust.
+    pub fn implementation(&self, interface: &Interface, generics: &Generics, config: &Config) -> Result<String> {
+        let trait_ = match interface.interfaces.as_slice() {
+            [] => None,
+            [trait_] => Some(source(&self.path_generator.generate(trait_, config)?)),
+            _ => return Err(Error::Message(format!("The implementation of {} has more than one trait.", interface.identifier))),
+        };
+        let mut interface = interface.clone();
+        if trait_.is_some() {
+            interface.objects.iter_mut().for_each(|object| object.visibility = Visibility::Private);
+            interface.types.iter_mut().for_each(|type_| type_.visibility = Visibility::Private);
+            interface.functions.iter_mut().for_each(|function| function.visibility = Visibility::Private);
+            interface.methods.iter_mut().for_each(|method| method.visibility = Visibility::Private);
+        } else {
+            interface.types.clear();
+        }
+        let mut items = Vec::new();
+        for type_ in &interface.types {
+            items.push(self.type_definition_generator.generate(type_, config)?);
+        }
+        for object in &interface.objects {
+            items.push(self.object_generator.generate(object, config)?);
+        }
+        for function in &interface.functions {
+            items.push(self.function_generator.generate(function, config)?);
+        }
+        for method in &interface.methods {
+            items.push(self.function_generator.method(method, config)?);
+        }
+        let identifier = self.identifier_generator.generate(&interface.identifier, config)?;
+        let generics = self.type_definition_generator.generics(generics, config)?;
+        let header = match trait_ {
+            Some(trait_) => format!("impl{generics} {trait_} for {identifier}{generics}"),
+            None => format!("impl{generics} {identifier}{generics}"),
+        };
+        let mut lines = documentation(&interface.attributes);
+        lines.push(Self::block(header, items));
+        Ok(lines.join("\n"))
+    }
+}
+
+/// Generates the interface as a trait.
+impl Generator<&Interface, String> for RustInterfaceGenerator {
+    fn generate(&self, interface: &Interface, config: &Config) -> Result<String> {
+        let mut items = Vec::new();
+        for object in &interface.objects {
+            items.push(self.object_generator.declaration(object, config)?);
+        }
+        for type_ in &interface.types {
+            let mut lines = documentation(&type_.attributes);
+            let identifier = self.identifier_generator.generate(&type_.identifier, config)?;
+            let bounds = self.type_definition_generator.bounds(&type_.interfaces, config)?;
+            lines.push(format!("type {identifier}{bounds};"));
+            items.push(lines.join("\n"));
+        }
+        for function in &interface.functions {
+            items.push(self.function_generator.signature(function, config)?);
+        }
+        for method in &interface.methods {
+            items.push(self.function_generator.method_signature(method, config)?);
+        }
+        let identifier = self.identifier_generator.generate(&interface.identifier, config)?;
+        let bounds = self.type_definition_generator.bounds(&interface.interfaces, config)?;
+        let mut lines = documentation(&interface.attributes);
+        lines.push(Self::block(format!("{}trait {identifier}{bounds}", visibility(&interface.visibility)), items));
+        Ok(lines.join("\n"))
+    }
+}
@@ -4,8 +4,21 @@ mod identifier;
 mod type_;
 mod path;
 mod literal;
+mod source;
+mod object;
+mod type_definition;
+mod function;
+mod interface;
+mod module;
+mod library;
 
 pub use identifier::*;
 pub use type_::*;
 pub use path::*;
 pub use literal::*;
+pub use object::*;
+pub use type_definition::*;
+pub use function::*;
+pub use interface::*;
+pub use module::*;
+pub use library::*;
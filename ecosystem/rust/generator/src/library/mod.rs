@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use ligen_transformer::prelude::*;
+use ligen_idl::{Library, Location, Module, Registry, Resolver};
+
+use crate::RustModuleGenerator;
+
+/// Generates a crate from a library, with a file for each module, `src/lib.rs` for the root one.
+/// Functions and methods are generated with `todo!()` bodies.
+#[derive(Default)]
+pub struct RustLibraryGenerator {
+    module_generator: RustModuleGenerator,
+}
+
+impl RustLibraryGenerator {
+    /// Generates the `file` of the module at `scope` and the ones of its sub-modules, which are
+    /// placed next to the root module's file, or in a folder named after theirs.
+    fn generate_module(&self, module: &Module, scope: &Location, resolver: &Resolver, file: PathBuf, file_set: &mut FileSet, config: &Config) -> Result<()> {
+        file_set.entry(file.clone()).write(self.module_generator.module(module, scope, resolver, config)?);
+        let directory = match scope.parent() {
+            Some(_) => file.with_extension(""),
+            None => file.parent().map(PathBuf::from).unwrap_or_default(),
+        };
+        for child in &module.modules {
+            let file = directory.join(format!("{}.rs", child.identifier));
+            self.generate_module(child, &scope.join(child.identifier.clone()), resolver, file, file_set, config)?;
+        }
+        Ok(())
+    }
+}
+
+impl FileGenerator<&Library> for RustLibraryGenerator {
+    fn base_path(&self) -> PathBuf {
+        PathBuf::from("rust")
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::default().with(InvalidIdentifiers::rust())
+    }
+
+    fn generate_files(&self, library: &Library, file_set: &mut FileSet, config: &Config) -> Result<()> {
+        let folder = PathBuf::from(library.identifier.to_kebab_case().to_string());
+
+        let cargo_toml = file_set.entry(folder.join("Cargo.toml"));
+        cargo_toml.write(format!(
+            r#"[package]
+name = "{}"
+version = "{}"
+edition = "2021"
+"#,
+            library.identifier.to_kebab_case(),
+            library.metadata.version
+        ));
+
+        let mut registry = Registry::new();
+        registry.libraries.insert(library.identifier.clone(), library.clone());
+        let resolver = Resolver::new(&registry);
+        let scope = Location::root(library.identifier.clone());
+        let file = folder.join("src").join("lib.rs");
+        self.generate_module(&library.root_module, &scope, &resolver, file, file_set, config)
+    }
+}
@@ -0,0 +1,247 @@
+use ligen_transformer::prelude::*;
+use ligen_idl::{Identifier, Import, Library, Location, Module, Path, Registry, Resolver, Type, TypeDefinition};
+
+use crate::source::{source, visibility};
+use crate::{RustFunctionGenerator, RustIdentifierGenerator, RustInterfaceGenerator, RustObjectGenerator, RustPathGenerator, RustTypeDefinitionGenerator};
+
+/// Generates the contents of a module's file. Its sub-modules are declared, but not generated.
+#[derive(Default)]
+pub struct RustModuleGenerator {
+    identifier_generator: RustIdentifierGenerator,
+    path_generator: RustPathGenerator,
+    object_generator: RustObjectGenerator,
+    type_definition_generator: RustTypeDefinitionGenerator,
+    interface_generator: RustInterfaceGenerator,
+    function_generator: RustFunctionGenerator,
+}
+
+impl RustModuleGenerator {
+    fn import(&self, import: &Import, config: &Config) -> Result<String> {
+        let path = source(&self.path_generator.generate(&import.path, config)?);
+        let renaming = match &import.renaming {
+            Some(renaming) => format!(" as {}", self.identifier_generator.generate(renaming, config)?),
+            None => String::new(),
+        };
+        Ok(format!("{}use {path}{renaming};", visibility(&import.visibility)))
+    }
+
+    /// Generates the module at `scope`, resolving the types its interfaces implement with
+    /// `resolver`.
+    pub fn module(&self, module: &Module, scope: &Location, resolver: &Resolver, config: &Config) -> Result<String> {
+        let mut sections = Vec::new();
+        let documentation = module
+            .attributes
+            .get_documentation()
+            .iter()
+            .flat_map(|documentation| documentation.lines().map(|line| format!("//! {line}")).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        sections.push(documentation.join("\n"));
+        let modules = module
+            .modules
+            .iter()
+            .map(|child| Ok(format!("{}mod {};", visibility(&child.visibility), self.identifier_generator.generate(&child.identifier, config)?)))
+            .collect::<Result<Vec<_>>>()?;
+        sections.push(modules.join("\n"));
+        let imports = module
+            .imports
+            .iter()
+            .map(|import| self.import(import, config))
+            .collect::<Result<Vec<_>>>()?;
+        sections.push(imports.join("\n"));
+        let objects = module
+            .objects
+            .iter()
+            .map(|object| self.object_generator.generate(object, config))
+            .collect::<Result<Vec<_>>>()?;
+        sections.push(objects.join("\n"));
+        for type_definition in &module.types {
+            sections.push(self.type_definition_generator.generate(type_definition, config)?);
+        }
+        for interface in &module.interfaces {
+            // Interfaces named after a type, here or anywhere else in the library, are its
+            // implementations, and so are the ones implementing a trait. The others are traits.
+            // Implementations only keep the last segment of the type's path, so one written as
+            // `impl super::Point` is looked for by name if it doesn't resolve from here.
+            let type_definition = resolver
+                .resolve_type(scope, &Type::from(Path::from(interface.identifier.clone())))
+                .or_else(|| find_type(&resolver.library(&scope.library)?.root_module, &interface.identifier));
+            sections.push(match type_definition {
+                Some(type_definition) => self.interface_generator.implementation(interface, &type_definition.generics, config)?,
+                None if !interface.interfaces.is_empty() => self.interface_generator.implementation(interface, &Default::default(), config)?,
+                None => self.interface_generator.generate(interface, config)?,
+            });
+        }
+        for function in &module.functions {
+            sections.push(self.function_generator.generate(function, config)?);
+        }
+        let sections = sections
+            .into_iter()
+            .filter(|section| !section.is_empty())
+            .collect::<Vec<_>>();
+        Ok(format!("{}\n", sections.join("\n\n")))
+    }
+}
+
+/// Finds the type definition named `identifier` in `module` or its sub-modules.
+fn find_type<'a>(module: &'a Module, identifier: &Identifier) -> Option<&'a TypeDefinition> {
+    module
+        .types
+        .iter()
+        .find(|type_definition| type_definition.identifier == *identifier)
+        .or_else(|| module.modules.iter().find_map(|child| find_type(child, identifier)))
+}
+
+impl Generator<&Module, String> for RustModuleGenerator {
+    /// Generates the module as the root of a library of its own.
+    fn generate(&self, module: &Module, config: &Config) -> Result<String> {
+        let library = Library { identifier: module.identifier.clone(), root_module: module.clone(), ..Default::default() };
+        let scope = Location::root(library.identifier.clone());
+        let mut registry = Registry::new();
+        registry.libraries.insert(library.identifier.clone(), library);
+        self.module(module, &scope, &Resolver::new(&registry), config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ligen_idl::{Attributes, Field, Function, Interface, KindDefinition, Literal, Method, Mutability, Named, Object, Path, Receiver, Structure, Type, TypeDefinition};
+
+    use super::*;
+
+    #[test]
+    fn module() -> Result<()> {
+        let length = Method { identifier: "length".into(), receiver: Receiver::Shared, output: Some(Type::f32()), ..Default::default() };
+        let area = Method { identifier: "area".into(), receiver: Receiver::Shared, output: Some(Type::f32()), ..Default::default() };
+        let module = Module {
+            identifier: "shapes".into(),
+            objects: vec![Object { mutability: Mutability::Constant, identifier: "ORIGIN".into(), type_: Type::f32(), literal: Literal::Float(0.0), ..Default::default() }],
+            types: vec![TypeDefinition {
+                attributes: Attributes::from(Named::new("doc", "A point.")),
+                identifier: "Point".into(),
+                interfaces: vec![Path::from("Clone"), Path::from("Debug")],
+                definition: KindDefinition::Structure(Structure {
+                    fields: vec![
+                        Field { identifier: Some("x".into()), type_: Type::f32(), ..Default::default() },
+                        Field { identifier: Some("y".into()), type_: Type::f32(), ..Default::default() },
+                    ]
+                }),
+                ..Default::default()
+            }],
+            interfaces: vec![
+                Interface { identifier: "Point".into(), methods: vec![length], ..Default::default() },
+                Interface { identifier: "Shape".into(), methods: vec![area], ..Default::default() },
+            ],
+            functions: vec![Function::new("origin", vec![], Some(Type::from("Point")), ())],
+            ..Default::default()
+        };
+        let source = RustModuleGenerator::default().generate(&module, &Config::default())?;
+        assert_eq!(source, r#"pub const ORIGIN: f32 = 0.0;
+
+/// A point.
+#[derive(Clone, Debug)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    pub fn length(&self) -> f32 {
+        todo!()
+    }
+}
+
+pub trait Shape {
+    fn area(&self) -> f32;
+}
+
+pub fn origin() -> Point {
+    todo!()
+}
+"#);
+        Ok(())
+    }
+
+    #[test]
+    fn trait_implementation() -> Result<()> {
+        use ligen_idl::{PathSegment, TypeAlias};
+        let item = TypeDefinition { identifier: "Item".into(), definition: KindDefinition::TypeAlias(TypeAlias { type_: Type::u32() }), ..Default::default() };
+        let next = Method { identifier: "next".into(), receiver: Receiver::Mutable, output: Some(Type::from(Path::from(PathSegment::new("Option", Type::u32())))), ..Default::default() };
+        let module = Module {
+            identifier: "counters".into(),
+            types: vec![TypeDefinition { identifier: "Counter".into(), definition: KindDefinition::Structure(Structure::default()), ..Default::default() }],
+            interfaces: vec![Interface { identifier: "Counter".into(), interfaces: vec![Path::from("Iterator")], types: vec![item], methods: vec![next], ..Default::default() }],
+            ..Default::default()
+        };
+        let source = RustModuleGenerator::default().generate(&module, &Config::default())?;
+        assert_eq!(source, r#"pub struct Counter;
+
+impl Iterator for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        todo!()
+    }
+}
+"#);
+        Ok(())
+    }
+
+    #[test]
+    fn imported_type_implementation() -> Result<()> {
+        use ligen_idl::{Generics, Visibility};
+        let length = Method { identifier: "length".into(), receiver: Receiver::Shared, output: Some(Type::f32()), ..Default::default() };
+        let point = TypeDefinition {
+            identifier: "Point".into(),
+            generics: Generics { types: vec![Type::from("T")] },
+            definition: KindDefinition::Structure(Structure::default()),
+            ..Default::default()
+        };
+        let api = Module {
+            identifier: "api".into(),
+            imports: vec![Import { visibility: Visibility::Private, path: Path::from("crate::shapes::Point"), ..Default::default() }],
+            interfaces: vec![Interface { identifier: "Point".into(), methods: vec![length], ..Default::default() }],
+            ..Default::default()
+        };
+        let shapes = Module { identifier: "shapes".into(), types: vec![point], ..Default::default() };
+        let root_module = Module { identifier: "geometry".into(), modules: vec![shapes, api.clone()], ..Default::default() };
+        let library = Library { identifier: "geometry".into(), root_module, ..Default::default() };
+        let mut registry = Registry::new();
+        registry.libraries.insert(library.identifier.clone(), library);
+        let scope = Location::root("geometry").join("api");
+        let source = RustModuleGenerator::default().module(&api, &scope, &Resolver::new(&registry), &Config::default())?;
+        assert_eq!(source, r#"use crate::shapes::Point;
+
+impl<T> Point<T> {
+    pub fn length(&self) -> f32 {
+        todo!()
+    }
+}
+"#);
+        Ok(())
+    }
+
+    #[test]
+    fn external_type_implementation() -> Result<()> {
+        let area = Method { identifier: "area".into(), receiver: Receiver::Shared, output: Some(Type::f32()), ..Default::default() };
+        let module = Module {
+            identifier: "shapes".into(),
+            interfaces: vec![
+                Interface { identifier: "Shape".into(), methods: vec![area.clone()], ..Default::default() },
+                Interface { identifier: "String".into(), interfaces: vec![Path::from("Shape")], methods: vec![area], ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let source = RustModuleGenerator::default().generate(&module, &Config::default())?;
+        assert_eq!(source, r#"pub trait Shape {
+    fn area(&self) -> f32;
+}
+
+impl Shape for String {
+    fn area(&self) -> f32 {
+        todo!()
+    }
+}
+"#);
+        Ok(())
+    }
+}
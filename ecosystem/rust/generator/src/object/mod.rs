@@ -0,0 +1,57 @@
+use ligen_transformer::prelude::*;
+use ligen_idl::{Literal, Mutability, Object};
+
+use crate::source::{source, visibility};
+use crate::{RustIdentifierGenerator, RustLiteralGenerator, RustTypeGenerator};
+
+/// Generates constants and statics. Immutable statics are generated as constants.
+#[derive(Default)]
+pub struct RustObjectGenerator {
+    identifier_generator: RustIdentifierGenerator,
+    type_generator: RustTypeGenerator,
+    literal_generator: RustLiteralGenerator,
+}
+
+impl RustObjectGenerator {
+    /// Generates the object's value as an expression.
+    pub fn value(&self, literal: &Literal, config: &Config) -> Result<String> {
+        match literal {
+            Literal::Tuple(literals) => Ok(format!("({})", self.values(literals, config)?)),
+            Literal::Array(literals) => Ok(format!("[{}]", self.values(literals, config)?)),
+            Literal::None => Ok("None".to_string()),
+            Literal::Unknown(expression) => Ok(expression.clone()),
+            literal => Ok(source(&self.literal_generator.generate(literal, config)?)),
+        }
+    }
+
+    fn values(&self, literals: &[Literal], config: &Config) -> Result<String> {
+        let values = literals
+            .iter()
+            .map(|literal| self.value(literal, config))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(values.join(", "))
+    }
+
+    /// Generates the object's declaration, without its value if it's `None`, as in traits.
+    pub fn declaration(&self, object: &Object, config: &Config) -> Result<String> {
+        let identifier = self.identifier_generator.generate(&object.identifier, config)?;
+        let type_ = source(&self.type_generator.generate(&object.type_, config)?);
+        match object.literal {
+            Literal::None => Ok(format!("const {identifier}: {type_};")),
+            _ => Ok(format!("const {identifier}: {type_} = {};", self.value(&object.literal, config)?)),
+        }
+    }
+}
+
+impl Generator<&Object, String> for RustObjectGenerator {
+    fn generate(&self, object: &Object, config: &Config) -> Result<String> {
+        let keyword = match object.mutability {
+            Mutability::Constant => "const",
+            Mutability::Mutable => "static mut",
+        };
+        let identifier = self.identifier_generator.generate(&object.identifier, config)?;
+        let type_ = source(&self.type_generator.generate(&object.type_, config)?);
+        let value = self.value(&object.literal, config)?;
+        Ok(format!("{}{keyword} {identifier}: {type_} = {value};", visibility(&object.visibility)))
+    }
+}
@@ -0,0 +1,122 @@
+use ligen_idl::{Attributes, Visibility};
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::ToTokens;
+
+/// Renders tokens as Rust source, spaced the way `rustfmt` spaces types and paths, instead of
+/// with the space `TokenStream::to_string` puts between every token.
+pub(crate) fn source(tokens: &impl ToTokens) -> String {
+    render(tokens.to_token_stream())
+}
+
+/// A token of the rendered source: an identifier, literal or lifetime, an operator made of joint
+/// punctuation, like `::` and `->`, or a delimited group, already rendered.
+enum Atom {
+    Word(String),
+    Operator(String),
+    Group(Delimiter, String),
+}
+
+fn atoms(tokens: TokenStream) -> Vec<Atom> {
+    let mut atoms = Vec::new();
+    let mut operator = String::new();
+    let mut tokens = tokens.into_iter();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '\'' && punct.spacing() == Spacing::Joint => {
+                let lifetime = tokens.next().map(|token| token.to_string()).unwrap_or_default();
+                atoms.push(Atom::Word(format!("'{lifetime}")));
+            },
+            TokenTree::Punct(punct) => {
+                operator.push(punct.as_char());
+                if punct.spacing() == Spacing::Alone {
+                    atoms.push(Atom::Operator(std::mem::take(&mut operator)));
+                }
+            },
+            TokenTree::Ident(ident) => atoms.push(Atom::Word(ident.to_string())),
+            TokenTree::Literal(literal) => atoms.push(Atom::Word(literal.to_string())),
+            TokenTree::Group(group) => atoms.push(Atom::Group(group.delimiter(), render(group.stream()))),
+        }
+    }
+    if !operator.is_empty() {
+        atoms.push(Atom::Operator(operator));
+    }
+    atoms
+}
+
+/// Whether a space goes between `left` and `right`. `before` is the atom before `left`, which
+/// tells a unary `-` from a binary one.
+fn is_spaced(before: Option<&Atom>, left: &Atom, right: &Atom) -> bool {
+    match (left, right) {
+        (_, Atom::Operator(right)) if matches!(right.as_str(), "," | ";" | ":" | "::" | "?" | ".") || right.starts_with('>') => false,
+        (Atom::Operator(left), _) if matches!(left.as_str(), "&" | "&&" | "*" | "::" | "<" | "!" | "#" | ".") => false,
+        (Atom::Operator(left), _) if left == "-" => matches!(before, Some(Atom::Word(_) | Atom::Group(..))),
+        (Atom::Word(_), Atom::Operator(right)) => !matches!(right.as_str(), "<" | "::"),
+        (Atom::Word(_), Atom::Group(Delimiter::Parenthesis | Delimiter::Bracket, _)) => false,
+        _ => true,
+    }
+}
+
+fn render(tokens: TokenStream) -> String {
+    let atoms = atoms(tokens);
+    let mut result = String::new();
+    for (index, atom) in atoms.iter().enumerate() {
+        if index > 0 && is_spaced(index.checked_sub(2).map(|before| &atoms[before]), &atoms[index - 1], atom) {
+            result.push(' ');
+        }
+        match atom {
+            Atom::Word(word) | Atom::Operator(word) => result.push_str(word),
+            Atom::Group(delimiter, inner) => {
+                let (open, close) = match delimiter {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{ ", " }"),
+                    Delimiter::None => ("", ""),
+                };
+                result.push_str(&format!("{open}{inner}{close}"));
+            },
+        }
+    }
+    result
+}
+
+/// The documentation attributes as `///` lines.
+pub(crate) fn documentation(attributes: &Attributes) -> Vec<String> {
+    attributes
+        .get_documentation()
+        .iter()
+        .flat_map(|documentation| documentation.lines().map(|line| format!("/// {line}")).collect::<Vec<_>>())
+        .collect()
+}
+
+/// Indents every non-empty line by four spaces.
+pub(crate) fn indent(source: &str) -> String {
+    source
+        .lines()
+        .map(|line| if line.is_empty() { String::new() } else { format!("    {line}") })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The visibility as an item prefix.
+pub(crate) fn visibility(visibility: &Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "pub ",
+        Visibility::Private => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spacing() {
+        let type_: syn::Type = syn::parse_quote!(&'a mut Vec<Box<dyn Fn(u32, &[u8]) -> std::option::Option<(i32, *const u8)> + Send>>);
+        assert_eq!(source(&type_), "&'a mut Vec<Box<dyn Fn(u32, &[u8]) -> std::option::Option<(i32, *const u8)> + Send>>");
+        let bounds: syn::Type = syn::parse_quote!(fn(Iterator<Item = u32>) -> [f32; 4]);
+        assert_eq!(source(&bounds), "fn(Iterator<Item = u32>) -> [f32; 4]");
+        // Literals are kept as they are.
+        let literal: syn::Expr = syn::parse_quote!(-1 - " a :: b < c > ");
+        assert_eq!(source(&literal), "-1 - \" a :: b < c > \"");
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::Context;
 pub use ligen_transformer::prelude::*;
-use ligen_idl::Type;
+use ligen_idl::{Identifier, Type};
 
 use crate::RustPathGenerator;
 
@@ -11,6 +11,23 @@ pub struct RustTypeGenerator {
     path_generator: RustPathGenerator,
 }
 
+impl RustTypeGenerator {
+    fn generics(&self, type_: &Type, config: &Config) -> Result<Vec<syn::Type>> {
+        type_
+            .path
+            .last()
+            .generics
+            .types
+            .iter()
+            .map(|type_| self.generate(type_, config))
+            .collect()
+    }
+
+    fn tuple(elems: Vec<syn::Type>) -> syn::Type {
+        syn::Type::Tuple(syn::TypeTuple { paren_token: Default::default(), elems: elems.into_iter().collect() })
+    }
+}
+
 impl Generator<&Type, syn::Type> for RustTypeGenerator {
     fn generate(&self, type_: &Type, _config: &Config) -> Result<syn::Type> {
         if type_.is_mutable_reference() || type_.is_constant_reference() {
@@ -29,6 +46,40 @@ impl Generator<&Type, syn::Type> for RustTypeGenerator {
             let type_ = self.generate(type_, _config)?;
             let elem = Box::new(type_);
             Ok(syn::Type::Reference(syn::TypeReference { and_token, lifetime, mutability, elem }))    
+        } else if type_ == &Type::void() {
+            Ok(Self::tuple(Default::default()))
+        } else if type_.is_tuple() {
+            Ok(Self::tuple(self.generics(type_, _config)?))
+        } else if type_.path.last().identifier == Identifier::slice() {
+            let elem = self.generics(type_, _config)?.pop().context("Slice has no element type")?;
+            Ok(syn::Type::Slice(syn::TypeSlice { bracket_token: Default::default(), elem: Box::new(elem) }))
+        } else if type_.is_array() {
+            Err(Error::Message(format!("Array lengths aren't represented, so `{type_}` can't be generated")))
+        } else if type_.path.last().identifier == Identifier::function() {
+            let mut generics = self.generics(type_, _config)?;
+            let output = generics.pop().context("Function type has no output")?;
+            let inputs = match generics.pop().context("Function type has no inputs")? {
+                syn::Type::Tuple(tuple) => tuple.elems,
+                input => std::iter::once(input).collect(),
+            };
+            let output = match &output {
+                syn::Type::Tuple(tuple) if tuple.elems.is_empty() => syn::ReturnType::Default,
+                _ => syn::ReturnType::Type(Default::default(), Box::new(output)),
+            };
+            let inputs = inputs
+                .into_iter()
+                .map(|ty| syn::BareFnArg { attrs: Default::default(), name: None, ty })
+                .collect();
+            Ok(syn::Type::BareFn(syn::TypeBareFn {
+                lifetimes: None,
+                unsafety: None,
+                abi: None,
+                fn_token: Default::default(),
+                paren_token: Default::default(),
+                inputs,
+                variadic: None,
+                output,
+            }))
         } else {
             let qself = None;
             let path = self.path_generator.generate(&type_.path, _config)?;
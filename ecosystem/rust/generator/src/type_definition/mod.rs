@@ -0,0 +1,121 @@
+use ligen_transformer::prelude::*;
+use ligen_idl::{Field, Generics, KindDefinition, Path, TypeDefinition};
+
+use crate::source::{documentation, indent, source, visibility};
+use crate::{RustIdentifierGenerator, RustPathGenerator, RustTypeGenerator};
+
+/// Interfaces generated as `#[derive]`s.
+const DERIVABLE: [&str; 9] = ["Clone", "Copy", "Debug", "Default", "PartialEq", "Eq", "PartialOrd", "Ord", "Hash"];
+
+/// Generates structures, enumerations, type aliases and unions.
+#[derive(Default)]
+pub struct RustTypeDefinitionGenerator {
+    identifier_generator: RustIdentifierGenerator,
+    path_generator: RustPathGenerator,
+    type_generator: RustTypeGenerator,
+}
+
+impl RustTypeDefinitionGenerator {
+    /// Generates the generic parameters, e.g. `<T, U>`.
+    pub fn generics(&self, generics: &Generics, config: &Config) -> Result<String> {
+        if generics.types.is_empty() {
+            return Ok(String::new());
+        }
+        let types = generics
+            .types
+            .iter()
+            .map(|type_| Ok(source(&self.type_generator.generate(type_, config)?)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(format!("<{}>", types.join(", ")))
+    }
+
+    /// Generates the interfaces as bounds, e.g. `: Clone + Debug`.
+    pub fn bounds(&self, interfaces: &[Path], config: &Config) -> Result<String> {
+        if interfaces.is_empty() {
+            return Ok(String::new());
+        }
+        let interfaces = interfaces
+            .iter()
+            .map(|interface| Ok(source(&self.path_generator.generate(interface, config)?)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(format!(": {}", interfaces.join(" + ")))
+    }
+
+    fn fields(&self, fields: &[Field], config: &Config) -> Result<Vec<String>> {
+        fields
+            .iter()
+            .map(|field| {
+                let mut lines = documentation(&field.attributes);
+                let type_ = source(&self.type_generator.generate(&field.type_, config)?);
+                let visibility = visibility(&field.visibility);
+                let identifier = field.identifier.as_ref().ok_or("Named fields can't be mixed with unnamed ones.")?;
+                let identifier = self.identifier_generator.generate(identifier, config)?;
+                lines.push(format!("{visibility}{identifier}: {type_},"));
+                Ok(lines.join("\n"))
+            })
+            .collect()
+    }
+
+    fn body(&self, fields: &[Field], config: &Config) -> Result<String> {
+        let fields = self.fields(fields, config)?;
+        if fields.is_empty() {
+            Ok(" {}".to_string())
+        } else {
+            Ok(format!(" {{\n{}\n}}", indent(&fields.join("\n"))))
+        }
+    }
+}
+
+impl Generator<&TypeDefinition, String> for RustTypeDefinitionGenerator {
+    fn generate(&self, type_definition: &TypeDefinition, config: &Config) -> Result<String> {
+        let mut lines = documentation(&type_definition.attributes);
+        let derives = type_definition
+            .interfaces
+            .iter()
+            .map(|interface| interface.last().identifier.name.as_str())
+            .filter(|interface| DERIVABLE.contains(interface))
+            .collect::<Vec<_>>();
+        if !derives.is_empty() && !matches!(type_definition.definition, KindDefinition::TypeAlias(_)) {
+            lines.push(format!("#[derive({})]", derives.join(", ")));
+        }
+        let visibility = visibility(&type_definition.visibility);
+        let identifier = self.identifier_generator.generate(&type_definition.identifier, config)?;
+        let generics = self.generics(&type_definition.generics, config)?;
+        lines.push(match &type_definition.definition {
+            KindDefinition::Structure(structure) if structure.fields.is_empty() => {
+                format!("{visibility}struct {identifier}{generics};")
+            },
+            KindDefinition::Structure(structure) if structure.fields.iter().all(|field| field.identifier.is_none()) => {
+                let fields = structure
+                    .fields
+                    .iter()
+                    .map(|field| Ok(format!("{}{}", crate::source::visibility(&field.visibility), source(&self.type_generator.generate(&field.type_, config)?))))
+                    .collect::<Result<Vec<_>>>()?;
+                format!("{visibility}struct {identifier}{generics}({});", fields.join(", "))
+            },
+            KindDefinition::Structure(structure) => {
+                format!("{visibility}struct {identifier}{generics}{}", self.body(&structure.fields, config)?)
+            },
+            KindDefinition::Enumeration(enumeration) => {
+                let variants = enumeration
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        let mut lines = documentation(&variant.attributes);
+                        lines.push(format!("{},", self.identifier_generator.generate(&variant.identifier, config)?));
+                        Ok(lines.join("\n"))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                format!("{visibility}enum {identifier}{generics} {{\n{}\n}}", indent(&variants.join("\n")))
+            },
+            KindDefinition::TypeAlias(alias) => {
+                let type_ = source(&self.type_generator.generate(&alias.type_, config)?);
+                format!("{visibility}type {identifier}{generics} = {type_};")
+            },
+            KindDefinition::Union(union) => {
+                format!("{visibility}union {identifier}{generics}{}", self.body(&union.fields, config)?)
+            },
+        });
+        Ok(lines.join("\n"))
+    }
+}
@@ -1,12 +1,135 @@
 use ligen::parser::universal::PathParser;
 use ligen::prelude::*;
-use ligen::ir::{Block, Statement, Expression, BinaryExpression};
+use ligen::idl::{Identifier, Mutability};
+use ligen::ir::{
+    Assignment, BinaryExpression, Block, CallExpression, CastExpression, Expression, FieldExpression,
+    FieldValue, For, If, IndexExpression, Let, Loop, MethodCallExpression, Statement,
+    StructureExpression, UnaryExpression, While,
+};
 use quote::quote;
 
+use crate::{RustLiteralParser, RustTypeParser};
+
 
 #[derive(Default)]
 pub struct RustBlockParser {
     path_parser: PathParser,
+    literal_parser: RustLiteralParser,
+    type_parser: RustTypeParser,
+}
+
+impl RustBlockParser {
+    /// Parses the body of a function returning a value, whose tail expression, or the ones of the
+    /// `if` branches it ends with, is what it returns.
+    pub fn body(&self, block: syn::Block, config: &Config) -> Result<Block> {
+        let mut stmts = block.stmts;
+        let tail = match stmts.last() {
+            Some(syn::Stmt::Expr(_, None)) => stmts.pop(),
+            _ => None,
+        };
+        let mut statements = stmts
+            .into_iter()
+            .map(|stmt| self.transform(stmt, config))
+            .collect::<Result<Vec<Statement>>>()?;
+        if let Some(syn::Stmt::Expr(expr, None)) = tail {
+            statements.push(self.tail(expr, config)?);
+        }
+        Ok(Block::new(statements))
+    }
+
+    fn tail(&self, expr: syn::Expr, config: &Config) -> Result<Statement> {
+        match expr {
+            syn::Expr::If(if_) => self.if_(if_, config, true),
+            syn::Expr::Return(_) | syn::Expr::Assign(_) | syn::Expr::While(_) | syn::Expr::Loop(_)
+            | syn::Expr::ForLoop(_) | syn::Expr::Break(_) | syn::Expr::Continue(_) => self.statement(expr, config),
+            syn::Expr::Binary(ref binary) if assignment_operator(&binary.op).is_some() => self.statement(expr, config),
+            expr => Ok(Statement::return_(Some(self.transform(expr, config)?))),
+        }
+    }
+
+    fn statement(&self, expr: syn::Expr, config: &Config) -> Result<Statement> {
+        match expr {
+            syn::Expr::Return(expr) => {
+                if let Some(expr) = expr.expr {
+                    let expr = self.transform(expr, config)?;
+                    Ok(Statement::return_(Some(expr)))
+                } else {
+                    Ok(Statement::return_(None as Option<Expression>))
+                }
+            },
+            syn::Expr::Assign(assign) => {
+                let target = self.transform(assign.left, config)?;
+                let value = self.transform(assign.right, config)?;
+                Ok(Assignment::new(target, "=", value).into())
+            },
+            syn::Expr::Binary(binary) if assignment_operator(&binary.op).is_some() => {
+                let operator = assignment_operator(&binary.op).unwrap_or_default();
+                let target = self.transform(binary.left, config)?;
+                let value = self.transform(binary.right, config)?;
+                Ok(Assignment::new(target, operator, value).into())
+            },
+            syn::Expr::If(if_) => self.if_(if_, config, false),
+            syn::Expr::Loop(loop_) => {
+                let body: Block = self.transform(loop_.body, config)?;
+                Ok(Loop { body }.into())
+            },
+            syn::Expr::While(while_) => {
+                let condition = self.transform(while_.cond, config)?;
+                let body: Block = self.transform(while_.body, config)?;
+                Ok(While { condition, body }.into())
+            },
+            syn::Expr::ForLoop(for_) => {
+                let identifier = match *for_.pat {
+                    syn::Pat::Ident(pattern) => Identifier::from(pattern.ident.to_string()),
+                    pattern => return Err(anyhow::anyhow!("Unsupported loop pattern: {}", quote! { #pattern }).into()),
+                };
+                let (start, end, inclusive) = match *for_.expr {
+                    syn::Expr::Range(syn::ExprRange { start: Some(start), end: Some(end), limits, .. }) => {
+                        (self.transform(start, config)?, self.transform(end, config)?, matches!(limits, syn::RangeLimits::Closed(_)))
+                    },
+                    expr => return Err(anyhow::anyhow!("Only bounded ranges can be iterated: {}", quote! { #expr }).into()),
+                };
+                let body: Block = self.transform(for_.body, config)?;
+                Ok(For { identifier, start, end, inclusive, body }.into())
+            },
+            syn::Expr::Break(syn::ExprBreak { label: None, expr: None, .. }) => Ok(Statement::Break),
+            syn::Expr::Continue(syn::ExprContinue { label: None, .. }) => Ok(Statement::Continue),
+            expr => Ok(Statement::Expression(self.transform(expr, config)?)),
+        }
+    }
+
+    /// With `returns`, the tails of the branches are returned, as in a function body.
+    fn if_(&self, if_: syn::ExprIf, config: &Config, returns: bool) -> Result<Statement> {
+        let block = |block: syn::Block| -> Result<Block> {
+            if returns { self.body(block, config) } else { self.transform(block, config) }
+        };
+        let condition = self.transform(if_.cond, config)?;
+        let then = block(if_.then_branch)?;
+        let else_ = match if_.else_branch.map(|(_, else_)| *else_) {
+            Some(syn::Expr::Block(else_)) => Some(block(else_.block)?),
+            Some(syn::Expr::If(else_if)) => Some(Block::from(self.if_(else_if, config, returns)?)),
+            Some(expr) => return Err(anyhow::anyhow!("Unsupported else branch: {}", quote! { #expr }).into()),
+            None => None,
+        };
+        Ok(If::new(condition, then, else_).into())
+    }
+}
+
+/// The operator of a compound assignment, like `+=`.
+fn assignment_operator(operator: &syn::BinOp) -> Option<&'static str> {
+    Some(match operator {
+        syn::BinOp::AddAssign(_) => "+=",
+        syn::BinOp::SubAssign(_) => "-=",
+        syn::BinOp::MulAssign(_) => "*=",
+        syn::BinOp::DivAssign(_) => "/=",
+        syn::BinOp::RemAssign(_) => "%=",
+        syn::BinOp::BitXorAssign(_) => "^=",
+        syn::BinOp::BitAndAssign(_) => "&=",
+        syn::BinOp::BitOrAssign(_) => "|=",
+        syn::BinOp::ShlAssign(_) => "<<=",
+        syn::BinOp::ShrAssign(_) => ">>=",
+        _ => return None,
+    })
 }
 
 impl Transformer<syn::Block, ()> for RustBlockParser {
@@ -50,19 +173,24 @@ impl Transformer<syn::Block, Block> for RustBlockParser {
 impl Transformer<syn::Stmt, Statement> for RustBlockParser {
     fn transform(&self, stmt: syn::Stmt, config: &Config) -> Result<Statement> {
         match stmt {
-            syn::Stmt::Expr(expr, _) => {
-                match expr {
-                    syn::Expr::Return(expr) => {
-                        if let Some(expr) = expr.expr {
-                            let expr = self.transform(expr, config)?;
-                            Ok(Statement::return_(Some(expr)))
-                        } else {
-                            Ok(Statement::return_(None as Option<Expression>))
-                        }
-                    }
-                    _ => Err(anyhow::anyhow!("Unsupported expression").into()),
-                }
-            }
+            syn::Stmt::Local(syn::Local { pat, init, .. }) => {
+                let (pat, type_) = match pat {
+                    syn::Pat::Type(syn::PatType { pat, ty, .. }) => (*pat, Some(self.type_parser.transform(*ty, config)?)),
+                    pat => (pat, None),
+                };
+                let (mutability, ident) = match pat {
+                    syn::Pat::Ident(pattern) => (pattern.mutability, pattern.ident),
+                    pat => return Err(anyhow::anyhow!("Unsupported binding: {}", quote! { #pat }).into()),
+                };
+                let mutability = if mutability.is_some() { Mutability::Mutable } else { Mutability::Constant };
+                let value = match init {
+                    Some(syn::LocalInit { diverge: Some(_), .. }) => return Err(anyhow::anyhow!("Unsupported `let else` binding: {ident}").into()),
+                    Some(init) => Some(self.transform(init.expr, config)?),
+                    None => None,
+                };
+                Ok(Let::new(mutability, ident.to_string(), type_, value).into())
+            },
+            syn::Stmt::Expr(expr, _) => self.statement(expr, config),
             _ => Err(anyhow::anyhow!("Unsupported statement").into()),
         }
     }
@@ -81,16 +209,94 @@ impl Transformer<syn::Expr, Expression> for RustBlockParser {
                 let left = self.transform(binary.left, config)?;
                 let right = self.transform(binary.right, config)?;
                 let operator = match binary.op {
+                    syn::BinOp::Add(_) => "+",
+                    syn::BinOp::Sub(_) => "-",
+                    syn::BinOp::Mul(_) => "*",
                     syn::BinOp::Div(_) => "/",
+                    syn::BinOp::Rem(_) => "%",
+                    syn::BinOp::And(_) => "&&",
+                    syn::BinOp::Or(_) => "||",
+                    syn::BinOp::BitXor(_) => "^",
+                    syn::BinOp::BitAnd(_) => "&",
+                    syn::BinOp::BitOr(_) => "|",
+                    syn::BinOp::Shl(_) => "<<",
+                    syn::BinOp::Shr(_) => ">>",
+                    syn::BinOp::Eq(_) => "==",
+                    syn::BinOp::Lt(_) => "<",
+                    syn::BinOp::Le(_) => "<=",
+                    syn::BinOp::Ne(_) => "!=",
+                    syn::BinOp::Ge(_) => ">=",
+                    syn::BinOp::Gt(_) => ">",
                     _ => return Err(anyhow::anyhow!("Unsupported binary operation").into())
                 };
                 Ok(BinaryExpression::new(left, operator, right).into())
             },
+            syn::Expr::Unary(unary) => {
+                let operator = match unary.op {
+                    syn::UnOp::Deref(_) => "*",
+                    syn::UnOp::Not(_) => "!",
+                    syn::UnOp::Neg(_) => "-",
+                    _ => return Err(anyhow::anyhow!("Unsupported unary operation").into())
+                };
+                let operand = self.transform(unary.expr, config)?;
+                Ok(UnaryExpression::new(operator, operand).into())
+            },
             syn::Expr::Path(path) => {
                 let path = self.path_parser.transform(path.path, config)?;
                 Ok(path.into())
             },
-            _ => Err(anyhow::anyhow!("Unsupported binary expression: {}", quote! { #expr }).into())
+            syn::Expr::Lit(literal) => {
+                let literal = self.literal_parser.transform(literal.lit, config)?;
+                Ok(literal.into())
+            },
+            syn::Expr::Paren(parenthesized) => {
+                let expression = self.transform(parenthesized.expr, config)?;
+                Ok(Expression::Parenthesized(Box::new(expression)))
+            },
+            syn::Expr::Call(call) => {
+                let function = self.transform(call.func, config)?;
+                let arguments = call.args.into_iter().map(|argument| self.transform(argument, config)).collect::<Result<Vec<_>>>()?;
+                Ok(CallExpression::new(function, arguments).into())
+            },
+            syn::Expr::MethodCall(call) => {
+                let receiver = self.transform(call.receiver, config)?;
+                let arguments = call.args.into_iter().map(|argument| self.transform(argument, config)).collect::<Result<Vec<_>>>()?;
+                Ok(MethodCallExpression::new(receiver, call.method.to_string(), arguments).into())
+            },
+            syn::Expr::Field(field) => {
+                let base = self.transform(field.base, config)?;
+                let member = match field.member {
+                    syn::Member::Named(identifier) => identifier.to_string(),
+                    syn::Member::Unnamed(index) => index.index.to_string(),
+                };
+                Ok(FieldExpression::new(base, member).into())
+            },
+            syn::Expr::Index(index) => {
+                let base = self.transform(index.expr, config)?;
+                let index = self.transform(index.index, config)?;
+                Ok(IndexExpression::new(base, index).into())
+            },
+            syn::Expr::Cast(cast) => {
+                let expression = self.transform(cast.expr, config)?;
+                let type_ = self.type_parser.transform(*cast.ty, config)?;
+                Ok(CastExpression::new(expression, type_).into())
+            },
+            syn::Expr::Struct(structure) if structure.rest.is_none() => {
+                let path = self.path_parser.transform(structure.path, config)?;
+                let fields = structure
+                    .fields
+                    .into_iter()
+                    .map(|field| {
+                        let identifier = match field.member {
+                            syn::Member::Named(identifier) => identifier.to_string(),
+                            syn::Member::Unnamed(index) => index.index.to_string(),
+                        };
+                        Ok(FieldValue::new(identifier, self.transform(field.expr, config)?))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(StructureExpression::new(path, fields).into())
+            },
+            _ => Err(anyhow::anyhow!("Unsupported expression: {}", quote! { #expr }).into())
         }
     }
 }
@@ -98,7 +304,7 @@ impl Transformer<syn::Expr, Expression> for RustBlockParser {
 #[cfg(test)]
 mod tests {
     use ligen::ir::{Statement, Expression, BinaryExpression};
-    use ligen::idl::Path;
+    use ligen::idl::{Literal, Path, Type};
 
     use super::*;
 
@@ -111,4 +317,59 @@ mod tests {
         let block: Block = block_parser.transform(block, &Config::default()).unwrap();
         assert_eq!(block, Block::new(vec![Statement::return_(Some(Expression::Binary(BinaryExpression::new(Path::from("a"), "/", Path::from("b")))))]));
     }
+
+    #[test]
+    fn kernel() -> Result<()> {
+        let block = syn::parse_str::<syn::Block>("{
+            let index = id.x as usize;
+            let mut sum: f32 = 0.0;
+            for i in 0..count {
+                sum += input[i].value * scale(-weights[i]);
+            }
+            if sum > limit.max(1.0) { output[index] = Sample { value: sum }; } else { return; }
+        }").map_err(|error| Error::Message(error.to_string()))?;
+        let block: Block = RustBlockParser::default().transform(block, &Config::default())?;
+        let index = CastExpression::new(FieldExpression::new(Path::from("id"), "x"), Type::from("usize"));
+        let value = FieldExpression::new(IndexExpression::new(Path::from("input"), Path::from("i")), "value");
+        let weight = UnaryExpression::new("-", IndexExpression::new(Path::from("weights"), Path::from("i")));
+        let product = BinaryExpression::new(value, "*", CallExpression::new(Path::from("scale"), vec![weight.into()]));
+        let maximum = MethodCallExpression::new(Path::from("limit"), "max", vec![Literal::Float(1.0).into()]);
+        let sample = StructureExpression::new("Sample", vec![FieldValue::new("value", Path::from("sum"))]);
+        assert_eq!(block, Block::new(vec![
+            Let::new(Mutability::Constant, "index", None, Some(index.into())).into(),
+            Let::new(Mutability::Mutable, "sum", Some(Type::f32()), Some(Literal::Float(0.0).into())).into(),
+            For {
+                identifier: "i".into(),
+                start: Literal::Integer(0).into(),
+                end: Path::from("count").into(),
+                inclusive: false,
+                body: Statement::from(Assignment::new(Path::from("sum"), "+=", product)).into(),
+            }.into(),
+            If::new(
+                BinaryExpression::new(Path::from("sum"), ">", maximum),
+                Statement::from(Assignment::new(IndexExpression::new(Path::from("output"), Path::from("index")), "=", sample)),
+                Some(Statement::return_(None as Option<Expression>).into()),
+            ).into(),
+        ]));
+        Ok(())
+    }
+
+    #[test]
+    fn tail() -> Result<()> {
+        let block = syn::parse_str::<syn::Block>("{
+            if a > b { a } else if a < b { b } else { 0 }
+        }").map_err(|error| Error::Message(error.to_string()))?;
+        let block = RustBlockParser::default().body(block, &Config::default())?;
+        let else_if = If::new(
+            BinaryExpression::new(Path::from("a"), "<", Path::from("b")),
+            Statement::return_(Some(Path::from("b"))),
+            Some(Statement::return_(Some(Literal::Integer(0))).into()),
+        );
+        assert_eq!(block, Block::from(Statement::from(If::new(
+            BinaryExpression::new(Path::from("a"), ">", Path::from("b")),
+            Statement::return_(Some(Path::from("a"))),
+            Some(Statement::from(else_if).into()),
+        ))));
+        Ok(())
+    }
 }
@@ -1,7 +1,50 @@
-use anyhow::Context;
 use ligen::prelude::*;
 use ligen::idl::{Author, Dependency, Identifier, VersionRequirement, Version};
-use cargo_toml::{Inheritable, Manifest, Workspace};
+use cargo_toml::{Inheritable, Manifest, Package, PackageTemplate, Workspace};
+
+/// A crate target of a package.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Target {
+    /// The target's name.
+    pub name: String,
+    /// The target's crate root file.
+    pub path: std::path::PathBuf,
+    /// Whether it's the library or a binary.
+    pub kind: TargetKind,
+}
+
+/// The kind of a crate target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    Library,
+    Binary,
+}
+
+/// Errors reading a package's manifest.
+#[derive(Debug, thiserror::Error)]
+pub enum CargoError {
+    /// The manifest couldn't be read or parsed.
+    #[error("Failed to read {path}: {message}")]
+    Manifest { path: std::path::PathBuf, message: String },
+    /// The manifest has no `[package]`, like a virtual workspace manifest.
+    #[error("Package not found in {0}.")]
+    MissingPackage(std::path::PathBuf),
+    /// A field is inherited, but there's no workspace to inherit it from.
+    #[error("`{0}` is inherited from the workspace, but the package isn't in one.")]
+    MissingWorkspace(String),
+    /// A field is inherited, but `[workspace.package]` doesn't set it.
+    #[error("`{0}` is inherited from the workspace, but `[workspace.package]` doesn't set it.")]
+    MissingWorkspaceField(&'static str),
+    /// A dependency is inherited, but `[workspace.dependencies]` doesn't have it.
+    #[error("Dependency `{0}` is inherited from the workspace, but `[workspace.dependencies]` doesn't have it.")]
+    MissingWorkspaceDependency(String),
+}
+
+impl From<CargoError> for Error {
+    fn from(error: CargoError) -> Self {
+        Error::Anyhow(error.into())
+    }
+}
 
 pub struct Cargo {
     pub folder: std::path::PathBuf,
@@ -17,116 +60,150 @@ impl Cargo {
         } else {
             (path.parent().unwrap().to_path_buf(), path.to_path_buf())
         };
-        let workspace = Self::get_project_root_from_path(&folder)?;
-        let workspace = cargo_toml::Manifest::from_path(workspace.join("Cargo.toml"))
-            .map_err(|e| anyhow::anyhow!("Failed to read Cargo.toml: {}", e))?;
-        let workspace = workspace.workspace;
-        let manifest = cargo_toml::Manifest::from_path(manifest)
-            .map_err(|e| anyhow::anyhow!("Failed to read Cargo.toml: {}", e))?;
+        let manifest = Self::read_manifest(&manifest)?;
+        let workspace = Self::find_workspace(&folder, &manifest)?;
         Ok(Self { folder, workspace, manifest })
     }
 
+    /// Reads the manifest as written. `Manifest::from_path` would also resolve the inherited
+    /// fields and discover the targets, but its errors don't tell which field is missing, so
+    /// both are done here.
+    fn read_manifest(path: &std::path::Path) -> Result<Manifest> {
+        let error = |message: String| CargoError::Manifest { path: path.to_path_buf(), message };
+        let content = std::fs::read(path).map_err(|e| error(e.to_string()))?;
+        let manifest = Manifest::from_slice(&content).map_err(|e| error(e.to_string()))?;
+        Ok(manifest)
+    }
+
+    /// The workspace the package at `folder` belongs to: its own `[workspace]`, the one
+    /// `package.workspace` points to, or the closest ancestor's.
+    fn find_workspace(folder: &std::path::Path, manifest: &Manifest) -> Result<Option<Workspace>> {
+        if manifest.workspace.is_some() {
+            return Ok(manifest.workspace.clone());
+        }
+        if let Some(path) = manifest.package.as_ref().and_then(|package| package.workspace.as_ref()) {
+            let manifest = Self::read_manifest(&folder.join(path).join("Cargo.toml"))?;
+            return Ok(manifest.workspace);
+        }
+        for ancestor in folder.ancestors().skip(1) {
+            let path = ancestor.join("Cargo.toml");
+            if path.exists() {
+                let manifest = Self::read_manifest(&path)?;
+                if manifest.workspace.is_some() {
+                    return Ok(manifest.workspace);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn package(&self) -> Result<&Package> {
+        let package = self.manifest.package.as_ref().ok_or_else(|| CargoError::MissingPackage(self.folder.join("Cargo.toml")))?;
+        Ok(package)
+    }
+
+    fn workspace_package(&self) -> Option<&PackageTemplate> {
+        self.workspace.as_ref().and_then(|workspace| workspace.package.as_ref())
+    }
+
     pub fn get_name(&self) -> Result<&String> {
-        let package = self.manifest.package.as_ref().context("Package not found in Cargo.toml.")?;
-        Ok(&package.name)
+        Ok(&self.package()?.name)
     }
 
-    fn get_from_package_or_workspace<T>(package: Inheritable<T>, workspace: Option<T>) -> Result<T> {
+    /// The value of `field`, from the package or, if it's inherited, from `[workspace.package]`.
+    fn get_from_package_or_workspace<'a, T: ?Sized>(&'a self, field: &'static str, package: Inheritable<&'a T>, workspace: impl FnOnce(&'a PackageTemplate) -> Option<&'a T>) -> Result<&'a T> {
         match package {
-            Inheritable::Inherited { .. } => Ok(workspace.context("Workspace not found in Cargo.toml.")?),
-            Inheritable::Set(authors) => Ok(authors),
+            Inheritable::Set(value) => Ok(value),
+            Inheritable::Inherited { .. } => {
+                let template = self.workspace_package().ok_or_else(|| match &self.workspace {
+                    None => CargoError::MissingWorkspace(field.to_string()),
+                    Some(_) => CargoError::MissingWorkspaceField(field),
+                })?;
+                Ok(workspace(template).ok_or(CargoError::MissingWorkspaceField(field))?)
+            }
         }
     }
 
+    /// The value of the optional `field`, from the package or, if it's inherited, from
+    /// `[workspace.package]`.
+    fn get_optional_from_package_or_workspace<'a, T: ?Sized>(&'a self, field: &'static str, package: Option<Inheritable<&'a T>>, workspace: impl FnOnce(&'a PackageTemplate) -> Option<&'a T>) -> Result<Option<&'a T>> {
+        package.map(|package| self.get_from_package_or_workspace(field, package, workspace)).transpose()
+    }
+
     pub fn get_authors(&self) -> Result<Vec<Author>> {
-        let package = self.manifest.package.as_ref().context("Package not found in Cargo.toml.")?.authors.as_ref();
-        let workspace_package = self.workspace.as_ref().and_then(|workspace| workspace.package.as_ref()).and_then(|package| package.authors.as_ref());
-        let authors = Self::get_from_package_or_workspace(package, workspace_package)?;
+        let package = self.package()?.authors.as_ref();
+        let authors = self.get_from_package_or_workspace("authors", package, |package| package.authors.as_ref())?;
         Ok(authors.iter().map(Author::from).collect())
     }
 
     pub fn get_keywords(&self) -> Result<Vec<String>> {
-        let package = self.manifest.package.as_ref().context("Package not found in Cargo.toml.")?.keywords.as_ref();
-        let workspace_package = self.workspace.as_ref().and_then(|workspace| workspace.package.as_ref()).and_then(|package| package.keywords.as_ref());
-        let keywords = Self::get_from_package_or_workspace(package, workspace_package)?;
+        let package = self.package()?.keywords.as_ref();
+        let keywords = self.get_from_package_or_workspace("keywords", package, |package| package.keywords.as_ref())?;
         Ok(keywords.to_vec())
     }
 
     pub fn get_license(&self) -> Result<Option<String>> {
-        let manifest = self.manifest.package.as_ref().context("Package not found in Cargo.toml.")?;
-        if let Some(license) = &manifest.license {
-            let package = license.as_ref();
-            let workspace_package = self.workspace.as_ref().and_then(|workspace| workspace.package.as_ref()).and_then(|package| package.license.as_ref());
-            let license = Self::get_from_package_or_workspace(package, workspace_package)?;
-            Ok(Some(license.clone()))
-        }
-        else {
-            Ok(None)
-        }
+        let package = self.package()?.license.as_ref().map(Inheritable::as_ref);
+        let license = self.get_optional_from_package_or_workspace("license", package, |package| package.license.as_ref())?;
+        Ok(license.cloned())
     }
 
     pub fn get_version(&self) -> Result<Version> {
-        let package = self.manifest.package.as_ref().context("Package not found in Cargo.toml.")?.version.as_ref();
-        let workspace_package = self.workspace.as_ref().and_then(|workspace| workspace.package.as_ref()).and_then(|package| package.version.as_ref());
-        let version = Self::get_from_package_or_workspace(package, workspace_package)?;
+        let package = self.package()?.version.as_ref();
+        let version = self.get_from_package_or_workspace("version", package, |package| package.version.as_ref())?;
         Version::try_from(version.clone())
     }
 
     pub fn get_description(&self) -> Result<Option<String>> {
-        let manifest = self.manifest.package.as_ref().context("Package not found in Cargo.toml.")?;
-        if let Some(description) = manifest.description.as_ref() {
-            let package = description.as_ref();
-            let workspace_package = self.workspace.as_ref().and_then(|workspace| workspace.package.as_ref()).and_then(|package| package.description.as_ref());
-            let description = Self::get_from_package_or_workspace(package, workspace_package)?;
-            Ok(Some(description.clone()))
-        }
-        else {
-            Ok(None)
-        }
+        let package = self.package()?.description.as_ref().map(Inheritable::as_ref);
+        let description = self.get_optional_from_package_or_workspace("description", package, |package| package.description.as_ref())?;
+        Ok(description.cloned())
     }
-    
+
     pub fn get_homepage(&self) -> Result<Option<String>> {
-        let manifest = self.manifest.package.as_ref().context("Package not found in Cargo.toml.")?;
-        if let Some(homepage) = manifest.homepage.as_ref() {
-            let package = homepage.as_ref();
-            let workspace_package = self.workspace.as_ref().and_then(|workspace| workspace.package.as_ref()).and_then(|package| package.homepage.as_ref());
-            let homepage = Self::get_from_package_or_workspace(package, workspace_package)?;
-            Ok(Some(homepage.clone()))
-        }
-        else {
-            Ok(None)
-        }
+        let package = self.package()?.homepage.as_ref().map(Inheritable::as_ref);
+        let homepage = self.get_optional_from_package_or_workspace("homepage", package, |package| package.homepage.as_ref())?;
+        Ok(homepage.cloned())
+    }
+
+    pub fn get_repository(&self) -> Result<Option<String>> {
+        let package = self.package()?.repository.as_ref().map(Inheritable::as_ref);
+        let repository = self.get_optional_from_package_or_workspace("repository", package, |package| package.repository.as_ref())?;
+        Ok(repository.cloned())
+    }
+
+    pub fn get_documentation(&self) -> Result<Option<String>> {
+        let package = self.package()?.documentation.as_ref().map(Inheritable::as_ref);
+        let documentation = self.get_optional_from_package_or_workspace("documentation", package, |package| package.documentation.as_ref())?;
+        Ok(documentation.cloned())
     }
 
     pub fn get_rust_version(&self) -> Result<Option<VersionRequirement>> {
-        let manifest = self.manifest.package.as_ref().context("Package not found in Cargo.toml.")?;
-        if let Some(rust_version) = manifest.rust_version.as_ref() {
-            let package = rust_version.as_ref();
-            let workspace_package = self.workspace.as_ref().and_then(|workspace| workspace.package.as_ref()).and_then(|package| package.rust_version.as_ref());
-            let version = Self::get_from_package_or_workspace(package, workspace_package)?;
-            Ok(Some(VersionRequirement::from(version.clone())))
-        }
-        else {
-            Ok(None)
-        }
+        let package = self.package()?.rust_version.as_ref().map(Inheritable::as_ref);
+        let version = self.get_optional_from_package_or_workspace("rust-version", package, |package| package.rust_version.as_ref())?;
+        Ok(version.cloned().map(VersionRequirement::from))
     }
 
+    /// The dependencies, with the ones declared `workspace = true` taken from
+    /// `[workspace.dependencies]`, plus the features they add.
     pub fn get_dependencies(&self) -> Result<Vec<Dependency>> {
         let mut dependencies = vec![];
         for (name, requirements) in self.manifest.dependencies.iter() {
             let identifier = Identifier::from(name.clone());
             let (requirement, features) = match requirements {
-                cargo_toml::Dependency::Simple(requirement) => {
-                    let requirement = VersionRequirement::from(requirement.clone());
-                    (requirement, vec![])
-                },
-                cargo_toml::Dependency::Detailed(dependency) => {
-                    let requirement = dependency.version.as_ref().cloned().map(VersionRequirement::from).unwrap_or(VersionRequirement::any());
-                    (requirement, dependency.features.clone())
+                cargo_toml::Dependency::Inherited(dependency) => {
+                    let inherited = self
+                        .workspace
+                        .as_ref()
+                        .ok_or_else(|| CargoError::MissingWorkspace(format!("dependencies.{name}")))?
+                        .dependencies
+                        .get(name)
+                        .ok_or_else(|| CargoError::MissingWorkspaceDependency(name.clone()))?;
+                    let (requirement, mut features) = Self::requirement(inherited);
+                    features.extend(dependency.features.iter().cloned());
+                    (requirement, features)
                 },
-                cargo_toml::Dependency::Inherited(_dependency) => {
-                    todo!("Inherited dependencies are not supported yet.")
-                }
+                dependency => Self::requirement(dependency),
             };
             let features = features.into_iter().map(Identifier::from).collect();
             let dependency = Dependency { identifier, requirement, features };
@@ -135,6 +212,90 @@ impl Cargo {
         Ok(dependencies)
     }
 
+    fn requirement(dependency: &cargo_toml::Dependency) -> (VersionRequirement, Vec<String>) {
+        match dependency {
+            cargo_toml::Dependency::Simple(requirement) => (VersionRequirement::from(requirement.clone()), vec![]),
+            cargo_toml::Dependency::Detailed(dependency) => {
+                let requirement = dependency.version.as_ref().cloned().map(VersionRequirement::from).unwrap_or(VersionRequirement::any());
+                (requirement, dependency.features.clone())
+            },
+            // `[workspace.dependencies]` can't inherit, so there's nothing to resolve.
+            cargo_toml::Dependency::Inherited(dependency) => (VersionRequirement::any(), dependency.features.clone()),
+        }
+    }
+
+    /// The library target, from `[lib]` or `src/lib.rs`.
+    pub fn get_library_target(&self) -> Result<Option<Target>> {
+        let package = self.get_name()?;
+        let name = self.manifest.lib.as_ref().and_then(|lib| lib.name.clone()).unwrap_or_else(|| package.replace('-', "_"));
+        let path = match self.manifest.lib.as_ref().and_then(|lib| lib.path.as_ref()) {
+            Some(path) => self.folder.join(path),
+            None => self.folder.join("src").join("lib.rs"),
+        };
+        let kind = TargetKind::Library;
+        Ok(path.exists().then_some(Target { name, path, kind }))
+    }
+
+    /// The binary targets, from `[[bin]]`, `src/main.rs`, `src/bin/*.rs` and `src/bin/*/main.rs`.
+    pub fn get_binary_targets(&self) -> Result<Vec<Target>> {
+        let package = self.get_name()?;
+        let bin = self.folder.join("src").join("bin");
+        let mut targets = Vec::new();
+        for target in &self.manifest.bin {
+            let name = target.name.clone().unwrap_or_else(|| package.clone());
+            let path = match &target.path {
+                Some(path) => self.folder.join(path),
+                None if name == *package => self.folder.join("src").join("main.rs"),
+                None if bin.join(&name).join("main.rs").exists() => bin.join(&name).join("main.rs"),
+                None => bin.join(&name).with_extension("rs"),
+            };
+            targets.push(Target { name, path, kind: TargetKind::Binary });
+        }
+        // Binaries are discovered unless `autobins = false`, even if the manifest lists others.
+        let autobins = self.manifest.package.as_ref().map(|package| package.autobins).unwrap_or(true);
+        if autobins {
+            let mut discovered = vec![(package.clone(), self.folder.join("src").join("main.rs"))];
+            if let Ok(entries) = std::fs::read_dir(&bin) {
+                let mut entries = entries.flatten().map(|entry| entry.path()).collect::<Vec<_>>();
+                entries.sort();
+                for path in entries {
+                    let Some(name) = path.file_stem().map(|name| name.to_string_lossy().to_string()) else { continue };
+                    if path.is_dir() {
+                        discovered.push((name, path.join("main.rs")));
+                    } else if path.extension().is_some_and(|extension| extension == "rs") {
+                        discovered.push((name, path));
+                    }
+                }
+            }
+            for (name, path) in discovered {
+                if path.exists() && !targets.iter().any(|target| target.path == path || target.name == name) {
+                    targets.push(Target { name, path, kind: TargetKind::Binary });
+                }
+            }
+        }
+        targets.retain(|target| target.path.exists());
+        Ok(targets)
+    }
+
+    /// The features enabled by default: `default` itself, if the package has it, and the ones it
+    /// enables, expanded. Optional dependencies (`dep:name`) and the features of dependencies
+    /// (`name/feature`) aren't features of this package, so they're left out.
+    pub fn get_default_features(&self) -> Vec<String> {
+        let mut features = std::collections::BTreeSet::new();
+        let mut pending = vec!["default".to_string()];
+        while let Some(feature) = pending.pop() {
+            if let Some(enabled) = self.manifest.features.get(&feature) {
+                features.insert(feature.clone());
+                for feature in enabled {
+                    if !feature.starts_with("dep:") && !feature.contains('/') && features.insert(feature.clone()) {
+                        pending.push(feature.clone());
+                    }
+                }
+            }
+        }
+        features.into_iter().collect()
+    }
+
     pub fn get_project_root_from_path(path: impl AsRef<std::path::Path>) -> Result<std::path::PathBuf> {
         let path = path.as_ref();
 
@@ -157,4 +318,127 @@ impl Cargo {
         }
         Err(anyhow::anyhow!("Failed to find workspace root").into())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(name: &str, member: &str) -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(format!("ligen-cargo-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("member").join("src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), r#"
+            [workspace]
+            members = ["member"]
+
+            [workspace.package]
+            version = "1.2.3"
+            authors = ["Jane Doe <jane@example.com>"]
+            license = "MIT"
+
+            [workspace.dependencies]
+            serde = { version = "1.0", features = ["derive"] }
+        "#).unwrap();
+        std::fs::write(root.join("member").join("Cargo.toml"), member).unwrap();
+        std::fs::write(root.join("member").join("src").join("lib.rs"), "").unwrap();
+        root
+    }
+
+    #[test]
+    fn inheritance() -> Result<()> {
+        let root = workspace("inheritance", r#"
+            [package]
+            name = "member"
+            version.workspace = true
+            authors.workspace = true
+            license.workspace = true
+
+            [dependencies]
+            serde = { workspace = true, features = ["rc"] }
+        "#);
+        let cargo = Cargo::new(root.join("member"))?;
+        assert_eq!(cargo.get_version()?, Version::try_from("1.2.3")?);
+        assert_eq!(cargo.get_license()?.as_deref(), Some("MIT"));
+        assert_eq!(cargo.get_authors()?.len(), 1);
+        let dependencies = cargo.get_dependencies()?;
+        assert_eq!(dependencies[0].requirement, VersionRequirement::from("1.0"));
+        assert_eq!(dependencies[0].features, vec![Identifier::from("derive"), Identifier::from("rc")]);
+        std::fs::remove_dir_all(root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn default_features() -> Result<()> {
+        let root = workspace("default-features", r#"
+            [package]
+            name = "member"
+            version = "0.1.0"
+
+            [features]
+            default = ["std", "dep:serde"]
+            std = ["alloc", "serde/std"]
+            alloc = []
+        "#);
+        let cargo = Cargo::new(root.join("member"))?;
+        std::fs::remove_dir_all(root)?;
+        assert_eq!(cargo.get_default_features(), ["alloc", "default", "std"]);
+        Ok(())
+    }
+
+    #[test]
+    fn targets() -> Result<()> {
+        let root = workspace("targets", r#"
+            [package]
+            name = "member"
+            version = "0.1.0"
+
+            [lib]
+            name = "core"
+            path = "src/core.rs"
+
+            [[bin]]
+            name = "server"
+            path = "server/main.rs"
+
+            [[bin]]
+            name = "cli"
+        "#);
+        let member = root.join("member");
+        std::fs::create_dir_all(member.join("server"))?;
+        std::fs::create_dir_all(member.join("src").join("bin").join("migrate"))?;
+        for file in ["src/core.rs", "src/main.rs", "server/main.rs", "src/bin/cli.rs", "src/bin/seed.rs", "src/bin/migrate/main.rs", "src/bin/notes.txt"] {
+            std::fs::write(member.join(file), "")?;
+        }
+        let cargo = Cargo::new(&member)?;
+        let library = cargo.get_library_target()?;
+        let binaries = cargo.get_binary_targets()?;
+        std::fs::remove_dir_all(root)?;
+        assert_eq!(library, Some(Target { name: "core".into(), path: member.join("src/core.rs"), kind: TargetKind::Library }));
+        let mut binaries = binaries.iter().map(|target| (target.name.as_str(), target.path.strip_prefix(&member).unwrap().to_path_buf())).collect::<Vec<_>>();
+        binaries.sort();
+        assert_eq!(binaries, [
+            ("cli", std::path::PathBuf::from("src/bin/cli.rs")),
+            ("member", std::path::PathBuf::from("src/main.rs")),
+            ("migrate", std::path::PathBuf::from("src/bin/migrate/main.rs")),
+            ("seed", std::path::PathBuf::from("src/bin/seed.rs")),
+            ("server", std::path::PathBuf::from("server/main.rs")),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_field() -> Result<()> {
+        let root = workspace("missing-field", r#"
+            [package]
+            name = "member"
+            version = "0.1.0"
+            description.workspace = true
+        "#);
+        let error = Cargo::new(root.join("member")).and_then(|cargo| cargo.get_description());
+        std::fs::remove_dir_all(root)?;
+        let Err(Error::Anyhow(error)) = error else {
+            panic!("Expected an error.");
+        };
+        assert!(matches!(error.downcast_ref::<CargoError>(), Some(CargoError::MissingWorkspaceField("description"))));
+        Ok(())
+    }
+}
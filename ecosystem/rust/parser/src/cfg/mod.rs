@@ -0,0 +1,403 @@
+//! `#[cfg]` evaluation.
+
+use std::collections::{BTreeSet, HashMap};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use crate::prelude::*;
+use ligen::idl::Literal;
+use syn::punctuated::Punctuated;
+
+const TARGET: &str = "ligen::cfg::target";
+const FEATURES: &str = "ligen::cfg::features";
+const FLAGS: &str = "ligen::cfg::flags";
+
+/// What `#[cfg(...)]` predicates are evaluated against.
+///
+/// It's read from the `Config`, so every parser sees it:
+///
+/// ```toml
+/// [ligen.cfg]
+/// target = "x86_64-unknown-linux-gnu"
+/// features = ["serde"]
+/// flags = ["debug_assertions"]
+/// ```
+///
+/// Items whose predicate is false aren't parsed. A predicate that can't be decided, because the
+/// target or the features aren't known or it asks something else, like `target_feature`, keeps
+/// the item with its `cfg` attribute, so generators can emit conditional code. `RustLibraryParser`
+/// enables the crate's default features, and `RustRegistryParser` the ones Cargo resolved.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CfgTarget {
+    /// The target triple, such as `x86_64-unknown-linux-gnu`.
+    pub triple: Option<String>,
+    /// The enabled features.
+    pub features: Option<BTreeSet<String>>,
+    /// The set flags, such as `test` or `debug_assertions`.
+    pub flags: BTreeSet<String>,
+}
+
+/// Options whose values are all known once the triple is.
+const TARGET_OPTIONS: &[&str] = &[
+    "target_arch", "target_vendor", "target_os", "target_env", "target_family", "target_pointer_width",
+    "target_endian", "unix", "windows",
+];
+
+/// Flags that are unset unless `flags` has them.
+const FLAGS_OPTIONS: &[&str] = &["test", "debug_assertions", "doc", "doctest", "miri", "proc_macro"];
+
+impl CfgTarget {
+    /// Creates a target from a triple, such as `x86_64-unknown-linux-gnu`.
+    pub fn new(triple: impl Into<String>) -> Self {
+        let triple = Some(triple.into());
+        Self { triple, ..Default::default() }
+    }
+
+    /// Enables `features`.
+    pub fn with_features(mut self, features: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.features = Some(features.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Reads the target from `config`.
+    pub fn from_config(config: &Config) -> Self {
+        let strings = |literal: &Literal| match literal {
+            Literal::Array(items) => items.iter().filter_map(string).collect(),
+            _ => BTreeSet::new(),
+        };
+        let triple = config.get(TARGET).and_then(string);
+        let features = config.get(FEATURES).map(strings);
+        let flags = config.get(FLAGS).map(strings).unwrap_or_default();
+        Self { triple, features, flags }
+    }
+
+    /// A copy of `config` with this target.
+    pub fn to_config(&self, config: &Config) -> Config {
+        let strings = |strings: &BTreeSet<String>| Literal::Array(strings.iter().cloned().map(Literal::String).collect());
+        let mut config = config.clone();
+        if let Some(triple) = &self.triple {
+            config.set(TARGET, triple.clone());
+        }
+        if let Some(features) = &self.features {
+            config.set(FEATURES, strings(features));
+        }
+        config.set(FLAGS, strings(&self.flags));
+        config
+    }
+
+    /// Whether the items with `attributes` are compiled, which is `false` only if one of their
+    /// `cfg` predicates is known to be false.
+    pub fn is_enabled(&self, attributes: &[syn::Attribute]) -> bool {
+        attributes
+            .iter()
+            .filter(|attribute| attribute.path().is_ident("cfg"))
+            .filter_map(|attribute| attribute.parse_args::<syn::Meta>().ok())
+            .all(|predicate| self.evaluate(&predicate) != Some(false))
+    }
+
+    /// Evaluates a `cfg` predicate, returning `None` if it can't be decided.
+    pub fn evaluate(&self, predicate: &syn::Meta) -> Option<bool> {
+        match predicate {
+            syn::Meta::Path(path) => self.option(&path.get_ident()?.to_string(), None),
+            syn::Meta::NameValue(name_value) => {
+                let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(value), .. }) = &name_value.value else {
+                    return None;
+                };
+                self.option(&name_value.path.get_ident()?.to_string(), Some(&value.value()))
+            },
+            syn::Meta::List(list) => {
+                let predicates = list
+                    .parse_args_with(Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated)
+                    .ok()?;
+                let mut values = predicates.iter().map(|predicate| self.evaluate(predicate));
+                match list.path.get_ident()?.to_string().as_str() {
+                    // Three-valued logic: a known value decides if it can, otherwise it's unknown.
+                    "all" => values.try_fold(Some(true), |all, value| match value {
+                        Some(false) => Err(()),
+                        Some(true) => Ok(all),
+                        None => Ok(None),
+                    }).unwrap_or(Some(false)),
+                    "any" => values.try_fold(Some(false), |any, value| match value {
+                        Some(true) => Err(()),
+                        Some(false) => Ok(any),
+                        None => Ok(None),
+                    }).unwrap_or(Some(true)),
+                    "not" if predicates.len() == 1 => values.next().flatten().map(|value| !value),
+                    _ => None,
+                }
+            },
+        }
+    }
+
+    fn option(&self, name: &str, value: Option<&str>) -> Option<bool> {
+        if name == "feature" {
+            return Some(self.features.as_ref()?.contains(value?));
+        }
+        if FLAGS_OPTIONS.contains(&name) {
+            return Some(value.is_none() && self.flags.contains(name));
+        }
+        if TARGET_OPTIONS.contains(&name) {
+            let options = target_options(self.triple.as_ref()?);
+            return Some(options.iter().any(|(option, option_value)| option == name && option_value.as_deref() == value));
+        }
+        None
+    }
+}
+
+fn string(literal: &Literal) -> Option<String> {
+    match literal {
+        Literal::String(string) => Some(string.clone()),
+        _ => None,
+    }
+}
+
+/// The options a target triple sets, as `rustc --print cfg` lists them.
+///
+/// They're asked to `rustc`, or to `$RUSTC` if it's set, once per triple. If it isn't available
+/// or doesn't know the target, they're worked out from the triple.
+fn target_options(triple: &str) -> Vec<(String, Option<String>)> {
+    static OPTIONS: OnceLock<Mutex<HashMap<String, Vec<(String, Option<String>)>>>> = OnceLock::new();
+    let mut options = OPTIONS.get_or_init(Default::default).lock().unwrap_or_else(PoisonError::into_inner);
+    options
+        .entry(triple.to_string())
+        .or_insert_with(|| rustc_options(triple).unwrap_or_else(|| triple_options(triple)))
+        .clone()
+}
+
+/// The target options `rustc --print cfg` lists for `triple`.
+fn rustc_options(triple: &str) -> Option<Vec<(String, Option<String>)>> {
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = Command::new(rustc).args(["--print", "cfg", "--target", triple]).output().ok()?;
+    output.status.success().then(|| parse_options(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// The target options among the lines of `rustc --print cfg`, such as `unix` and
+/// `target_os="linux"`.
+fn parse_options(cfg: &str) -> Vec<(String, Option<String>)> {
+    cfg.lines()
+        .map(|line| match line.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.trim_matches('"').to_string())),
+            None => (line.to_string(), None),
+        })
+        .filter(|(name, _)| TARGET_OPTIONS.contains(&name.as_str()))
+        .collect()
+}
+
+/// Vendors that can appear after the architecture of a triple. Triples without one, such as
+/// `aarch64-linux-android` and `thumbv7em-none-eabihf`, have the `unknown` vendor.
+const VENDORS: &[&str] = &[
+    "unknown", "pc", "apple", "nvidia", "fortanix", "sun", "wrs", "uwp", "ibm", "kmc", "nintendo",
+    "sony", "esp", "espressif", "win7", "unikraft", "risc0", "openwrt",
+];
+
+/// The target options worked out from `triple`, which is `arch-vendor-os-env`, `arch-vendor-os`,
+/// `arch-os-env` or `arch-os`. The environment can carry the ABI, like `gnueabihf` does.
+fn triple_options(triple: &str) -> Vec<(String, Option<String>)> {
+    let mut parts = triple.split('-');
+    let architecture = parts.next().unwrap_or_default();
+    let mut parts = parts.collect::<Vec<_>>();
+    let vendor = if parts.len() > 1 && VENDORS.contains(&parts[0]) { parts.remove(0) } else { "unknown" };
+    let os = parts.first().copied().unwrap_or("none");
+    let abi = parts.get(1).copied().unwrap_or_default();
+    let (os, abi) = match os {
+        "darwin" => ("macos", abi),
+        "linux" if abi.starts_with("android") => ("android", ""),
+        os => match os.strip_prefix("wasi") {
+            Some(version) if !version.is_empty() => ("wasi", version),
+            _ => (os, abi),
+        },
+    };
+    let env = if abi.starts_with("gnu") {
+        "gnu"
+    } else if abi.starts_with("musl") {
+        "musl"
+    } else if abi.starts_with("uclibc") {
+        "uclibc"
+    } else if abi.starts_with("eabi") || abi.starts_with("android") || ["elf", "softfloat", "threads"].contains(&abi) {
+        ""
+    } else {
+        abi
+    };
+    let arch = if architecture.starts_with('i') && architecture.ends_with("86") {
+        "x86"
+    } else if ["armv", "armeb", "thumbv"].iter().any(|prefix| architecture.starts_with(prefix)) {
+        "arm"
+    } else if architecture.starts_with("riscv64") {
+        "riscv64"
+    } else if architecture.starts_with("riscv32") {
+        "riscv32"
+    } else if architecture.starts_with("wasm32") {
+        "wasm32"
+    } else {
+        match architecture {
+            "powerpc64le" => "powerpc64",
+            "mipsel" => "mips",
+            "mips64el" => "mips64",
+            "sparcv9" => "sparc64",
+            "bpfel" | "bpfeb" => "bpf",
+            architecture => architecture,
+        }
+    };
+    let pointer_width = if abi.ends_with("x32") || abi.ends_with("ilp32") {
+        "32"
+    } else if arch.ends_with("64") || ["s390x", "bpf"].contains(&arch) {
+        "64"
+    } else if ["avr", "msp430"].contains(&arch) {
+        "16"
+    } else {
+        "32"
+    };
+    let little = architecture.ends_with("el") || architecture.ends_with("le");
+    let big = architecture.starts_with("armeb") || architecture == "bpfeb" || ["s390x", "powerpc", "powerpc64", "sparc", "sparc64", "mips", "mips64"].contains(&arch);
+    let endian = if big && !little { "big" } else { "little" };
+    let family = match os {
+        "linux" | "macos" | "ios" | "tvos" | "watchos" | "visionos" | "android" | "freebsd" | "netbsd" | "openbsd" | "dragonfly"
+        | "solaris" | "illumos" | "haiku" | "fuchsia" | "emscripten" | "redox" | "aix" | "nto" | "hurd" | "espidf" | "vxworks" => Some("unix"),
+        "windows" => Some("windows"),
+        _ => None,
+    };
+    let mut options = vec![
+        ("target_arch".to_string(), Some(arch.to_string())),
+        ("target_vendor".to_string(), Some(vendor.to_string())),
+        ("target_os".to_string(), Some(os.to_string())),
+        ("target_env".to_string(), Some(env.to_string())),
+        ("target_pointer_width".to_string(), Some(pointer_width.to_string())),
+        ("target_endian".to_string(), Some(endian.to_string())),
+    ];
+    if let Some(family) = family {
+        options.push(("target_family".to_string(), Some(family.to_string())));
+        options.push((family.to_string(), None));
+    }
+    if arch.starts_with("wasm") {
+        options.push(("target_family".to_string(), Some("wasm".to_string())));
+    }
+    options
+}
+
+/// The attributes of a module item.
+pub(crate) fn item_attributes(item: &syn::Item) -> &[syn::Attribute] {
+    match item {
+        syn::Item::Const(item) => &item.attrs,
+        syn::Item::Enum(item) => &item.attrs,
+        syn::Item::ExternCrate(item) => &item.attrs,
+        syn::Item::Fn(item) => &item.attrs,
+        syn::Item::ForeignMod(item) => &item.attrs,
+        syn::Item::Impl(item) => &item.attrs,
+        syn::Item::Macro(item) => &item.attrs,
+        syn::Item::Mod(item) => &item.attrs,
+        syn::Item::Static(item) => &item.attrs,
+        syn::Item::Struct(item) => &item.attrs,
+        syn::Item::Trait(item) => &item.attrs,
+        syn::Item::TraitAlias(item) => &item.attrs,
+        syn::Item::Type(item) => &item.attrs,
+        syn::Item::Union(item) => &item.attrs,
+        syn::Item::Use(item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+/// The attributes of an `impl` item.
+pub(crate) fn impl_item_attributes(item: &syn::ImplItem) -> &[syn::Attribute] {
+    match item {
+        syn::ImplItem::Const(item) => &item.attrs,
+        syn::ImplItem::Fn(item) => &item.attrs,
+        syn::ImplItem::Type(item) => &item.attrs,
+        syn::ImplItem::Macro(item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled(target: &CfgTarget, attribute: syn::Attribute) -> bool {
+        target.is_enabled(&[attribute])
+    }
+
+    #[test]
+    fn evaluation() {
+        let linux = CfgTarget::new("x86_64-unknown-linux-gnu").with_features(["serde"]);
+        assert!(enabled(&linux, syn::parse_quote!(#[cfg(unix)])));
+        assert!(!enabled(&linux, syn::parse_quote!(#[cfg(windows)])));
+        assert!(enabled(&linux, syn::parse_quote!(#[cfg(all(target_os = "linux", target_pointer_width = "64"))])));
+        assert!(!enabled(&linux, syn::parse_quote!(#[cfg(any(target_os = "macos", not(feature = "serde")))])));
+        assert!(!enabled(&linux, syn::parse_quote!(#[cfg(test)])));
+        // Undecidable predicates keep the item.
+        assert!(enabled(&linux, syn::parse_quote!(#[cfg(target_feature = "avx2")])));
+        assert!(!enabled(&linux, syn::parse_quote!(#[cfg(all(windows, target_feature = "avx2"))])));
+
+        let unknown = CfgTarget::default();
+        assert!(enabled(&unknown, syn::parse_quote!(#[cfg(windows)])));
+        assert!(enabled(&unknown, syn::parse_quote!(#[cfg(feature = "serde")])));
+    }
+
+    /// `rustc --print cfg --target <triple>`, trimmed to the target options.
+    const RUSTC: &[(&str, &str)] = &[
+        ("x86_64-unknown-linux-gnu", "target_arch=\"x86_64\"\ntarget_endian=\"little\"\ntarget_env=\"gnu\"\ntarget_family=\"unix\"\ntarget_os=\"linux\"\ntarget_pointer_width=\"64\"\ntarget_vendor=\"unknown\"\nunix"),
+        ("x86_64-pc-windows-msvc", "target_arch=\"x86_64\"\ntarget_endian=\"little\"\ntarget_env=\"msvc\"\ntarget_family=\"windows\"\ntarget_os=\"windows\"\ntarget_pointer_width=\"64\"\ntarget_vendor=\"pc\"\nwindows"),
+        ("aarch64-apple-darwin", "target_arch=\"aarch64\"\ntarget_endian=\"little\"\ntarget_env=\"\"\ntarget_family=\"unix\"\ntarget_os=\"macos\"\ntarget_pointer_width=\"64\"\ntarget_vendor=\"apple\"\nunix"),
+        ("wasm32-unknown-unknown", "target_arch=\"wasm32\"\ntarget_endian=\"little\"\ntarget_env=\"\"\ntarget_family=\"wasm\"\ntarget_os=\"unknown\"\ntarget_pointer_width=\"32\"\ntarget_vendor=\"unknown\""),
+        ("wasm32-wasip1", "target_arch=\"wasm32\"\ntarget_endian=\"little\"\ntarget_env=\"p1\"\ntarget_family=\"wasm\"\ntarget_os=\"wasi\"\ntarget_pointer_width=\"32\"\ntarget_vendor=\"unknown\""),
+        ("thumbv7em-none-eabihf", "target_arch=\"arm\"\ntarget_endian=\"little\"\ntarget_env=\"\"\ntarget_os=\"none\"\ntarget_pointer_width=\"32\"\ntarget_vendor=\"unknown\""),
+        ("riscv32imac-unknown-none-elf", "target_arch=\"riscv32\"\ntarget_endian=\"little\"\ntarget_env=\"\"\ntarget_os=\"none\"\ntarget_pointer_width=\"32\"\ntarget_vendor=\"unknown\""),
+        ("aarch64-linux-android", "target_arch=\"aarch64\"\ntarget_endian=\"little\"\ntarget_env=\"\"\ntarget_family=\"unix\"\ntarget_os=\"android\"\ntarget_pointer_width=\"64\"\ntarget_vendor=\"unknown\"\nunix"),
+        ("arm-linux-androideabi", "target_arch=\"arm\"\ntarget_endian=\"little\"\ntarget_env=\"\"\ntarget_family=\"unix\"\ntarget_os=\"android\"\ntarget_pointer_width=\"32\"\ntarget_vendor=\"unknown\"\nunix"),
+        ("armv7-unknown-linux-gnueabihf", "target_arch=\"arm\"\ntarget_endian=\"little\"\ntarget_env=\"gnu\"\ntarget_family=\"unix\"\ntarget_os=\"linux\"\ntarget_pointer_width=\"32\"\ntarget_vendor=\"unknown\"\nunix"),
+        ("mipsel-unknown-linux-gnu", "target_arch=\"mips\"\ntarget_endian=\"little\"\ntarget_env=\"gnu\"\ntarget_family=\"unix\"\ntarget_os=\"linux\"\ntarget_pointer_width=\"32\"\ntarget_vendor=\"unknown\"\nunix"),
+        ("x86_64-unknown-linux-gnux32", "target_arch=\"x86_64\"\ntarget_endian=\"little\"\ntarget_env=\"gnu\"\ntarget_family=\"unix\"\ntarget_os=\"linux\"\ntarget_pointer_width=\"32\"\ntarget_vendor=\"unknown\"\nunix"),
+        ("wasm32-unknown-emscripten", "target_arch=\"wasm32\"\ntarget_endian=\"little\"\ntarget_env=\"\"\ntarget_family=\"unix\"\ntarget_family=\"wasm\"\ntarget_os=\"emscripten\"\ntarget_pointer_width=\"32\"\ntarget_vendor=\"unknown\"\nunix"),
+    ];
+
+    fn sorted(mut options: Vec<(String, Option<String>)>) -> Vec<(String, Option<String>)> {
+        options.sort();
+        options
+    }
+
+    #[test]
+    fn triples() {
+        for (triple, cfg) in RUSTC {
+            assert_eq!(sorted(triple_options(triple)), sorted(parse_options(cfg)), "{triple}");
+        }
+    }
+
+    #[test]
+    fn rustc() {
+        for (triple, cfg) in RUSTC {
+            // Toolchains without rustc, or too old to know the target, fall back to the triple.
+            if let Some(options) = rustc_options(triple) {
+                assert_eq!(sorted(options), sorted(parse_options(cfg)), "{triple}");
+            }
+        }
+    }
+
+    #[test]
+    fn config() {
+        let target = CfgTarget::new("wasm32-unknown-unknown").with_features(["std"]);
+        let config = target.to_config(&Config::default());
+        assert_eq!(CfgTarget::from_config(&config), target);
+        assert!(enabled(&target, syn::parse_quote!(#[cfg(target_family = "wasm")])));
+        assert!(!enabled(&target, syn::parse_quote!(#[cfg(unix)])));
+    }
+
+    #[test]
+    fn parsing() -> Result<()> {
+        let config = CfgTarget::new("x86_64-pc-windows-msvc").with_features(Vec::<String>::new()).to_config(&Config::default());
+        let module = crate::RustModuleParser::new().transform(quote! {
+            pub mod platform {
+                #[cfg(unix)]
+                pub fn open() {}
+                #[cfg(windows)]
+                pub fn open() {}
+                #[cfg(feature = "serde")]
+                pub struct Serializer;
+                #[cfg(target_feature = "avx2")]
+                pub fn fast() {}
+            }
+        }, &config)?;
+        assert_eq!(module.functions.len(), 2);
+        assert!(module.functions[0].attributes.get_group("cfg").is_some());
+        assert!(module.types.is_empty());
+        Ok(())
+    }
+}
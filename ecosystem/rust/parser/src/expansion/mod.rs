@@ -0,0 +1,226 @@
+//! Macro expansion.
+
+use crate::cargo::{Cargo, Target, TargetKind};
+use crate::prelude::*;
+use crate::CfgTarget;
+use ligen::idl::{Attribute, Attributes, Group, Named};
+
+const EXPAND: &str = "ligen::rust::expand";
+const BOOTSTRAP: &str = "ligen::rust::bootstrap";
+
+/// Whether `config` asks to parse the macro-expanded sources, with `ligen::rust::expand = true`.
+pub fn is_expansion_enabled(config: &Config) -> bool {
+    config
+        .get(EXPAND)
+        .and_then(|literal| literal.as_boolean())
+        .cloned()
+        .unwrap_or(false)
+}
+
+/// Expands the macros of `target`, as `cargo expand` does, with
+/// `cargo rustc -- -Zunpretty=expanded`.
+///
+/// `-Z` flags need a nightly compiler, unless `config` allows them with `ligen::rust::bootstrap`,
+/// as `allow_unstable` describes. The expanded crate has the same modules, inlined, so items keep
+/// their module paths. `#[cfg]`s are evaluated by the compiler, against the features and target
+/// of `cfg`, and the items the compiler adds, like the prelude import and the derived `impl`s, are
+/// removed. The derived traits are put back as `#[derive]`s on their types.
+pub fn expand(cargo: &Cargo, target: &Target, cfg: &CfgTarget, config: &Config) -> Result<syn::File> {
+    let mut command = std::process::Command::new("cargo");
+    allow_unstable(&mut command, &cargo.folder, config)?;
+    command
+        .arg("rustc")
+        .arg("--quiet")
+        .arg("--profile=check")
+        .arg("--manifest-path")
+        .arg(cargo.folder.join("Cargo.toml"));
+    match target.kind {
+        TargetKind::Library => command.arg("--lib"),
+        TargetKind::Binary => command.arg("--bin").arg(&target.name),
+    };
+    if let Some(triple) = &cfg.triple {
+        command.arg("--target").arg(triple);
+    }
+    if let Some(features) = &cfg.features {
+        command.arg("--no-default-features");
+        if !features.is_empty() {
+            command.arg("--features").arg(features.iter().cloned().collect::<Vec<_>>().join(","));
+        }
+    }
+    let output = command
+        .arg("--")
+        .arg("-Zunpretty=expanded")
+        .output()
+        .map_err(|e| Error::Message(format!("Failed to run cargo rustc: {e}")))?;
+    if !output.status.success() {
+        return Err(Error::Message(format!(
+            "Failed to expand {}: {}",
+            target.name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let source = String::from_utf8_lossy(&output.stdout);
+    let mut file = syn::parse_file(&source)
+        .map_err(|e| Error::Message(format!("Failed to parse the expansion of {}: {e:?}", target.name)))?;
+    remove_generated(&mut file.items);
+    Ok(file)
+}
+
+/// Allows the nightly-only `-Z` flags in `command`, which runs cargo for the package at `folder`.
+///
+/// Nightly toolchains allow them already. On the others, `RUSTC_BOOTSTRAP` is only set if
+/// `config` opts in with `ligen::rust::bootstrap = true`, because it unlocks the unstable features
+/// for the whole build. Otherwise, it fails, so the callers can fall back to the stable way.
+pub(crate) fn allow_unstable(command: &mut std::process::Command, folder: &std::path::Path, config: &Config) -> Result<()> {
+    // Rustup picks the toolchain from the working directory, which may have a `rust-toolchain`.
+    command.current_dir(folder);
+    if is_nightly(folder) {
+        return Ok(());
+    }
+    let bootstrap = config
+        .get(BOOTSTRAP)
+        .and_then(|literal| literal.as_boolean())
+        .cloned()
+        .unwrap_or(false);
+    if bootstrap {
+        command.env("RUSTC_BOOTSTRAP", "1");
+        Ok(())
+    } else {
+        Err(Error::Message("The toolchain isn't nightly, and `ligen::rust::bootstrap` isn't set to allow unstable flags on it.".into()))
+    }
+}
+
+/// Whether the toolchain used at `folder` is a nightly or a locally built one.
+fn is_nightly(folder: &std::path::Path) -> bool {
+    std::process::Command::new("rustc")
+        .arg("--version")
+        .current_dir(folder)
+        .output()
+        .map(|output| {
+            let version = String::from_utf8_lossy(&output.stdout);
+            version.contains("-nightly") || version.contains("-dev")
+        })
+        .unwrap_or(false)
+}
+
+/// Removes the items the compiler adds to the expanded crate. The traits of the derived `impl`s
+/// are recorded as `#[derive]`s on the types they're for, as they're written before expansion.
+fn remove_generated(items: &mut Vec<syn::Item>) {
+    let has = |attributes: &[syn::Attribute], name: &str| attributes.iter().any(|attribute| attribute.path().is_ident(name));
+    let mut derived: Vec<(syn::Ident, syn::Path)> = Vec::new();
+    items.retain(|item| match item {
+        syn::Item::Use(import) => !has(&import.attrs, "prelude_import"),
+        syn::Item::ExternCrate(extern_crate) => !has(&extern_crate.attrs, "macro_use") || !matches!(extern_crate.ident.to_string().as_str(), "std" | "core"),
+        syn::Item::Impl(implementation) if has(&implementation.attrs, "automatically_derived") => {
+            if let (Some((None, trait_, _)), syn::Type::Path(type_)) = (&implementation.trait_, implementation.self_ty.as_ref()) {
+                // The compiler adds `StructuralPartialEq` along with `PartialEq`.
+                let is_structural = trait_.segments.last().is_some_and(|segment| segment.ident == "StructuralPartialEq");
+                if let (false, Some(segment)) = (is_structural, type_.path.segments.last()) {
+                    derived.push((segment.ident.clone(), trait_.clone()));
+                }
+            }
+            false
+        },
+        _ => true,
+    });
+    for item in items.iter_mut() {
+        let (identifier, attributes) = match item {
+            syn::Item::Struct(structure) => (&structure.ident, &mut structure.attrs),
+            syn::Item::Enum(enumeration) => (&enumeration.ident, &mut enumeration.attrs),
+            syn::Item::Union(union) => (&union.ident, &mut union.attrs),
+            _ => continue,
+        };
+        let traits = derived.iter().filter(|(type_, _)| type_ == identifier).map(|(_, trait_)| trait_).collect::<Vec<_>>();
+        if !traits.is_empty() {
+            attributes.push(syn::parse_quote!(#[derive(#(#traits),*)]));
+        }
+    }
+    for item in items {
+        if let syn::Item::Mod(syn::ItemMod { content: Some((_, items)), .. }) = item {
+            remove_generated(items);
+        }
+    }
+}
+
+/// The macro invocations of unexpanded sources, recorded as opaque attributes, like
+/// `#[macro(path = "vec", tokens = "1, 2")]`, so they aren't silently lost. `macro_rules!`
+/// definitions aren't invocations.
+pub(crate) fn macro_invocations<'a>(macros: impl IntoIterator<Item = &'a syn::Macro>) -> Attributes {
+    macros
+        .into_iter()
+        .filter(|invocation| !invocation.path.is_ident("macro_rules"))
+        .map(|invocation| {
+            let path = invocation.path.segments.iter().map(|segment| segment.ident.to_string()).collect::<Vec<_>>().join("::");
+            let tokens = invocation.tokens.to_string();
+            let attributes = Attributes::from(vec![Named::new("path", path), Named::new("tokens", tokens)]);
+            Attribute::Group(Group::new("macro", attributes))
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_items() {
+        let mut file: syn::File = syn::parse_quote! {
+            #[prelude_import]
+            use std::prelude::rust_2021::*;
+            #[macro_use]
+            extern crate std;
+            pub struct Point;
+            #[automatically_derived]
+            impl ::core::fmt::Debug for Point {}
+            #[automatically_derived]
+            impl ::core::marker::StructuralPartialEq for Point {}
+            #[automatically_derived]
+            impl ::core::cmp::PartialEq for Point {}
+            pub mod shapes {
+                #[automatically_derived]
+                impl ::core::clone::Clone for Circle {}
+                pub struct Circle;
+            }
+        };
+        remove_generated(&mut file.items);
+        assert_eq!(file.items.len(), 2);
+        let point = &file.items[0];
+        assert_eq!(
+            quote!(#point).to_string(),
+            quote!(#[derive(::core::fmt::Debug, ::core::cmp::PartialEq)] pub struct Point;).to_string()
+        );
+        let syn::Item::Mod(syn::ItemMod { content: Some((_, items)), .. }) = &file.items[1] else {
+            panic!("Expected a module.");
+        };
+        assert_eq!(quote!(#(#items)*).to_string(), quote!(#[derive(::core::clone::Clone)] pub struct Circle;).to_string());
+    }
+
+    #[test]
+    fn derived_traits() -> Result<()> {
+        let mut file: syn::File = syn::parse_quote! {
+            pub struct Point;
+            #[automatically_derived]
+            impl ::core::fmt::Debug for Point {}
+        };
+        remove_generated(&mut file.items);
+        let items = &file.items;
+        let module = crate::RustModuleParser::new().transform(quote! { pub mod shapes { #(#items)* } }, &Config::default())?;
+        let derive = module.types[0].attributes.get_group("derive").expect("Expected a derive attribute.");
+        assert_eq!(derive.attributes.attributes.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn invocations() -> Result<()> {
+        let module = crate::RustModuleParser::new().transform(quote! {
+            pub mod api {
+                macro_rules! endpoint { ($name:ident) => { pub fn $name() {} }; }
+                endpoint!(users);
+            }
+        }, &Config::default())?;
+        let invocation = module.attributes.get_group("macro").expect("Expected a macro attribute.");
+        assert_eq!(invocation.get_named("path"), Some(&"endpoint".into()));
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
     RustAttributeParser, RustIdentifierParser, RustParameterParser, RustSynchronyParser,
     RustTypeParser, RustVisibilityParser,
 };
-use ligen::idl::{Attributes, Method, Mutability, Parameter, Type};
+use ligen::idl::{Attributes, Method, Mutability, Parameter, Receiver, Type};
 
 #[derive(Default)]
 pub struct RustMethodParser {
@@ -20,6 +20,20 @@ impl RustMethodParser {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// How a receiver of type `type_` takes `self`. `Self` itself is owned, `&Self` and
+    /// `&mut Self` are borrowed, and any other type is a pointer to it, like `Box<Self>`.
+    pub(crate) fn receiver(type_: Type) -> Receiver {
+        if type_ == Type::from("Self") {
+            Receiver::Owned
+        } else if type_ == Type::constant_reference("Self") {
+            Receiver::Shared
+        } else if type_ == Type::mutable_reference("Self") {
+            Receiver::Mutable
+        } else {
+            Receiver::Boxed(type_)
+        }
+    }
 }
 
 impl Transformer<syn::ImplItemFn, Method> for RustMethodParser {
@@ -30,6 +44,8 @@ impl Transformer<syn::ImplItemFn, Method> for RustMethodParser {
             } else {
                 Mutability::Constant
             };
+            let receiver = self.parameter_parser.transform(syn::FnArg::Receiver(receiver.clone()), config)?;
+            let receiver = Self::receiver(receiver.type_);
             let syn::Signature {
                 asyncness,
                 ident,
@@ -50,6 +66,7 @@ impl Transformer<syn::ImplItemFn, Method> for RustMethodParser {
             let body = ();
             Ok(Method {
                 mutability,
+                receiver,
                 attributes: Attributes {
                     attributes: method
                         .attrs
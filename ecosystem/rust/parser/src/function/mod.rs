@@ -8,6 +8,7 @@ pub use synchrony::*;
 
 use crate::prelude::*;
 use ligen::idl::{Function, Parameter, Type};
+use ligen::ir::Block;
 use crate::{RustIdentifierParser, RustAttributesParser, RustVisibilityParser, RustTypeParser, RustBlockParser};
 
 #[derive(Default)]
@@ -18,7 +19,7 @@ pub struct RustFunctionParser {
     synchrony_parser: RustSynchronyParser,
     parameter_parser: RustParameterParser,
     type_parser: RustTypeParser,
-    _block_parser: RustBlockParser
+    block_parser: RustBlockParser
 }
 
 impl RustFunctionParser {
@@ -40,6 +41,21 @@ impl Transformer<syn::ItemFn, Function> for RustFunctionParser {
     }
 }
 
+/// A function with its body, for the generators translating it, like the WGSL one.
+impl Transformer<syn::ItemFn, Function<Block>> for RustFunctionParser {
+    fn transform(&self, function: syn::ItemFn, config: &Config) -> Result<Function<Block>> {
+        let block = *function.block.clone();
+        let signature: Function = self.transform(function, config)?;
+        let Function { attributes, visibility, synchrony, identifier, inputs, output, .. } = signature;
+        let body = if output.is_some() {
+            self.block_parser.body(block, config)?
+        } else {
+            self.block_parser.transform(block, config)?
+        };
+        Ok(Function { attributes, visibility, synchrony, identifier, inputs, output, body })
+    }
+}
+
 impl Transformer<syn::ImplItemFn, Function> for RustFunctionParser {
     fn transform(&self, function: syn::ImplItemFn, config: &Config) -> Result<Function> {
         if function.sig.receiver().is_some() {
@@ -135,6 +151,17 @@ mod test {
         assert_eq(RustFunctionParser::default(), mock::function_async(), "pub async fn test() {}")
     }
 
+    #[test]
+    fn function_body() -> Result<()> {
+        use ligen::idl::{Function, Literal, Path, Type};
+        use ligen::ir::{BinaryExpression, Block, Statement};
+        let function: syn::ItemFn = syn::parse_str("fn half(a: f32) -> f32 { a / 2.0 }").map_err(|error| Error::Message(error.to_string()))?;
+        let function: Function<Block> = RustFunctionParser::default().transform(function, &Config::default())?;
+        assert_eq!(function.output, Some(Type::f32()));
+        assert_eq!(function.body, Block::from(Statement::return_(Some(BinaryExpression::new(Path::from("a"), "/", Literal::Float(2.0))))));
+        Ok(())
+    }
+
     #[test]
     fn function_complete() -> Result<()> {
         assert_eq(RustFunctionParser::default(), mock::function_complete(), "#[test(a = \"b\")] pub async fn test(a: String, b: &String, c: &mut String) -> &String {}")
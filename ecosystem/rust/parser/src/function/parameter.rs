@@ -1,7 +1,7 @@
 //! Function parameter.
 
 use crate::prelude::*;
-use ligen::idl::{Identifier, Type, Mutability, Parameter};
+use ligen::idl::{Identifier, Parameter};
 use crate::{RustIdentifierParser, RustAttributesParser, RustTypeParser};
 
 #[derive(Default)]
@@ -27,22 +27,12 @@ impl Transformer<syn::FnArg, Parameter> for RustParameterParser {
                     Err(Error::Message("Identifier not found".into()))
                 }
             }
-            // TODO: Implement translation for syn::Receiver. `Self` should be the fully qualified Type path.
-            syn::FnArg::Receiver(syn::Receiver {
-                                attrs,
-                                reference,
-                                mutability,
-                                ..
-                            }) => {
+            // `ty` is the receiver's full type, `&Self`, `&mut Self`, `Self` or the explicit one of
+            // `self: Box<Self>`. `Self` is resolved by `RustInterfaceParser`, which knows the type.
+            syn::FnArg::Receiver(syn::Receiver { attrs, ty, .. }) => {
                 let attributes = self.attributes_parser.transform(attrs, config)?;
                 let identifier = Identifier::new("self");
-                let type_ = reference
-                    .map(|_| {
-                        let mutability = if mutability.is_none() { Mutability::Constant } else { Mutability::Mutable };
-                        let type_ = Type::from(Identifier::new("Self"));
-                        Type::reference(mutability, type_)
-                    })
-                    .unwrap_or_else(|| Type::from(Identifier::new("Self")));
+                let type_ = self.type_parser.transform(*ty, config)?;
                 let default_value = Default::default();
                 Ok(Parameter { attributes, identifier, type_, default_value })
             },
@@ -1,7 +1,8 @@
 use crate::prelude::*;
 
-use crate::{RustFunctionParser, RustMethodParser, RustObjectParser, RustTypeParser, RustAttributesParser};
-use ligen::idl::{Path, Interface, Visibility, Function, Method, Object};
+use crate::module::qualify;
+use crate::{CfgTarget, RustFunctionParser, RustMethodParser, RustObjectParser, RustPathParser, RustTypeParser, RustTypeAliasParser, RustAttributesParser};
+use ligen::idl::{Path, Interface, KindDefinition, Visibility, Function, Method, Object, Receiver, Type, TypeDefinition};
 
 
 #[derive(Default)]
@@ -10,7 +11,9 @@ pub struct RustInterfaceParser {
     function_parser: RustFunctionParser,
     method_parser: RustMethodParser,
     object_parser: RustObjectParser,
+    type_alias_parser: RustTypeAliasParser,
     attributes_parser: RustAttributesParser,
+    path_parser: RustPathParser,
 }
 
 impl RustInterfaceParser {
@@ -20,33 +23,97 @@ impl RustInterfaceParser {
 }
 
 impl Transformer<syn::ItemImpl, Interface> for RustInterfaceParser {
+    /// An `impl` block. A trait implementation has the trait's path in `interfaces`.
     fn transform(&self, input: syn::ItemImpl, config: &Config) -> Result<Interface> {
-        let attributes = self.attributes_parser.transform(input.attrs, config)?;
+        let mut attributes = self.attributes_parser.transform(input.attrs, config)?;
         let visibility = Visibility::Public;
 
-        // TODO: What should we do with the self type?
-        let type_ = self.type_parser.transform(*input.self_ty, config)?;
-        let identifier = type_.path.last().clone().into(); // TODO: Fix this
+        // Diagnostics name the type as written, the IDL gets its fully qualified path.
+        let written = self.type_parser.transform(*input.self_ty, config)?;
+        let type_ = Type::from(qualify(written.path.clone(), config));
+        let identifier = type_.path.last().identifier.clone();
 
-        let functions = self.extract_functions(input.items.as_slice(), config)?;
-        let methods = self.extract_methods(input.items.as_slice(), config)?;
-        let objects = self.extract_objects(input.items.as_slice(), config)?;
-        let interfaces = self.extract_interfaces(input.items.as_slice(), config)?;
-        Ok(Interface { attributes, visibility, identifier, methods, objects, functions, interfaces })
+        let target = CfgTarget::from_config(config);
+        let items = input
+            .items
+            .into_iter()
+            .filter(|item| target.is_enabled(crate::cfg::impl_item_attributes(item)))
+            .collect::<Vec<_>>();
+        let macros = items.iter().filter_map(|item| match item {
+            syn::ImplItem::Macro(item) => Some(&item.mac),
+            _ => None,
+        });
+        attributes.attributes.extend(crate::expansion::macro_invocations(macros).attributes);
+        let functions = self.extract_functions(&written.path, items.as_slice(), config)?;
+        let methods = self.extract_methods(&written.path, items.as_slice(), config)?;
+        let objects = self.extract_objects(items.as_slice(), config)?;
+        let types = self.extract_types(items.as_slice(), config)?;
+        let interfaces = self.extract_interfaces(input.trait_, config)?;
+        let mut interface = Interface { attributes, visibility, identifier, methods, objects, types, functions, interfaces };
+        resolve_self(&mut interface, &type_);
+        Ok(interface)
+    }
+}
+
+/// Replaces `Self` with `self_type` in every type of `interface`, keeping what comes after it,
+/// like the `Item` of `Self::Item`.
+pub(crate) fn resolve_self(interface: &mut Interface, self_type: &Type) {
+    let types = interface
+        .objects
+        .iter_mut()
+        .map(|object| &mut object.type_)
+        .chain(interface.types.iter_mut().flat_map(|definition| match &mut definition.definition {
+            KindDefinition::TypeAlias(alias) => Some(&mut alias.type_),
+            _ => None,
+        }))
+        .chain(interface.functions.iter_mut().flat_map(|function| {
+            function.inputs.iter_mut().map(|parameter| &mut parameter.type_).chain(function.output.iter_mut())
+        }))
+        .chain(interface.methods.iter_mut().flat_map(|method| {
+            let receiver = match &mut method.receiver {
+                Receiver::Boxed(type_) => Some(type_),
+                _ => None,
+            };
+            receiver
+                .into_iter()
+                .chain(method.inputs.iter_mut().map(|parameter| &mut parameter.type_))
+                .chain(method.output.iter_mut())
+        }));
+    for type_ in types {
+        replace_self(type_, self_type);
+    }
+}
+
+fn replace_self(type_: &mut Type, self_type: &Type) {
+    if type_.path.segments.first().is_some_and(|segment| segment.identifier == "Self") {
+        let rest = type_.path.clone().without_first();
+        type_.path = self_type.path.clone().join(rest);
+    } else {
+        for segment in &mut type_.path.segments {
+            for generic in &mut segment.generics.types {
+                replace_self(generic, self_type);
+            }
+        }
     }
 }
 
 impl RustInterfaceParser {
-    fn extract_interfaces(&self, _items: &[syn::ImplItem], _config: &Config) -> Result<Vec<Path>> {
-        Ok(Default::default())
+    fn extract_interfaces(&self, trait_: Option<(Option<syn::Token![!]>, syn::Path, syn::Token![for])>, config: &Config) -> Result<Vec<Path>> {
+        match trait_ {
+            Some((None, path, _)) => Ok(vec![qualify(self.path_parser.transform(path, config)?, config)]),
+            _ => Ok(Default::default()),
+        }
     }
 
-    fn extract_methods(&self, items: &[syn::ImplItem], config: &Config) -> Result<Vec<Method>> {
+    fn extract_methods(&self, self_type: &Path, items: &[syn::ImplItem], config: &Config) -> Result<Vec<Method>> {
         let mut methods = Vec::new();
         for item in items {
             if let syn::ImplItem::Fn(method) = item {
-                if let Ok(method) = self.method_parser.transform(method.clone(), config) {
-                    methods.push(method);
+                if method.sig.receiver().is_some() {
+                    match self.method_parser.transform(method.clone(), config) {
+                        Ok(parsed) => methods.push(parsed),
+                        Err(error) => self.report_skipped("skipped-method", self_type, method, error, config)
+                    }
                 }
             }
         }
@@ -63,15 +130,107 @@ impl RustInterfaceParser {
         Ok(objects)
     }
 
-    fn extract_functions(&self, items: &[syn::ImplItem], config: &Config) -> Result<Vec<Function>> {
+    fn extract_types(&self, items: &[syn::ImplItem], config: &Config) -> Result<Vec<TypeDefinition>> {
+        let mut types = Vec::new();
+        for item in items {
+            if let syn::ImplItem::Type(type_) = item {
+                types.push(self.type_alias_parser.transform(type_.clone(), config)?);
+            }
+        }
+        Ok(types)
+    }
+
+    fn extract_functions(&self, self_type: &Path, items: &[syn::ImplItem], config: &Config) -> Result<Vec<Function>> {
         let mut functions = Vec::new();
         for item in items {
             if let syn::ImplItem::Fn(function) = item {
-                if let Ok(function) = self.function_parser.transform(function.clone(), config) {
-                    functions.push(function);
+                if function.sig.receiver().is_none() {
+                    match self.function_parser.transform(function.clone(), config) {
+                        Ok(parsed) => functions.push(parsed),
+                        Err(error) => self.report_skipped("skipped-function", self_type, function, error, config)
+                    }
                 }
             }
         }
         Ok(functions)
     }
-}
\ No newline at end of file
+
+    fn report_skipped(&self, code: &str, self_type: &Path, function: &syn::ImplItemFn, error: Error, config: &Config) {
+        let symbol = format!("{}::{}", self_type, function.sig.ident);
+        let diagnostic = Diagnostic::warning(code, error.to_string())
+            .with_symbol(symbol)
+            .with_span(syn2::span::span(function));
+        config.diagnostics().report(diagnostic);
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skipped_items_are_reported() -> Result<()> {
+        let config = Config::default();
+        let input: syn::ItemImpl = syn::parse_str(
+            "impl Type {\n    fn pair((a, b): (i32, i32)) {}\n    fn method(&self) {}\n}"
+        ).map_err(|error| Error::Message(error.to_string()))?;
+        let interface = RustInterfaceParser::new().transform(input, &config)?;
+        assert_eq!(interface.functions.len(), 0);
+        assert_eq!(interface.methods.len(), 1);
+
+        let diagnostics = config.diagnostics().to_vec();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].code, "skipped-function");
+        assert_eq!(diagnostics[0].symbol.as_deref(), Some("Type::pair"));
+        assert_eq!(diagnostics[0].span.as_ref().map(|span| span.line), Some(2));
+        Ok(())
+    }
+
+    #[test]
+    fn associated_items() -> Result<()> {
+        let input: syn::ItemImpl = syn::parse_str(
+            "impl Iterator for Counter {\n    type Item = u32;\n    const STEP: u32 = 1;\n}"
+        ).map_err(|error| Error::Message(error.to_string()))?;
+        let interface = RustInterfaceParser::new().transform(input, &Config::default())?;
+        assert_eq!(interface.types.len(), 1);
+        assert_eq!(interface.types[0].identifier, "Item");
+        assert_eq!(interface.types[0].definition, ligen::idl::TypeAlias { type_: ligen::idl::Type::u32() }.into());
+        assert_eq!(interface.objects.len(), 1);
+        assert_eq!(interface.interfaces, vec![Path::from("Iterator")]);
+        Ok(())
+    }
+
+    #[test]
+    fn self_type() -> Result<()> {
+        use ligen::idl::{Mutability, PathSegment};
+        let input: syn::ItemMod = syn::parse_str(
+            "mod root { mod shapes { struct Counter<T>(T); impl Counter<T> {\n    fn new() -> Self { todo!() }\n    fn get(&self) -> T { todo!() }\n    fn reset(&mut self) {}\n    fn take(self) {}\n    fn boxed(self: Box<Self>) {}\n} } }"
+        ).map_err(|error| Error::Message(error.to_string()))?;
+        let module = crate::RustModuleParser::new().transform(input, &Config::default())?;
+        let interface = &module.modules[0].interfaces[0];
+        let mut counter = Path::from("crate::shapes::Counter");
+        counter.last_mut().generics = vec![Type::from("T")].into();
+        let counter = Type::from(counter);
+        assert_eq!(interface.identifier, "Counter");
+        assert_eq!(interface.functions[0].output, Some(counter.clone()));
+        let receivers = interface.methods.iter().map(|method| method.receiver.clone()).collect::<Vec<_>>();
+        let boxed = Type::from(Path::from(PathSegment::new("Box", counter)));
+        assert_eq!(receivers, vec![Receiver::Shared, Receiver::Mutable, Receiver::Owned, Receiver::Boxed(boxed)]);
+        assert_eq!(interface.methods[1].mutability, Mutability::Mutable);
+        Ok(())
+    }
+
+    #[test]
+    fn qualified_paths() -> Result<()> {
+        let input: syn::ItemMod = syn::parse_str(
+            "mod root {\n    use std::fmt::Display;\n    use shapes::Circle as Round;\n    mod shapes { pub struct Circle; }\n    impl Round { fn new() -> Self { todo!() } }\n    impl Display for Round {}\n    impl Foreign { fn new() -> Self { todo!() } }\n}"
+        ).map_err(|error| Error::Message(error.to_string()))?;
+        let module = crate::RustModuleParser::new().transform(input, &Config::default())?;
+        let interfaces = &module.interfaces;
+        assert_eq!(interfaces[0].functions[0].output, Some(Type::from("crate::shapes::Circle")));
+        assert_eq!(interfaces[1].interfaces, vec![Path::from("std::fmt::Display")]);
+        // Types that are neither declared nor imported are kept as written.
+        assert_eq!(interfaces[2].functions[0].output, Some(Type::from("Foreign")));
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 pub mod prelude;
 
+mod cfg;
+mod expansion;
 mod function;
 mod identifier;
 mod interface;
@@ -10,12 +12,15 @@ mod module;
 mod mutability;
 mod object;
 mod path;
+mod rustdoc;
 mod types;
 mod visibility;
 
 pub mod cargo;
 pub mod registry;
 
+pub use cfg::*;
+pub use expansion::*;
 pub use function::*;
 pub use identifier::*;
 pub use interface::*;
@@ -26,6 +31,7 @@ pub use module::*;
 pub use mutability::*;
 pub use object::*;
 pub use path::*;
+pub use rustdoc::*;
 pub use registry::*;
 pub use types::*;
 pub use visibility::*;
@@ -1,25 +1,51 @@
-use crate::cargo::Cargo;
+use crate::cargo::{Cargo, Target};
+use crate::{expand, is_expansion_enabled, is_rustdoc_enabled, rustdoc, CfgTarget, RustdocParser};
 use crate::prelude::*;
 use anyhow::Context;
 use ligen::idl::{Identifier, Language, Library, Metadata};
 
 use crate::module::RustModuleParser;
 
+/// Parses a package's library target, from `[lib] path` or `src/lib.rs`. Packages without one
+/// are parsed from their first binary target, like `src/main.rs`.
+///
+/// With `ligen::rust::frontend` set to `"rustdoc"`, the crate is parsed from the JSON rustdoc
+/// generates for it, see `RustdocParser`, falling back to the sources if that fails.
+///
+/// With `ligen::rust::expand` set to `true`, the macro-expanded sources are parsed, see `expand`.
+/// If they can't be expanded, the unexpanded sources are parsed, with a `unexpanded-macros`
+/// warning, and their macro invocations are kept as `#[macro(...)]` attributes.
 #[derive(Default)]
 pub struct RustLibraryParser {
-    module_parser: RustModuleParser
+    module_parser: RustModuleParser,
+    rustdoc_parser: RustdocParser,
 }
 
 impl RustLibraryParser {
     pub fn new() -> Self {
         Default::default()
     }
-}
 
-impl Transformer<&std::path::Path, Library> for RustLibraryParser {
-    fn transform(&self, input: &std::path::Path, config: &Config) -> Result<Library> {
+    /// Parses each binary target of the package at `input` as its own library, named after the
+    /// binary. A binary named like the package is suffixed with `-bin`, so it doesn't collide
+    /// with the library.
+    pub fn parse_binaries(&self, input: &std::path::Path, config: &Config) -> Result<Vec<Library>> {
         let cargo = Cargo::new(input).context("Failed to create Cargo instance")?;
-        let identifier = Identifier::from(cargo.get_name()?.clone());
+        let package = cargo.get_name()?.clone();
+        let mut libraries = Vec::new();
+        for target in cargo.get_binary_targets()? {
+            let mut library = self.target(&cargo, &target, config)?;
+            library.identifier = if target.name == package {
+                Identifier::from(format!("{}-bin", target.name))
+            } else {
+                Identifier::from(target.name.clone())
+            };
+            libraries.push(library);
+        }
+        Ok(libraries)
+    }
+
+    fn metadata(&self, cargo: &Cargo) -> Result<Metadata> {
         let authors = cargo.get_authors()?;
         let dependencies = cargo.get_dependencies()?;
         let keywords = cargo.get_keywords()?;
@@ -29,11 +55,68 @@ impl Transformer<&std::path::Path, Library> for RustLibraryParser {
         let summary = Default::default();
         let description = cargo.get_description()?;
         let homepage = cargo.get_homepage()?;
-        let table = Default::default();
-        let metadata = Metadata { authors, dependencies, keywords, license, version, language, summary, description, homepage, table };
-        let root_module = self.module_parser.transform(cargo.folder.join("src").join("lib.rs").as_path(), config)?;
+        let mut table = std::collections::HashMap::new();
+        if let Some(repository) = cargo.get_repository()? {
+            table.insert("repository".to_string(), repository);
+        }
+        if let Some(documentation) = cargo.get_documentation()? {
+            table.insert("documentation".to_string(), documentation);
+        }
+        Ok(Metadata { authors, dependencies, keywords, license, version, language, summary, description, homepage, table })
+    }
+
+    fn target(&self, cargo: &Cargo, target: &Target, config: &Config) -> Result<Library> {
+        let identifier = Identifier::from(cargo.get_name()?.clone());
+        let metadata = self.metadata(cargo)?;
+        let mut cfg = CfgTarget::from_config(config);
+        if cfg.features.is_none() {
+            cfg = cfg.with_features(cargo.get_default_features());
+        }
+        let config = &cfg.to_config(config);
+        let root_module = if is_rustdoc_enabled(config) {
+            match rustdoc(cargo, target, &cfg, config).and_then(|json| self.rustdoc_parser.transform(&json, config)) {
+                Ok(library) => {
+                    // Named after the crate root file, as the `syn` frontend names it.
+                    let mut root_module = library.root_module;
+                    root_module.identifier = Identifier::from(target.path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default());
+                    root_module
+                },
+                Err(error) => {
+                    let diagnostic = Diagnostic::warning("rustdoc-failed", format!("{error} Parsing the sources instead."))
+                        .with_symbol(target.name.clone());
+                    config.diagnostics().report(diagnostic);
+                    self.module_parser.parse_crate_root(target.path.as_path(), config)?
+                }
+            }
+        } else if is_expansion_enabled(config) {
+            match expand(cargo, target, &cfg, config) {
+                Ok(file) => self.module_parser.parse_file(target.path.as_path(), file, config)?,
+                Err(error) => {
+                    let diagnostic = Diagnostic::warning("unexpanded-macros", format!("{error} Parsing the unexpanded sources instead."))
+                        .with_symbol(target.name.clone());
+                    config.diagnostics().report(diagnostic);
+                    self.module_parser.parse_crate_root(target.path.as_path(), config)?
+                }
+            }
+        } else {
+            self.module_parser.parse_crate_root(target.path.as_path(), config)?
+        };
         let library = Library { identifier, metadata, root_module };
         Ok(library)
     }
 }
 
+impl Transformer<&std::path::Path, Library> for RustLibraryParser {
+    fn transform(&self, input: &std::path::Path, config: &Config) -> Result<Library> {
+        let cargo = Cargo::new(input).context("Failed to create Cargo instance")?;
+        let target = match cargo.get_library_target()? {
+            Some(target) => target,
+            None => cargo
+                .get_binary_targets()?
+                .into_iter()
+                .next()
+                .ok_or_else(|| Error::Message(format!("Package {} has no library or binary target.", cargo.get_name().map(String::as_str).unwrap_or_default())))?,
+        };
+        self.target(&cargo, &target, config)
+    }
+}
@@ -5,13 +5,75 @@ pub use import::*;
 
 use crate::prelude::*;
 use crate::{
-    RustAttributesParser, RustEnumerationParser, RustFunctionParser, RustIdentifierParser,
+    CfgTarget, RustAttributesParser, RustEnumerationParser, RustFunctionParser, RustIdentifierParser,
     RustInterfaceParser, RustObjectParser, RustStructureParser, RustTypeAliasParser,
-    RustVisibilityParser,
+    RustUnionParser, RustVisibilityParser,
 };
-use ligen::idl::{Function, Import, Interface, Module, Object, TypeDefinition};
+use ligen::idl::{Function, Import, Interface, Literal, Module, Object, Path, TypeDefinition};
 use syn::spanned::Spanned;
 
+const MODULE: &str = "ligen::rust::module";
+const SCOPE: &str = "ligen::rust::scope";
+
+/// Path of the module being parsed, from the crate root, which `ligen::rust::module` carries down
+/// to the items of nested modules. It's empty in the crate root.
+pub(crate) fn module_path(config: &Config) -> Path {
+    config
+        .get(MODULE)
+        .and_then(|literal| literal.as_string())
+        .map(|path| Path::from(path.as_str()))
+        .unwrap_or_default()
+}
+
+/// `path`, written in the module at `module`, from the crate root if it starts with `self` or
+/// `super`. Other paths are kept as written.
+pub(crate) fn absolute(path: Path, module: &Path) -> Path {
+    let root = Path::from("crate");
+    let first = path.segments.first().map(|segment| segment.identifier.name.clone()).unwrap_or_default();
+    match first.as_str() {
+        "self" => root.join(module.clone()).join(path.without_first()),
+        "super" => {
+            let mut module = module.clone();
+            let mut path = path;
+            while path.segments.first().is_some_and(|segment| segment.identifier == "super") {
+                path.pop_front();
+                module.pop_back();
+            }
+            root.join(module).join(path)
+        },
+        _ => path,
+    }
+}
+
+/// The fully qualified path of a type or trait written in the module being parsed.
+///
+/// Its first segment is looked up among the items the module declares and imports, which
+/// `ligen::rust::scope` carries. Paths naming nothing there, like the prelude's types, generic
+/// parameters and glob imported items, are kept as written.
+pub(crate) fn qualify(path: Path, config: &Config) -> Path {
+    let path = absolute(path, &module_path(config));
+    let Some(first) = path.segments.first() else {
+        return path;
+    };
+    let target = match config.get(SCOPE) {
+        Some(Literal::Array(scope)) => scope.iter().find_map(|entry| match entry {
+            Literal::Tuple(entry) => match entry.as_slice() {
+                [Literal::String(name), Literal::String(target)] if first.identifier == name.as_str() => Some(Path::from(target.as_str())),
+                _ => None,
+            },
+            _ => None,
+        }),
+        _ => None,
+    };
+    match target {
+        Some(mut target) => {
+            target.last_mut().generics = first.generics.clone();
+            target.join(path.without_first())
+        },
+        None => path,
+    }
+}
+
 #[derive(Default)]
 pub struct RustModuleParser {
     interface_parser: RustInterfaceParser,
@@ -23,6 +85,7 @@ pub struct RustModuleParser {
     type_alias_parser: RustTypeAliasParser,
     enumeration_parser: RustEnumerationParser,
     structure_parser: RustStructureParser,
+    union_parser: RustUnionParser,
     imports_parser: RustImportsParser,
     literal_parser: crate::literal::RustLiteralParser,
 }
@@ -61,7 +124,17 @@ impl Transformer<syn::ItemMod, Module> for RustModuleParser {
             .content
             .map(|(_, items)| items)
             .ok_or("Module file isn't loaded.")?;
-        let attributes = self.attributes_parser.transform(module.attrs, config)?;
+        let target = CfgTarget::from_config(config);
+        let items = items
+            .into_iter()
+            .filter(|item| target.is_enabled(crate::cfg::item_attributes(item)))
+            .collect::<Vec<_>>();
+        let mut attributes = self.attributes_parser.transform(module.attrs, config)?;
+        let macros = items.iter().filter_map(|item| match item {
+            syn::Item::Macro(item) => Some(&item.mac),
+            _ => None,
+        });
+        attributes.attributes.extend(crate::expansion::macro_invocations(macros).attributes);
         let visibility = self.visibility_parser.transform(module.vis, config)?;
         let identifier = self.identifier_parser.transform(module.ident, config)?;
 
@@ -69,10 +142,11 @@ impl Transformer<syn::ItemMod, Module> for RustModuleParser {
         // constant, an array length or a nested module can refer to one by name.
         let config = &self.constants(items.as_slice(), config);
         let imports = self.extract_imports(items.as_slice(), config)?;
+        let config = &self.scope(items.as_slice(), imports.as_slice(), config);
         let functions = self.extract_functions(items.as_slice(), config)?;
         let objects = self.extract_objects(items.as_slice(), config)?;
         let types = self.extract_types(items.as_slice(), config)?;
-        let interfaces = self.extract_interfaces(items.as_slice())?;
+        let interfaces = self.extract_interfaces(items.as_slice(), config)?;
         let modules = self.extract_modules(items, config)?;
         Ok(Module {
             attributes,
@@ -90,7 +164,20 @@ impl Transformer<syn::ItemMod, Module> for RustModuleParser {
 
 impl Transformer<&std::path::Path, Module> for RustModuleParser {
     fn transform(&self, path: &std::path::Path, config: &Config) -> Result<Module> {
-        let module = syn2::file_parser::parse_file_recursive(path)?;
+        let module = syn2::file_parser::parse_file_recursive(path, &CfgTarget::from_config(config))?;
+        self.parse_file(path, module, config)
+    }
+}
+
+impl RustModuleParser {
+    /// Parses the crate root file at `path`, whose submodules are next to it whatever its name.
+    pub fn parse_crate_root(&self, path: &std::path::Path, config: &Config) -> Result<Module> {
+        let module = syn2::file_parser::parse_crate_recursive(path, &CfgTarget::from_config(config))?;
+        self.parse_file(path, module, config)
+    }
+
+    /// Parses the already loaded `module` file at `path`.
+    pub(crate) fn parse_file(&self, path: &std::path::Path, module: syn::File, config: &Config) -> Result<Module> {
         let ident = syn::Ident::new(
             path.file_stem()
                 .unwrap_or_default()
@@ -116,18 +203,61 @@ impl Transformer<&std::path::Path, Module> for RustModuleParser {
         };
         self.transform(module, config)
     }
-}
 
-impl RustModuleParser {
-    fn extract_interfaces(&self, items: &[syn::Item]) -> Result<Vec<Interface>> {
+    /// The config the items of this module are parsed against, with the names of the items it
+    /// declares and imports, and their paths, for `qualify`. It replaces the enclosing module's
+    /// names, as Rust doesn't inherit them.
+    fn scope(&self, items: &[syn::Item], imports: &[Import], config: &Config) -> Config {
+        let module = module_path(config);
+        let declared = items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Struct(item) => Some(&item.ident),
+                syn::Item::Enum(item) => Some(&item.ident),
+                syn::Item::Union(item) => Some(&item.ident),
+                syn::Item::Type(item) => Some(&item.ident),
+                syn::Item::Trait(item) => Some(&item.ident),
+                syn::Item::Mod(item) => Some(&item.ident),
+                _ => None,
+            })
+            .map(|ident| (ident.to_string(), Path::from("crate").join(module.clone()).join(ident.to_string())))
+            .collect::<Vec<_>>();
+        // Imports may start with a module declared here.
+        let imported = imports
+            .iter()
+            .filter(|import| import.path.last().identifier != "*")
+            .map(|import| {
+                let name = import.renaming.as_ref().unwrap_or(&import.path.last().identifier).name.clone();
+                let mut path = absolute(import.path.clone(), &module);
+                if let Some((_, target)) = declared.iter().find(|(declared, _)| path.first().identifier == declared.as_str()) {
+                    path = target.clone().join(path.without_first());
+                }
+                (name, path)
+            })
+            .collect::<Vec<_>>();
+        let scope = declared
+            .into_iter()
+            .chain(imported)
+            .map(|(name, path)| Literal::Tuple(vec![Literal::String(name), Literal::String(path.to_string())]))
+            .collect();
+        let mut config = config.clone();
+        config.set(SCOPE, Literal::Array(scope));
+        config
+    }
+
+    fn extract_interfaces(&self, items: &[syn::Item], config: &Config) -> Result<Vec<Interface>> {
         let mut interfaces = Vec::new();
         for item in items {
             if let syn::Item::Impl(impl_) = item {
-                if let Ok(interface) = self
-                    .interface_parser
-                    .transform(impl_.clone(), &Config::default())
-                {
-                    interfaces.push(interface);
+                match self.interface_parser.transform(impl_.clone(), config) {
+                    Ok(interface) => interfaces.push(interface),
+                    Err(error) => {
+                        let self_ty = &impl_.self_ty;
+                        let diagnostic = Diagnostic::warning("skipped-interface", error.to_string())
+                            .with_symbol(quote::quote!(#self_ty).to_string())
+                            .with_span(syn2::span::span(impl_));
+                        config.diagnostics().report(diagnostic);
+                    }
                 }
             }
         }
@@ -147,10 +277,8 @@ impl RustModuleParser {
                 syn::Item::Type(type_) => {
                     types.push(self.type_alias_parser.transform(type_.clone(), config)?);
                 }
-                syn::Item::Union(_union) => {
-                    return Err(Error::Message(
-                        "Union object isn't implemented yet.".to_string(),
-                    ))
+                syn::Item::Union(union) => {
+                    types.push(self.union_parser.transform(union.clone(), config)?);
                 }
                 _ => (),
             }
@@ -187,7 +315,10 @@ impl RustModuleParser {
             }
         });
         for module in items {
-            modules.push(self.transform(module, config)?)
+            let mut config = config.clone();
+            let path = module_path(&config).join(module.ident.to_string());
+            config.set(MODULE, path.to_string());
+            modules.push(self.transform(module, &config)?)
         }
         Ok(modules)
     }
@@ -195,8 +326,10 @@ impl RustModuleParser {
     fn extract_objects(&self, items: &[syn::Item], config: &Config) -> Result<Vec<Object>> {
         let mut objects = Vec::new();
         for item in items {
-            if let syn::Item::Const(constant) = item {
-                objects.push(self.object_parser.transform(constant.clone(), config)?);
+            match item {
+                syn::Item::Const(constant) => objects.push(self.object_parser.transform(constant.clone(), config)?),
+                syn::Item::Static(static_) => objects.push(self.object_parser.transform(static_.clone(), config)?),
+                _ => (),
             }
         }
         Ok(objects)
@@ -297,6 +430,53 @@ mod tests {
         )
     }
 
+    #[test]
+    fn serde_attributes() -> Result<()> {
+        use ligen::idl::Library;
+        use ligen::utils::transformers::wire::{DefaultValue, InterpretSerde, Tagging, WireName};
+
+        let root_module = RustModuleParser::default().transform(quote! {
+            pub mod shapes {
+                #[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+                pub enum Shape {
+                    #[serde(alias = "Round")]
+                    Circle,
+                    RightTriangle,
+                    #[serde(skip)]
+                    Unknown,
+                }
+
+                #[serde(rename_all(serialize = "camelCase", deserialize = "SCREAMING_SNAKE_CASE"))]
+                pub struct Point {
+                    x_axis: f32,
+                    #[serde(skip_deserializing, default = "Point::origin")]
+                    y_axis: f32,
+                    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+                    extra: Option<Extra>,
+                }
+            }
+        }, &Config::default())?;
+        let library = Library { identifier: "shapes".into(), root_module, ..Default::default() };
+        let interpret = InterpretSerde::default();
+        let formats = interpret.transform(&library);
+
+        let shape = formats.get("Shape").expect("Shape isn't interpreted.");
+        assert_eq!(shape.tagging, Tagging::Adjacent { tag: "kind".into(), content: "data".into() });
+        assert_eq!(shape.variants[0].name, WireName::new("circle").with_aliases(["Round"]));
+        assert_eq!(shape.variants[1].name, WireName::new("right_triangle"));
+        assert!(shape.variants[2].skip.serializing && shape.variants[2].skip.deserializing);
+
+        let point = formats.get("Point").expect("Point isn't interpreted.");
+        assert_eq!(point.fields[0].name.serialize, "xAxis");
+        assert_eq!(point.fields[0].name.deserialize, "X_AXIS");
+        assert!(point.fields[1].skip.deserializing && !point.fields[1].skip.serializing);
+        assert_eq!(point.fields[1].default, Some(DefaultValue::Function("Point::origin".into())));
+        assert!(point.fields[2].flatten);
+        assert_eq!(point.fields[2].skip.serializing_if, Some(Path::from("Option::is_none")));
+        assert!(interpret.diagnostics.to_vec().is_empty());
+        Ok(())
+    }
+
     // TODO: Implement these:
     // #[test]
     // fn module_functions() -> Result<()> {
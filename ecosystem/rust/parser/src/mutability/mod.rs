@@ -21,3 +21,12 @@ impl Transformer<Option<syn::token::Mut>, Mutability> for RustMutabilityParser {
         }
     }
 }
+
+impl Transformer<syn::StaticMutability, Mutability> for RustMutabilityParser {
+    fn transform(&self, mutability: syn::StaticMutability, _config: &Config) -> Result<Mutability> {
+        match mutability {
+            syn::StaticMutability::Mut(_) => Ok(Mutability::Mutable),
+            _ => Ok(Mutability::Constant),
+        }
+    }
+}
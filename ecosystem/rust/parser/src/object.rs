@@ -1,16 +1,19 @@
 use crate::identifier::RustIdentifierParser;
 use crate::literal::RustLiteralParser;
 use crate::types::RustTypeParser;
-use crate::RustVisibilityParser;
-use ligen::idl::{Mutability, Object};
+use crate::{RustAttributesParser, RustMutabilityParser, RustVisibilityParser};
+use ligen::idl::{Literal, Mutability, Object};
+use quote::ToTokens;
 use ligen::transformer::prelude::*;
 
 #[derive(Default)]
 pub struct RustObjectParser {
+    attributes_parser: RustAttributesParser,
     identifier_parser: RustIdentifierParser,
     type_parser: RustTypeParser,
     literal_parser: RustLiteralParser,
     visibility_parser: RustVisibilityParser,
+    mutability_parser: RustMutabilityParser,
 }
 
 impl RustObjectParser {
@@ -24,6 +27,7 @@ impl Transformer<syn::ImplItemConst, Object> for RustObjectParser {
         match item_const.expr {
             syn::Expr::Array(syn::ExprArray { elems, .. }) => {
                 let mutability = Mutability::Constant;
+                let attributes = self.attributes_parser.transform(item_const.attrs, config)?;
                 let visibility = self.visibility_parser.transform(item_const.vis, config)?;
                 let identifier = self
                     .identifier_parser
@@ -31,6 +35,7 @@ impl Transformer<syn::ImplItemConst, Object> for RustObjectParser {
                 let type_ = self.type_parser.transform(item_const.ty, config)?;
                 let literal = self.literal_parser.transform(elems, config)?;
                 Ok(Object {
+                    attributes,
                     visibility,
                     mutability,
                     identifier,
@@ -40,6 +45,7 @@ impl Transformer<syn::ImplItemConst, Object> for RustObjectParser {
             }
             syn::Expr::Lit(syn::ExprLit { lit, .. }) => {
                 let mutability = Mutability::Constant;
+                let attributes = self.attributes_parser.transform(item_const.attrs, config)?;
                 let visibility = self.visibility_parser.transform(item_const.vis, config)?;
                 let identifier = self
                     .identifier_parser
@@ -47,6 +53,7 @@ impl Transformer<syn::ImplItemConst, Object> for RustObjectParser {
                 let type_ = self.type_parser.transform(item_const.ty, config)?;
                 let literal = self.literal_parser.transform(lit, config)?;
                 Ok(Object {
+                    attributes,
                     visibility,
                     mutability,
                     identifier,
@@ -69,6 +76,7 @@ impl Transformer<syn::ItemConst, Object> for RustObjectParser {
     /// why.
     fn transform(&self, item_const: syn::ItemConst, config: &Config) -> Result<Object> {
         let mutability = Mutability::Constant;
+        let attributes = self.attributes_parser.transform(item_const.attrs, config)?;
         let visibility = self.visibility_parser.transform(item_const.vis, config)?;
         let identifier = self
             .identifier_parser
@@ -79,6 +87,36 @@ impl Transformer<syn::ItemConst, Object> for RustObjectParser {
             .transform(*item_const.expr, config)
             .map_err(|error| Error::Message(format!("Constant {}: {error}", item_const.ident)))?;
         Ok(Object {
+            attributes,
+            visibility,
+            mutability,
+            identifier,
+            type_,
+            literal,
+        })
+    }
+}
+
+impl Transformer<syn::ItemStatic, Object> for RustObjectParser {
+    /// A `static` item, mutable if it's a `static mut`, with its value worked out as a constant's.
+    ///
+    /// Statics are often initialized at compile time with a `const fn`, like `AtomicUsize::new(0)`
+    /// or `Mutex::new(Vec::new())`, which has no literal. Those keep their tokens in a
+    /// `Literal::Unknown`.
+    fn transform(&self, item_static: syn::ItemStatic, config: &Config) -> Result<Object> {
+        let mutability = self.mutability_parser.transform(item_static.mutability, config)?;
+        let attributes = self.attributes_parser.transform(item_static.attrs, config)?;
+        let visibility = self.visibility_parser.transform(item_static.vis, config)?;
+        let identifier = self
+            .identifier_parser
+            .transform(item_static.ident.clone(), config)?;
+        let type_ = self.type_parser.transform(*item_static.ty, config)?;
+        let literal = self
+            .literal_parser
+            .transform((*item_static.expr).clone(), config)
+            .unwrap_or_else(|_| Literal::Unknown(item_static.expr.to_token_stream().to_string()));
+        Ok(Object {
+            attributes,
             visibility,
             mutability,
             identifier,
@@ -96,9 +134,12 @@ impl Transformer<proc_macro::TokenStream, Object> for RustObjectParser {
 
 impl Transformer<proc_macro2::TokenStream, Object> for RustObjectParser {
     fn transform(&self, input: proc_macro2::TokenStream, config: &Config) -> Result<Object> {
-        syn::parse2::<syn::ItemConst>(input)
-            .map_err(|e| Error::Message(format!("Failed to parse constant: {e:?}")))
-            .and_then(|constant| self.transform(constant, config))
+        match syn::parse2::<syn::Item>(input) {
+            Ok(syn::Item::Const(constant)) => self.transform(constant, config),
+            Ok(syn::Item::Static(static_)) => self.transform(static_, config),
+            Ok(_) => Err(Error::Message("Expected a constant or a static.".into())),
+            Err(e) => Err(Error::Message(format!("Failed to parse object: {e:?}"))),
+        }
     }
 }
 
@@ -109,6 +150,7 @@ mod test {
     use quote::quote;
 
     use ligen::idl::object::mock;
+    use ligen::idl::Literal;
     use ligen::transformer::assert::assert_eq;
 
     #[test]
@@ -122,5 +164,27 @@ mod test {
         )
     }
 
-    // TODO: Add test for static.
+    #[test]
+    fn static_() -> Result<()> {
+        assert_eq(
+            RustObjectParser::default(),
+            mock::static_(),
+            quote! {
+                pub static mut COUNTER: u32 = 0;
+            },
+        )
+    }
+
+    #[test]
+    fn non_literal_statics() -> Result<()> {
+        let parser = RustObjectParser::default();
+        let config = Config::default();
+        let counter = parser.transform(quote! { static COUNTER: AtomicUsize = AtomicUsize::new(0); }, &config)?;
+        assert_eq!(counter.literal, Literal::Unknown("AtomicUsize :: new (0)".into()));
+        let names = parser.transform(quote! { static NAMES: Mutex<Vec<String>> = Mutex::new(Vec::new()); }, &config)?;
+        assert_eq!(names.literal, Literal::Unknown("Mutex :: new (Vec :: new ())".into()));
+        let cell = parser.transform(quote! { static CELL: OnceLock<u32> = OnceLock::new(); }, &config)?;
+        assert_eq!(cell.literal, Literal::Unknown("OnceLock :: new ()".into()));
+        Ok(())
+    }
 }
@@ -1,36 +1,99 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use ligen::common::*;
+use crate::CfgTarget;
 
-pub fn parse_file_recursive(path: &Path) -> Result<syn::File> {
+/// Parses the module file at `path` and the files of its `mod` declarations, skipping the ones
+/// `target` disables.
+pub fn parse_file_recursive(path: &Path, target: &CfgTarget) -> Result<syn::File> {
     let mut file = load_file(path)?;
-    load_modules(path, &mut file.items)?;
+    let directories = Directories::module(path)?;
+    load_modules(&directories, &mut file.items, target)?;
+    Ok(file)
+}
+
+/// Parses the crate root file at `path`, like `src/lib.rs` or a `[lib] path`, and the files of its
+/// `mod` declarations, skipping the ones `target` disables.
+///
+/// Crate roots own their directory whatever their name, so their submodules are next to them.
+pub fn parse_crate_recursive(path: &Path, target: &CfgTarget) -> Result<syn::File> {
+    let mut file = load_file(path)?;
+    let directories = Directories::owner(path)?;
+    load_modules(&directories, &mut file.items, target)?;
     Ok(file)
 }
 
 fn load_file(path: &Path) -> Result<syn::File> {
-    let content = std::fs::read_to_string(path)?;
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| Error::Message(format!("Failed to read file {}: {e}.", path.display())))?;
     syn::parse_file(&content)
         .map_err(|e| Error::Message(format!("Failed to parse file {e:?}.")))
 }
 
-fn load_modules(path: &Path, items: &mut [syn::Item]) -> Result<()> {
-    let path = path_handling::find_base_path(path)?;
+/// Where the files of a file's `mod` declarations are.
+struct Directories {
+    /// Where `mod name;` looks for `name.rs` and `name/mod.rs`.
+    modules: PathBuf,
+    /// What `#[path = "..."]` is relative to.
+    paths: PathBuf,
+}
+
+impl Directories {
+    /// The directories of a module file. `mod.rs`, `lib.rs` and `main.rs` own their directory,
+    /// and other files have their submodules in a directory named after them.
+    fn module(path: &Path) -> Result<Self> {
+        let modules = path_handling::find_base_path(path)?;
+        let paths = path_handling::parent(path)?;
+        Ok(Self { modules, paths })
+    }
+
+    /// The directories of a file owning its directory, like a crate root or a `#[path]` module.
+    fn owner(path: &Path) -> Result<Self> {
+        let modules = path_handling::parent(path)?;
+        let paths = modules.clone();
+        Ok(Self { modules, paths })
+    }
+
+    /// The directories of the inline module `name`.
+    fn inline(&self, name: &str) -> Self {
+        let modules = self.modules.join(name);
+        let paths = modules.clone();
+        Self { modules, paths }
+    }
+}
+
+fn load_modules(directories: &Directories, items: &mut [syn::Item], target: &CfgTarget) -> Result<()> {
     for item in items {
         if let syn::Item::Mod(module) = item {
-            load_module(&path, module)?;
+            if target.is_enabled(&module.attrs) {
+                load_module(directories, module, target)?;
+            }
         }
     }
     Ok(())
 }
 
-fn load_module(path: &Path, module: &mut syn::ItemMod) -> Result<()> {
-    if module.content.is_none() {
-        let module_name = module.ident.to_string();
-        let module_path = path_handling::find_module_path(path, &module_name)?;
-        let mut file = load_file(&module_path)?;
-        load_modules(&module_path, &mut file.items)?;
-        module.attrs.extend(file.attrs);
-        module.content = Some((syn::token::Brace::default(), file.items));
+fn load_module(directories: &Directories, module: &mut syn::ItemMod, target: &CfgTarget) -> Result<()> {
+    let module_name = module.ident.to_string();
+    match &mut module.content {
+        Some((_, items)) => load_modules(&directories.inline(&module_name), items, target)?,
+        None => {
+            let (module_path, module_directories) = match path_handling::path_attribute(&module.attrs) {
+                Some(path) => {
+                    let module_path = directories.paths.join(path);
+                    let module_directories = Directories::owner(&module_path)?;
+                    (module_path, module_directories)
+                },
+                None => {
+                    let module_path = path_handling::find_module_path(&directories.modules, &module_name)?;
+                    let module_directories = Directories::module(&module_path)?;
+                    (module_path, module_directories)
+                }
+            };
+            let mut file = load_file(&module_path)?;
+            load_modules(&module_directories, &mut file.items, target)?;
+            module.attrs.extend(file.attrs);
+            module.content = Some((syn::token::Brace::default(), file.items));
+        }
     }
     Ok(())
 }
@@ -49,13 +112,28 @@ mod path_handling {
     }
 
     pub fn find_base_path(path: &Path) -> Result<std::path::PathBuf> {
-        if path.ends_with("mod.rs") || path.ends_with("lib.rs") {
-            Ok(path
-                .parent()
-                .ok_or("Failed to get parent directory.")?
-                .to_path_buf())
+        if path.ends_with("mod.rs") || path.ends_with("lib.rs") || path.ends_with("main.rs") {
+            parent(path)
         } else {
             Ok(path.with_extension(""))
         }
     }
+
+    pub fn parent(path: &Path) -> Result<std::path::PathBuf> {
+        Ok(path
+            .parent()
+            .ok_or("Failed to get parent directory.")?
+            .to_path_buf())
+    }
+
+    /// The value of a `#[path = "..."]` attribute.
+    pub fn path_attribute(attributes: &[syn::Attribute]) -> Option<String> {
+        attributes
+            .iter()
+            .filter(|attribute| attribute.path().is_ident("path"))
+            .find_map(|attribute| match &attribute.meta {
+                syn::Meta::NameValue(syn::MetaNameValue { value: syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(path), .. }), .. }) => Some(path.value()),
+                _ => None,
+            })
+    }
 }
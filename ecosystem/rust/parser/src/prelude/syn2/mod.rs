@@ -1,2 +1,3 @@
 pub mod file_parser;
-pub mod punctuated;
\ No newline at end of file
+pub mod punctuated;
+pub mod span;
\ No newline at end of file
@@ -0,0 +1,10 @@
+//! Source spans.
+
+use ligen::common::Span;
+
+/// Source span of a syn node. Requires `proc-macro2`'s `span-locations` feature to be meaningful.
+pub fn span(spanned: &impl syn::spanned::Spanned) -> Span {
+    let span = spanned.span();
+    let (start, end) = (span.start(), span.end());
+    Span::new(start.line, start.column, end.line, end.column)
+}
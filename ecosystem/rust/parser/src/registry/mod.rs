@@ -1,8 +1,14 @@
 use crate::library::RustLibraryParser;
-use ligen::idl::Registry;
+use crate::CfgTarget;
+use crate::cargo::Cargo;
+use ligen::idl::{Library, Registry};
 use ligen::prelude::*;
 use std::process::Stdio;
 
+/// Parses a Cargo workspace and its dependencies into a `Registry`.
+///
+/// With `ligen::rust::binaries` set to `true`, the binary targets of the workspace members are
+/// parsed as their own libraries too, see `RustLibraryParser::parse_binaries`.
 #[derive(Default)]
 pub struct RustRegistryParser;
 
@@ -10,6 +16,20 @@ impl RustRegistryParser {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Parses the package's library and, with `binaries`, its binaries.
+    fn parse_package(library_parser: &RustLibraryParser, package_dir: &std::path::Path, binaries: bool, config: &Config) -> Result<Vec<Library>> {
+        if !binaries {
+            return Ok(vec![library_parser.transform(package_dir, config)?]);
+        }
+        let mut libraries = Vec::new();
+        // Without a library target, the library parser would parse the first binary again.
+        if Cargo::new(package_dir)?.get_library_target()?.is_some() {
+            libraries.push(library_parser.transform(package_dir, config)?);
+        }
+        libraries.extend(library_parser.parse_binaries(package_dir, config)?);
+        Ok(libraries)
+    }
 }
 
 impl Transformer<std::path::PathBuf, Registry> for RustRegistryParser {
@@ -132,6 +152,21 @@ impl RustRegistryParser {
             .filter_map(|v| v.as_str().map(|s| s.to_string()))
             .collect();
 
+        // The features Cargo resolved for each package, which its `#[cfg(feature)]`s are evaluated
+        // against.
+        let mut features = std::collections::HashMap::new();
+        if let Some(nodes) = metadata["resolve"]["nodes"].as_array() {
+            for node in nodes {
+                if let Some(id) = node["id"].as_str() {
+                    let enabled = node["features"]
+                        .as_array()
+                        .map(|enabled| enabled.iter().filter_map(|feature| feature.as_str().map(String::from)).collect::<Vec<_>>())
+                        .unwrap_or_default();
+                    features.insert(id.to_string(), enabled);
+                }
+            }
+        }
+
         let mut package_ids_to_parse = std::collections::HashSet::new();
         for member_id in &workspace_members {
             package_ids_to_parse.insert(member_id.clone());
@@ -154,6 +189,11 @@ impl RustRegistryParser {
         }
 
         let library_parser = RustLibraryParser::new();
+        let binaries = config
+            .get("ligen::rust::binaries")
+            .and_then(|literal| literal.as_boolean())
+            .cloned()
+            .unwrap_or(false);
 
         if let Some(packages) = metadata["packages"].as_array() {
             for package in packages {
@@ -162,10 +202,26 @@ impl RustRegistryParser {
                         if let Some(manifest_path_str) = package["manifest_path"].as_str() {
                             let package_manifest_path = std::path::Path::new(manifest_path_str);
                             let package_dir = package_manifest_path.parent().unwrap();
-                            if let Ok(library) = library_parser.transform(package_dir, config) {
-                                registry
-                                    .libraries
-                                    .insert(library.identifier.clone(), library);
+                            let config = &match features.get(id) {
+                                Some(features) => CfgTarget::from_config(config).with_features(features.clone()).to_config(config),
+                                None => config.clone(),
+                            };
+                            let binaries = binaries && workspace_members.iter().any(|member| member == id);
+                            match Self::parse_package(&library_parser, package_dir, binaries, config) {
+                                Ok(libraries) => {
+                                    for library in libraries {
+                                        registry
+                                            .libraries
+                                            .insert(library.identifier.clone(), library);
+                                    }
+                                }
+                                Err(error) => {
+                                    let span = Span::new(1, 0, 1, 0).with_file(manifest_path_str);
+                                    let mut diagnostic = Diagnostic::warning("skipped-library", error.to_string())
+                                        .with_span(span);
+                                    diagnostic.symbol = package["name"].as_str().map(String::from);
+                                    config.diagnostics().report(diagnostic);
+                                }
                             }
                         }
                     }
@@ -0,0 +1,737 @@
+//! Rustdoc JSON frontend.
+
+use std::collections::HashSet;
+
+use crate::cargo::{Cargo, Target, TargetKind};
+use crate::expansion::allow_unstable;
+use crate::literal::RustLiteralParser;
+use crate::prelude::*;
+use crate::{CfgTarget, RustMethodParser};
+use ligen::idl::{
+    Attribute, Attributes, Enumeration, Field, Function, Generics, Group, Identifier, Import, Interface,
+    KindDefinition, Library, Literal, Method, Module, Mutability, Named, Object, Parameter, Path,
+    PathSegment, Receiver, Structure, Synchrony, Type, TypeAlias, TypeDefinition, Union, Variant, Version, Visibility,
+};
+use serde_json::Value;
+
+const FRONTEND: &str = "ligen::rust::frontend";
+
+/// Whether `config` selects the rustdoc frontend, with `ligen::rust::frontend = "rustdoc"`.
+pub fn is_rustdoc_enabled(config: &Config) -> bool {
+    matches!(config.get(FRONTEND), Some(Literal::String(frontend)) if frontend == "rustdoc")
+}
+
+/// Generates the rustdoc JSON of `target`, with
+/// `cargo rustdoc -- -Zunstable-options --output-format json`.
+///
+/// The JSON output is unstable, so it needs a nightly toolchain, or `ligen::rust::bootstrap` set
+/// to allow it on stable ones. `#[cfg]`s are evaluated against the features and target of `cfg`.
+/// The crate is documented in its own `target/ligen-rustdoc` directory.
+pub fn rustdoc(cargo: &Cargo, target: &Target, cfg: &CfgTarget, config: &Config) -> Result<Value> {
+    let target_dir = cargo.folder.join("target").join("ligen-rustdoc");
+    let mut command = std::process::Command::new("cargo");
+    allow_unstable(&mut command, &cargo.folder, config)?;
+    command
+        .arg("rustdoc")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(cargo.folder.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir);
+    match target.kind {
+        TargetKind::Library => command.arg("--lib"),
+        TargetKind::Binary => command.arg("--bin").arg(&target.name),
+    };
+    if let Some(triple) = &cfg.triple {
+        command.arg("--target").arg(triple);
+    }
+    if let Some(features) = &cfg.features {
+        command.arg("--no-default-features");
+        if !features.is_empty() {
+            command.arg("--features").arg(features.iter().cloned().collect::<Vec<_>>().join(","));
+        }
+    }
+    let output = command
+        .arg("--")
+        .arg("-Zunstable-options")
+        .arg("--output-format")
+        .arg("json")
+        .output()
+        .map_err(|e| Error::Message(format!("Failed to run cargo rustdoc: {e}")))?;
+    if !output.status.success() {
+        return Err(Error::Message(format!(
+            "Failed to document {}: {}",
+            target.name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let mut doc = target_dir.join("doc");
+    if let Some(triple) = &cfg.triple {
+        doc = target_dir.join(triple).join("doc");
+    }
+    let json = std::fs::read_to_string(doc.join(target.name.replace('-', "_")).with_extension("json"))?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Parses the JSON `rustdoc --output-format json` generates into a `Library`.
+///
+/// Unlike the `syn` parser, rustdoc has resolved the crate: paths are absolute, re-exports of
+/// items that aren't public anywhere else are inlined where they're exported from, and
+/// macro-generated items are there. Re-exports of the others are public `Import`s.
+/// Types from the standard prelude, like `Vec` and `Option`, keep their prelude names, so they
+/// look like the ones the `syn` parser finds.
+///
+/// The traits a type implements, including blanket and auto trait implementations, are its
+/// `interfaces`. Its inherent `impl` blocks, and the trait implementations with items it has
+/// itself, are `Interface`s named after it, with the trait in their `interfaces`. Generic
+/// parameters and their bounds are recorded in a `#[generics(T = "Clone + Send")]` attribute.
+///
+/// Only the public items are in rustdoc's output, and `RustLibraryParser` uses this parser when
+/// `ligen::rust::frontend` is `"rustdoc"`.
+#[derive(Default)]
+pub struct RustdocParser {
+    literal_parser: RustLiteralParser,
+}
+
+impl RustdocParser {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Parser<Library> for RustdocParser {
+    fn parse(&self, input: impl AsRef<str>, config: &Config) -> Result<Library> {
+        let json = serde_json::from_str::<Value>(input.as_ref())?;
+        self.transform(&json, config)
+    }
+}
+
+impl Transformer<&std::path::Path, Library> for RustdocParser {
+    fn transform(&self, input: &std::path::Path, config: &Config) -> Result<Library> {
+        let json = std::fs::read_to_string(input)?;
+        self.parse(json, config)
+    }
+}
+
+impl Transformer<&Value, Library> for RustdocParser {
+    fn transform(&self, input: &Value, config: &Config) -> Result<Library> {
+        let index = input["index"].as_object().ok_or("Rustdoc JSON has no index.")?;
+        let paths = input["paths"].as_object().ok_or("Rustdoc JSON has no paths.")?;
+        let mut crate_ = Crate { parser: self, index, paths, config, defined: HashSet::new() };
+        let root = crate_.item(&input["root"]).ok_or("Rustdoc JSON has no root module.")?;
+        let mut defined = HashSet::new();
+        crate_.definitions(root, &mut defined);
+        crate_.defined = defined;
+        let identifier = Identifier::from(root["name"].as_str().unwrap_or_default());
+        let root_module = crate_.module(root, &mut HashSet::new())?;
+        let mut library = Library { identifier, root_module, ..Default::default() };
+        if let Some(Ok(version)) = input["crate_version"].as_str().map(Version::try_from) {
+            library.metadata.version = version;
+        }
+        Ok(library)
+    }
+}
+
+/// The index of a documented crate.
+struct Crate<'a> {
+    parser: &'a RustdocParser,
+    index: &'a serde_json::Map<String, Value>,
+    paths: &'a serde_json::Map<String, Value>,
+    config: &'a Config,
+    /// The ids of the public items defined in the public modules.
+    defined: HashSet<String>,
+}
+
+/// The item kind and its fields, as `inner` has them.
+fn contents(item: &Value) -> Option<(&str, &Value)> {
+    item["inner"].as_object()?.iter().next().map(|(kind, inner)| (kind.as_str(), inner))
+}
+
+/// Ids are numbers in recent formats and strings in older ones.
+fn key(id: &Value) -> Option<String> {
+    match id {
+        Value::Number(id) => Some(id.to_string()),
+        Value::String(id) => Some(id.clone()),
+        _ => None,
+    }
+}
+
+fn array(value: &Value) -> &[Value] {
+    value.as_array().map(Vec::as_slice).unwrap_or_default()
+}
+
+/// Takes the first of `keys` that `value` has, as fields were renamed across format versions.
+fn field<'a>(value: &'a Value, keys: &[&str]) -> &'a Value {
+    static NULL: Value = Value::Null;
+    keys.iter().map(|key| &value[*key]).find(|value| !value.is_null()).unwrap_or(&NULL)
+}
+
+impl<'a> Crate<'a> {
+    fn item(&self, id: &Value) -> Option<&'a Value> {
+        self.index.get(&key(id)?)
+    }
+
+    /// Collects the ids of the public items defined in `module` and in its public submodules.
+    fn definitions(&self, module: &Value, defined: &mut HashSet<String>) {
+        let Some((_, inner)) = contents(module) else {
+            return;
+        };
+        for item in array(&inner["items"]).iter().filter_map(|id| self.item(id)) {
+            let Some((kind, _)) = contents(item) else {
+                continue;
+            };
+            if matches!(kind, "use" | "import") || Self::visibility(item) != Visibility::Public {
+                continue;
+            }
+            if let Some(id) = key(&item["id"]) {
+                if defined.insert(id) && kind == "module" {
+                    self.definitions(item, defined);
+                }
+            }
+        }
+    }
+
+    /// The absolute path of the local item `id`.
+    fn path_of(&self, id: &Value) -> Option<Path> {
+        let segments = array(&self.paths.get(&key(id)?)?["path"]).iter().filter_map(Value::as_str).collect::<Vec<_>>();
+        (!segments.is_empty()).then(|| Path::from(segments.join("::")))
+    }
+
+    fn visibility(item: &Value) -> Visibility {
+        match item["visibility"].as_str() {
+            Some("public") => Visibility::Public,
+            _ => Visibility::Private,
+        }
+    }
+
+    fn attributes(item: &Value) -> Attributes {
+        let documentation = item["docs"].as_str().unwrap_or_default();
+        documentation
+            .lines()
+            .map(|line| Attribute::Named(Named::new("doc", line)))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    fn identifier(item: &Value) -> Identifier {
+        Identifier::from(item["name"].as_str().unwrap_or_default())
+    }
+
+    /// Parses a module, with the items it re-exports. `visited` breaks glob re-export cycles.
+    fn module(&self, item: &Value, visited: &mut HashSet<String>) -> Result<Module> {
+        let mut module = Module {
+            attributes: Self::attributes(item),
+            visibility: Self::visibility(item),
+            identifier: Self::identifier(item),
+            ..Default::default()
+        };
+        if let Some(id) = key(&item["id"]) {
+            visited.insert(id);
+        }
+        let (_, inner) = contents(item).ok_or("Module item has no inner fields.")?;
+        for id in array(&inner["items"]) {
+            if let Some(item) = self.item(id) {
+                self.module_item(&mut module, item, None, visited)?;
+            }
+        }
+        Ok(module)
+    }
+
+    /// Adds `item` to `module`, named `name` if it's re-exported under another name.
+    fn module_item(&self, module: &mut Module, item: &Value, name: Option<&str>, visited: &mut HashSet<String>) -> Result<()> {
+        let Some((kind, inner)) = contents(item) else {
+            return Ok(());
+        };
+        let rename = |identifier: &mut Identifier| {
+            if let Some(name) = name {
+                *identifier = Identifier::from(name);
+            }
+        };
+        match kind {
+            "module" => {
+                let mut child = self.module(item, visited)?;
+                rename(&mut child.identifier);
+                module.modules.push(child);
+            },
+            "struct" | "enum" | "union" | "type_alias" | "typedef" => {
+                let (mut definition, mut interfaces) = self.type_definition(item, kind, inner)?;
+                rename(&mut definition.identifier);
+                interfaces.iter_mut().for_each(|interface| rename(&mut interface.identifier));
+                module.types.push(definition);
+                module.interfaces.extend(interfaces);
+            },
+            "function" => {
+                let mut function = self.function(item, inner)?;
+                rename(&mut function.identifier);
+                module.functions.push(function);
+            },
+            "constant" | "static" => {
+                let mut object = self.object(item, inner)?;
+                rename(&mut object.identifier);
+                module.objects.push(object);
+            },
+            "trait" => {
+                let mut interface = self.trait_(item, inner)?;
+                rename(&mut interface.identifier);
+                module.interfaces.push(interface);
+            },
+            "use" | "import" => {
+                let name = inner["name"].as_str().unwrap_or_default();
+                let is_glob = inner["is_glob"].as_bool().or(inner["glob"].as_bool()).unwrap_or(false);
+                let target = self.item(&inner["id"]);
+                let defined = key(&inner["id"]).is_some_and(|id| self.defined.contains(&id));
+                let local = if defined { self.path_of(&inner["id"]) } else { None };
+                match (target, local) {
+                    // Re-exports of local items that are public elsewhere are imports of them.
+                    (Some(_), Some(path)) => {
+                        let renaming = (!is_glob && path.last().identifier != Identifier::from(name)).then(|| Identifier::from(name));
+                        let path = if is_glob { path.join("*") } else { path };
+                        let visibility = Self::visibility(item);
+                        module.imports.push(Import { path, renaming, visibility, ..Default::default() });
+                    },
+                    // Re-exports of the other local items are inlined.
+                    (Some(target), None) if is_glob => {
+                        let already_visited = key(&target["id"]).is_some_and(|id| visited.contains(&id));
+                        if let (false, Some((_, target))) = (already_visited, contents(target)) {
+                            for id in array(&target["items"]) {
+                                if let Some(item) = self.item(id) {
+                                    self.module_item(module, item, None, visited)?;
+                                }
+                            }
+                        }
+                    },
+                    (Some(target), None) => self.module_item(module, target, Some(name), visited)?,
+                    // Re-exports of external items are imports.
+                    (None, _) => {
+                        let source = inner["source"].as_str().unwrap_or_default();
+                        let path = if is_glob { format!("{source}::*") } else { source.to_string() };
+                        let renaming = (!is_glob && !source.ends_with(name)).then(|| Identifier::from(name));
+                        let visibility = Self::visibility(item);
+                        module.imports.push(Import { path: Path::from(path), renaming, visibility, ..Default::default() });
+                    },
+                }
+            },
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// Parses a type definition, and its inherent `impl` blocks.
+    fn type_definition(&self, item: &Value, kind: &str, inner: &Value) -> Result<(TypeDefinition, Vec<Interface>)> {
+        let identifier = Self::identifier(item);
+        let generics = &inner["generics"];
+        let definition = match kind {
+            "struct" => {
+                let fields = match &inner["kind"] {
+                    Value::Object(kind) if kind.contains_key("plain") => array(&kind["plain"]["fields"]).iter().filter_map(|id| self.item(id)).map(|field| self.field(field, true)).collect::<Result<_>>()?,
+                    Value::Object(kind) if kind.contains_key("tuple") => array(&kind["tuple"]).iter().filter_map(|id| self.item(id)).map(|field| self.field(field, false)).collect::<Result<_>>()?,
+                    _ => Vec::new(),
+                };
+                KindDefinition::Structure(Structure { fields })
+            },
+            "enum" => {
+                let variants = array(&inner["variants"])
+                    .iter()
+                    .filter_map(|id| self.item(id))
+                    .map(|variant| Variant { attributes: Self::attributes(variant), identifier: Self::identifier(variant) })
+                    .collect();
+                KindDefinition::Enumeration(Enumeration { variants })
+            },
+            "union" => {
+                let fields = array(&inner["fields"]).iter().filter_map(|id| self.item(id)).map(|field| self.field(field, true)).collect::<Result<_>>()?;
+                KindDefinition::Union(Union { fields })
+            },
+            _ => KindDefinition::TypeAlias(TypeAlias { type_: self.type_(field(inner, &["type", "type_"]))? }),
+        };
+        let mut attributes = Self::attributes(item);
+        attributes.attributes.extend(self.generics_attribute(generics)?);
+        let parameters = array(&generics["params"])
+            .iter()
+            .filter(|parameter| parameter["kind"].get("type").is_some())
+            .map(|parameter| Type::from(parameter["name"].as_str().unwrap_or_default()))
+            .collect::<Vec<_>>();
+        let mut definition = TypeDefinition {
+            attributes,
+            visibility: Self::visibility(item),
+            identifier: identifier.clone(),
+            generics: Generics::from(parameters),
+            definition,
+            ..Default::default()
+        };
+        let mut interfaces = Vec::new();
+        for implementation in array(&inner["impls"]).iter().filter_map(|id| self.item(id)) {
+            let Some((_, implementation)) = contents(implementation) else {
+                continue;
+            };
+            if implementation["is_negative"].as_bool().unwrap_or(false) {
+                continue;
+            }
+            match implementation.get("trait").filter(|trait_| !trait_.is_null()) {
+                Some(trait_) => {
+                    let path = self.path(trait_)?;
+                    // The items of the crate's own implementations, like associated types, are kept as
+                    // the `syn` parser keeps them.
+                    let is_blanket = implementation["blanket_impl"].is_object() || implementation["is_synthetic"].as_bool().unwrap_or(false);
+                    if !is_blanket && !array(&implementation["items"]).is_empty() {
+                        let mut interface = self.implementation(&identifier, implementation)?;
+                        interface.interfaces.push(path.clone());
+                        interfaces.push(interface);
+                    }
+                    definition.interfaces.push(path);
+                },
+                None => interfaces.push(self.implementation(&identifier, implementation)?),
+            }
+        }
+        Ok((definition, interfaces))
+    }
+
+    fn field(&self, item: &Value, named: bool) -> Result<Field> {
+        let (_, inner) = contents(item).ok_or("Field item has no inner fields.")?;
+        Ok(Field {
+            attributes: Self::attributes(item),
+            visibility: Self::visibility(item),
+            identifier: named.then(|| Self::identifier(item)),
+            type_: self.type_(inner)?,
+        })
+    }
+
+    /// Parses an `impl` block into an interface named after the type.
+    fn implementation(&self, identifier: &Identifier, implementation: &Value) -> Result<Interface> {
+        let mut interface = Interface { identifier: identifier.clone(), ..Default::default() };
+        for item in array(&implementation["items"]).iter().filter_map(|id| self.item(id)) {
+            match contents(item) {
+                Some(("function", inner)) => match self.method(item, inner)? {
+                    Some(method) => interface.methods.push(method),
+                    None => interface.functions.push(self.function(item, inner)?),
+                },
+                Some(("assoc_const" | "constant", inner)) => interface.objects.push(self.object(item, inner)?),
+                Some(("assoc_type", inner)) => interface.types.push(self.associated_type(item, inner)?),
+                _ => (),
+            }
+        }
+        crate::interface::resolve_self(&mut interface, &self.type_(&implementation["for"])?);
+        Ok(interface)
+    }
+
+    fn trait_(&self, item: &Value, inner: &Value) -> Result<Interface> {
+        let mut attributes = Self::attributes(item);
+        attributes.attributes.extend(self.generics_attribute(&inner["generics"])?);
+        let interfaces = array(&inner["bounds"])
+            .iter()
+            .filter_map(|bound| bound.get("trait_bound"))
+            .map(|bound| self.path(&bound["trait"]))
+            .collect::<Result<_>>()?;
+        let mut interface = Interface {
+            attributes,
+            visibility: Self::visibility(item),
+            identifier: Self::identifier(item),
+            interfaces,
+            ..Default::default()
+        };
+        for item in array(&inner["items"]).iter().filter_map(|id| self.item(id)) {
+            match contents(item) {
+                Some(("function", inner)) => match self.method(item, inner)? {
+                    Some(method) => interface.methods.push(method),
+                    None => interface.functions.push(self.function(item, inner)?),
+                },
+                Some(("assoc_const", inner)) => interface.objects.push(self.object(item, inner)?),
+                Some(("assoc_type", inner)) => interface.types.push(self.associated_type(item, inner)?),
+                _ => (),
+            }
+        }
+        Ok(interface)
+    }
+
+    /// An associated type, as an alias of its type or its default. Declarations without one
+    /// alias an opaque type, and their bounds are the interfaces it implements.
+    fn associated_type(&self, item: &Value, inner: &Value) -> Result<TypeDefinition> {
+        let type_ = match field(inner, &["type", "default"]) {
+            Value::Null => Type::opaque(),
+            type_ => self.type_(type_)?,
+        };
+        let interfaces = array(&inner["bounds"])
+            .iter()
+            .filter_map(|bound| bound.get("trait_bound"))
+            .map(|bound| self.path(&bound["trait"]))
+            .collect::<Result<_>>()?;
+        let mut attributes = Self::attributes(item);
+        attributes.attributes.extend(self.generics_attribute(&inner["generics"])?);
+        Ok(TypeDefinition {
+            attributes,
+            visibility: Self::visibility(item),
+            identifier: Self::identifier(item),
+            interfaces,
+            definition: KindDefinition::TypeAlias(TypeAlias { type_ }),
+            ..Default::default()
+        })
+    }
+
+    fn signature(&self, inner: &Value) -> Result<(Vec<Parameter>, Option<Type>, Synchrony)> {
+        let signature = field(inner, &["sig", "decl"]);
+        let inputs = array(&signature["inputs"])
+            .iter()
+            .map(|input| {
+                let identifier = input[0].as_str().unwrap_or_default();
+                Ok(Parameter::new(identifier, self.type_(&input[1])?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let output = match &signature["output"] {
+            Value::Null => None,
+            output => Some(self.type_(output)?),
+        };
+        let header = &inner["header"];
+        let synchrony = if field(header, &["is_async", "async_", "async"]).as_bool().unwrap_or(false) {
+            Synchrony::Asynchronous
+        } else {
+            Synchrony::Synchronous
+        };
+        Ok((inputs, output, synchrony))
+    }
+
+    fn function(&self, item: &Value, inner: &Value) -> Result<Function> {
+        let (inputs, output, synchrony) = self.signature(inner)?;
+        let mut attributes = Self::attributes(item);
+        attributes.attributes.extend(self.generics_attribute(&inner["generics"])?);
+        let visibility = Self::visibility(item);
+        let identifier = Self::identifier(item);
+        Ok(Function { attributes, visibility, synchrony, identifier, inputs, output, body: () })
+    }
+
+    /// Parses a function taking `self` as a method, or returns `None`.
+    fn method(&self, item: &Value, inner: &Value) -> Result<Option<Method>> {
+        let (mut inputs, output, synchrony) = self.signature(inner)?;
+        if inputs.first().is_none_or(|input| input.identifier.name != "self") {
+            return Ok(None);
+        }
+        let receiver = RustMethodParser::receiver(inputs.remove(0).type_);
+        let mutability = if receiver == Receiver::Mutable { Mutability::Mutable } else { Mutability::Constant };
+        let mut attributes = Self::attributes(item);
+        attributes.attributes.extend(self.generics_attribute(&inner["generics"])?);
+        let visibility = Self::visibility(item);
+        let identifier = Self::identifier(item);
+        Ok(Some(Method { attributes, mutability, receiver, visibility, synchrony, identifier, inputs, output, body: () }))
+    }
+
+    fn object(&self, item: &Value, inner: &Value) -> Result<Object> {
+        let type_ = self.type_(field(inner, &["type", "type_"]))?;
+        let constant = field(inner, &["const", "constant"]);
+        let expression = field(constant, &["value", "expr"]).as_str().or(field(inner, &["value", "expr", "default"]).as_str()).unwrap_or_default();
+        let literal = syn::parse_str::<syn::Expr>(expression)
+            .ok()
+            .and_then(|expression| self.parser.literal_parser.transform(expression, self.config).ok())
+            .unwrap_or_else(|| Literal::Unknown(expression.to_string()));
+        let mutability = if field(inner, &["is_mutable", "mutable"]).as_bool().unwrap_or(false) {
+            Mutability::Mutable
+        } else {
+            Mutability::Constant
+        };
+        let attributes = Self::attributes(item);
+        let visibility = Self::visibility(item);
+        let identifier = Self::identifier(item);
+        Ok(Object { attributes, visibility, mutability, identifier, type_, literal })
+    }
+
+    /// The generic parameters and their bounds, from the parameters and the `where` clause.
+    fn generics_attribute(&self, generics: &Value) -> Result<Option<Attribute>> {
+        let mut parameters: Vec<(String, Vec<String>)> = Vec::new();
+        for parameter in array(&generics["params"]) {
+            let name = parameter["name"].as_str().unwrap_or_default().to_string();
+            let bounds = match parameter["kind"].get("type") {
+                Some(type_) if type_["is_synthetic"].as_bool().unwrap_or(false) => continue,
+                Some(type_) => self.bounds(&type_["bounds"])?,
+                None if parameter["kind"].get("const").is_some() => vec![format!("const {}", self.type_(&parameter["kind"]["const"]["type"])?)],
+                None => continue,
+            };
+            parameters.push((name, bounds));
+        }
+        for predicate in array(&generics["where_predicates"]) {
+            let Some(predicate) = predicate.get("bound_predicate") else {
+                continue;
+            };
+            let name = self.type_(&predicate["type"])?.to_string();
+            let bounds = self.bounds(&predicate["bounds"])?;
+            match parameters.iter_mut().find(|(parameter, _)| *parameter == name) {
+                Some((_, existing)) => existing.extend(bounds),
+                None => parameters.push((name, bounds)),
+            }
+        }
+        if parameters.is_empty() {
+            return Ok(None);
+        }
+        let attributes = parameters
+            .into_iter()
+            .map(|(name, bounds)| Named::new(name.as_str(), bounds.join(" + ")))
+            .collect::<Vec<_>>();
+        Ok(Some(Attribute::Group(Group::new("generics", attributes))))
+    }
+
+    fn bounds(&self, bounds: &Value) -> Result<Vec<String>> {
+        let mut strings = Vec::new();
+        for bound in array(bounds) {
+            if let Some(bound) = bound.get("trait_bound") {
+                let maybe = matches!(bound["modifier"].as_str(), Some("maybe"));
+                let path = self.path(&bound["trait"])?;
+                strings.push(if maybe { format!("?{path}") } else { path.to_string() });
+            } else if let Some(lifetime) = bound.get("outlives").and_then(Value::as_str) {
+                strings.push(lifetime.to_string());
+            }
+        }
+        Ok(strings)
+    }
+
+    /// The absolute path of a resolved path, with its generic arguments.
+    fn path(&self, path: &Value) -> Result<Path> {
+        let absolute = key(&path["id"])
+            .and_then(|id| self.paths.get(&id))
+            .map(|summary| array(&summary["path"]).iter().filter_map(Value::as_str).collect::<Vec<_>>().join("::"));
+        let name = field(path, &["path", "name"]).as_str().unwrap_or_default();
+        let mut path_ = Path::from(prelude_name(absolute.as_deref().unwrap_or(name)));
+        let generics = array(&path["args"]["angle_bracketed"]["args"])
+            .iter()
+            .filter_map(|argument| argument.get("type"))
+            .map(|type_| self.type_(type_))
+            .collect::<Result<Vec<_>>>()?;
+        if !path_.is_empty() {
+            path_.last_mut().generics = Generics::from(generics);
+        }
+        Ok(path_)
+    }
+
+    fn type_(&self, type_: &Value) -> Result<Type> {
+        if type_.as_str() == Some("infer") {
+            return Ok(Type::infer());
+        }
+        let Some((kind, inner)) = type_.as_object().and_then(|type_| type_.iter().next()) else {
+            return Err(Error::Message(format!("Unsupported type: {type_}")));
+        };
+        match kind.as_str() {
+            "resolved_path" => Ok(self.path(inner)?.into()),
+            "generic" => Ok(Type::from(inner.as_str().unwrap_or_default())),
+            "primitive" => Ok(match inner.as_str().unwrap_or_default() {
+                "bool" => Type::boolean(),
+                "char" => Type::character(),
+                "never" | "!" => Type::void(),
+                primitive => Type::from(primitive),
+            }),
+            "borrowed_ref" | "raw_pointer" => {
+                let mutability = if field(inner, &["is_mutable", "mutable"]).as_bool().unwrap_or(false) {
+                    Mutability::Mutable
+                } else {
+                    Mutability::Constant
+                };
+                Ok(Type::reference(mutability, self.type_(&inner["type"])?))
+            },
+            "slice" => Ok(Type::slice(self.type_(inner)?)),
+            "array" => {
+                let length = inner["len"].as_str().and_then(|length| length.parse().ok()).unwrap_or_default();
+                Ok(Type::array(self.type_(&inner["type"])?, length))
+            },
+            "tuple" => Ok(Type::tuple(array(inner).iter().map(|type_| self.type_(type_)).collect::<Result<_>>()?)),
+            "dyn_trait" => {
+                let trait_ = array(&inner["traits"]).first().ok_or("Failed to find trait bound.")?;
+                Ok(self.path(&trait_["trait"])?.into())
+            },
+            "impl_trait" => {
+                let bound = array(inner).iter().find_map(|bound| bound.get("trait_bound")).ok_or("Failed to find trait bound.")?;
+                Ok(self.path(&bound["trait"])?.into())
+            },
+            "function_pointer" => {
+                let signature = field(inner, &["sig", "decl"]);
+                let inputs = array(&signature["inputs"]).iter().map(|input| self.type_(&input[1])).collect::<Result<_>>()?;
+                let output = match &signature["output"] {
+                    Value::Null => Type::void(),
+                    output => self.type_(output)?,
+                };
+                Ok(Type::function(inputs, output))
+            },
+            "qualified_path" => {
+                let name = inner["name"].as_str().unwrap_or_default();
+                let self_type = self.type_(&inner["self_type"])?;
+                Ok(Type::from(Path::from(self_type.path).join(PathSegment::from(name))))
+            },
+            _ => Err(Error::Message(format!("Unsupported type: {type_}"))),
+        }
+    }
+}
+
+/// The prelude name of the standard library types the prelude has.
+fn prelude_name(path: &str) -> &str {
+    match path {
+        "alloc::vec::Vec" | "std::vec::Vec" => "Vec",
+        "core::option::Option" | "std::option::Option" => "Option",
+        "core::result::Result" | "std::result::Result" => "Result",
+        "alloc::string::String" | "std::string::String" => "String",
+        "alloc::boxed::Box" | "std::boxed::Box" => "Box",
+        path => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rustdoc_json() -> Result<()> {
+        let json = serde_json::json!({
+            "root": 0,
+            "crate_version": "0.1.0",
+            "index": {
+                "0": { "id": 0, "name": "shapes", "visibility": "public", "docs": null, "inner": { "module": { "is_crate": true, "items": [1, 4, 6] } } },
+                "1": { "id": 1, "name": "Circle", "visibility": "public", "docs": "A circle.", "inner": { "struct": {
+                    "kind": { "plain": { "fields": [2], "has_stripped_fields": false } },
+                    "generics": { "params": [{ "name": "T", "kind": { "type": { "bounds": [{ "trait_bound": { "trait": { "path": "Copy", "id": 20, "args": null }, "generic_params": [], "modifier": "none" } }], "default": null, "is_synthetic": false } } }], "where_predicates": [] },
+                    "impls": [3, 7, 8]
+                } } },
+                "2": { "id": 2, "name": "radius", "visibility": "public", "docs": null, "inner": { "struct_field": { "generic": "T" } } },
+                "3": { "id": 3, "name": null, "visibility": "default", "docs": null, "inner": { "impl": { "trait": null, "for": { "resolved_path": { "path": "Circle", "id": 1, "args": null } }, "items": [5], "is_negative": false, "is_synthetic": false, "blanket_impl": null } } },
+                "4": { "id": 4, "name": "unit", "visibility": "public", "docs": null, "inner": { "function": {
+                    "sig": { "inputs": [], "output": { "resolved_path": { "path": "Circle", "id": 1, "args": { "angle_bracketed": { "args": [{ "type": { "primitive": "f32" } }], "constraints": [] } } } }, "is_c_variadic": false },
+                    "generics": { "params": [], "where_predicates": [] },
+                    "header": { "is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust" },
+                    "has_body": true
+                } } },
+                "5": { "id": 5, "name": "area", "visibility": "public", "docs": null, "inner": { "function": {
+                    "sig": { "inputs": [["self", { "borrowed_ref": { "lifetime": null, "is_mutable": false, "type": { "generic": "Self" } } }]], "output": { "resolved_path": { "path": "Vec", "id": 21, "args": { "angle_bracketed": { "args": [{ "type": { "primitive": "f32" } }], "constraints": [] } } } }, "is_c_variadic": false },
+                    "generics": { "params": [], "where_predicates": [] },
+                    "header": { "is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust" },
+                    "has_body": true
+                } } },
+                "6": { "id": 6, "name": "Shape", "visibility": "public", "docs": null, "inner": { "use": { "source": "self::Circle", "name": "Shape", "id": 1, "is_glob": false } } },
+                "7": { "id": 7, "name": null, "visibility": "default", "docs": null, "inner": { "impl": { "trait": { "path": "Send", "id": 22, "args": null }, "for": { "resolved_path": { "path": "Circle", "id": 1, "args": null } }, "items": [], "is_negative": false, "is_synthetic": true, "blanket_impl": null } } },
+                "8": { "id": 8, "name": null, "visibility": "default", "docs": null, "inner": { "impl": { "trait": { "path": "Iterator", "id": 23, "args": null }, "for": { "resolved_path": { "path": "Circle", "id": 1, "args": null } }, "items": [9], "is_negative": false, "is_synthetic": false, "blanket_impl": null } } },
+                "9": { "id": 9, "name": "Item", "visibility": "default", "docs": null, "inner": { "assoc_type": { "generics": { "params": [], "where_predicates": [] }, "bounds": [], "type": { "primitive": "u32" } } } }
+            },
+            "paths": {
+                "1": { "crate_id": 0, "path": ["shapes", "Circle"], "kind": "struct" },
+                "20": { "crate_id": 1, "path": ["core", "marker", "Copy"], "kind": "trait" },
+                "21": { "crate_id": 2, "path": ["alloc", "vec", "Vec"], "kind": "struct" },
+                "22": { "crate_id": 1, "path": ["core", "marker", "Send"], "kind": "trait" },
+                "23": { "crate_id": 1, "path": ["core", "iter", "traits", "iterator", "Iterator"], "kind": "trait" }
+            }
+        });
+        let library = RustdocParser::new().transform(&json, &Config::default())?;
+        assert_eq!(library.identifier, Identifier::from("shapes"));
+        let module = &library.root_module;
+        let circle = &module.types[0];
+        assert_eq!(circle.generics, Generics::from(vec![Type::from("T")]));
+        assert_eq!(circle.interfaces, vec![Path::from("core::marker::Send"), Path::from("core::iter::traits::iterator::Iterator")]);
+        assert_eq!(circle.attributes.get_group("generics").and_then(|generics| generics.get_named("T")), Some(&Literal::from("core::marker::Copy")));
+        // Circle is public where it's defined, so its re-export is an import instead of a copy.
+        assert_eq!(module.types.len(), 1);
+        let shape = Import { path: Path::from("shapes::Circle"), renaming: Some(Identifier::from("Shape")), visibility: Visibility::Public, ..Default::default() };
+        assert_eq!(module.imports, vec![shape]);
+        let mut circle = Path::from("shapes::Circle");
+        circle.last_mut().generics = Generics::from(vec![Type::f32()]);
+        assert_eq!(module.functions[0].output, Some(Type::from(circle)));
+        let area = &module.interfaces[0].methods[0];
+        assert_eq!(area.mutability, Mutability::Constant);
+        assert_eq!(area.receiver, Receiver::Shared);
+        assert_eq!(area.output, Some(Type::from(Path::from(PathSegment::new("Vec", vec![Type::f32()])))));
+        // The auto trait implementation has no interface, the `Iterator` one has its associated type.
+        let iterator = &module.interfaces[1];
+        assert_eq!(module.interfaces.len(), 2);
+        assert_eq!(iterator.interfaces, vec![Path::from("core::iter::traits::iterator::Iterator")]);
+        assert_eq!(iterator.types[0].identifier, Identifier::from("Item"));
+        Ok(())
+    }
+}
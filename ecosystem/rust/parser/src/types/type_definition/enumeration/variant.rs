@@ -2,7 +2,7 @@
 
 use crate::prelude::*;
 use ligen::idl::Variant;
-use crate::{RustIdentifierParser, RustAttributesParser};
+use crate::{CfgTarget, RustIdentifierParser, RustAttributesParser};
 
 #[derive(Default)]
 pub struct RustVariantParser {
@@ -20,8 +20,9 @@ impl Transformer<syn::Variant, Variant> for RustVariantParser {
 
 impl Transformer<syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>, Vec<Variant>> for RustVariantParser {
     fn transform(&self, input: syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>, config: &Config) -> Result<Vec<Variant>> {
+        let target = CfgTarget::from_config(config);
         let mut variants = Vec::new();
-        for variant in input {
+        for variant in input.into_iter().filter(|variant| target.is_enabled(&variant.attrs)) {
             variants.push(self.transform(variant, config)?);
         }
         Ok(variants)
@@ -3,7 +3,9 @@
 mod structure;
 mod enumeration;
 mod type_alias;
+mod union;
 
 pub use structure::*;
 pub use enumeration::*;
 pub use type_alias::*;
+pub use union::*;
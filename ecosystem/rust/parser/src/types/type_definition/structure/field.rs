@@ -2,7 +2,7 @@
 
 use crate::prelude::*;
 use ligen::idl::Field;
-use crate::{RustIdentifierParser, RustAttributesParser, RustTypeParser, RustVisibilityParser};
+use crate::{CfgTarget, RustIdentifierParser, RustAttributesParser, RustTypeParser, RustVisibilityParser};
 
 #[derive(Default)]
 pub struct RustFieldParser {
@@ -24,8 +24,9 @@ impl Transformer<syn::Field, Field> for RustFieldParser {
 
 impl Transformer<syn::Fields, Vec<Field>> for RustFieldParser {
     fn transform(&self, input: syn::Fields, config: &Config) -> Result<Vec<Field>> {
+        let target = CfgTarget::from_config(config);
         let mut fields = Vec::new();
-        for field in input {
+        for field in input.into_iter().filter(|field| target.is_enabled(&field.attrs)) {
             fields.push(self.transform(field, config)?);
         }
         Ok(fields)
@@ -46,6 +46,20 @@ impl Transformer<syn::ItemType, TypeDefinition> for RustTypeAliasParser {
     }
 }
 
+impl Transformer<syn::ImplItemType, TypeDefinition> for RustTypeAliasParser {
+    /// An associated type, like `type Item = u32;`, as an alias of the type it's set to.
+    fn transform(&self, associated: syn::ImplItemType, config: &Config) -> Result<TypeDefinition> {
+        let attributes = self.attributes_parser.transform(associated.attrs, config)?;
+        let identifier = self.identifier_parser.transform(associated.ident, config)?;
+        let visibility = self.visibility_parser.transform(associated.vis, config)?;
+        let interfaces = Default::default();
+        let type_ = self.type_parser.transform(associated.ty, config)?;
+        let definition = TypeAlias { type_ }.into();
+        let generics = self.generics_parser.transform(associated.generics, config)?;
+        Ok(TypeDefinition { attributes, visibility, identifier, generics, interfaces, definition })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -0,0 +1,66 @@
+//! Union representation.
+
+use crate::prelude::*;
+use crate::{RustIdentifierParser, RustAttributesParser, RustVisibilityParser, RustGenericsParser, RustFieldParser};
+use ligen::idl::{Union, TypeDefinition};
+
+#[derive(Default)]
+pub struct RustUnionParser {
+    field_parser: RustFieldParser,
+    identifier_parser: RustIdentifierParser,
+    visibility_parser: RustVisibilityParser,
+    attributes_parser: RustAttributesParser,
+    generics_parser: RustGenericsParser,
+}
+
+impl RustUnionParser {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Transformer<proc_macro::TokenStream, TypeDefinition> for RustUnionParser {
+    fn transform(&self, token_stream: proc_macro::TokenStream, config: &Config) -> Result<TypeDefinition> {
+        self.transform(proc_macro2::TokenStream::from(token_stream), config)
+    }
+}
+
+impl Transformer<proc_macro2::TokenStream, TypeDefinition> for RustUnionParser {
+    fn transform(&self, tokenstream: proc_macro2::TokenStream, config: &Config) -> Result<TypeDefinition> {
+        syn::parse2::<syn::ItemUnion>(tokenstream)
+            .map_err(|e| Error::Message(format!("Failed to parse to union: {e:?}")))
+            .and_then(|union| self.transform(union, config))
+    }
+}
+
+impl Transformer<syn::ItemUnion, TypeDefinition> for RustUnionParser {
+    fn transform(&self, union: syn::ItemUnion, config: &Config) -> Result<TypeDefinition> {
+        let attributes = self.attributes_parser.transform(union.attrs, config)?;
+        let identifier = self.identifier_parser.transform(union.ident, config)?;
+        let visibility = self.visibility_parser.transform(union.vis, config)?;
+        let interfaces = Default::default();
+        let fields = self.field_parser.transform(syn::Fields::Named(union.fields), config)?;
+        let definition = Union { fields }.into();
+        let generics = self.generics_parser.transform(union.generics, config)?;
+        Ok(TypeDefinition { attributes, visibility, identifier, generics, interfaces, definition })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::RustUnionParser;
+
+    use ligen::transformer::assert::*;
+    use ligen::idl::union::mock;
+
+    #[test]
+    fn union() -> Result<()> {
+        assert_eq(RustUnionParser::default(), mock::union(), quote! {
+            pub union Union {
+                pub integer: u32,
+                pub float: f32
+            }
+        })
+    }
+}
@@ -1,9 +1,11 @@
 pub mod child;
+#[path = "paths/renamed.rs"]
+pub mod renamed;
 
 #[test]
 pub fn module_file() {
     use ligen::idl::*;
-    use ligen_idl::macro_attributes::Group;
+    use ligen_idl::macro_attributes::{Group, Named};
     use ligen::transformer::assert::assert_eq;
     use ligen_rust_parser::RustModuleParser;
 
@@ -26,6 +28,17 @@ pub fn module_file() {
                     }
                 ],
                 .. Default::default()
+            },
+            Module {
+                attributes: Named::new("path", "paths/renamed.rs").into(),
+                identifier: "renamed".into(),
+                functions: vec![
+                    Function {
+                        identifier: "renamed_function".into(),
+                        ..Default::default()
+                    }
+                ],
+                .. Default::default()
             }
         ],
         .. Default::default()
@@ -0,0 +1 @@
+pub fn renamed_function() {}
@@ -51,7 +51,7 @@ impl FileGenerator<&Library> for LibraryGenerator {
         PathBuf::from("rust".to_string())
     }
 
-    fn generate_files(&self, library: &Library, file_set: &mut FileSet) -> Result<()> {
+    fn generate_files(&self, library: &Library, file_set: &mut FileSet, _config: &Config) -> Result<()> {
         self.generate_project_file(library, file_set)?;
         self.generate_lib_file(library, file_set)?;
         let visitor = TreeIterator::<Visitors>::new(library);
@@ -9,12 +9,6 @@ pub struct WgslBlockGenerator {
 
 impl Generator<&Block, String> for WgslBlockGenerator {
     fn generate(&self, block: &Block, config: &Config) -> Result<String> {
-        let mut result = String::new();
-        result.push('{');
-        for statement in &block.statements {
-            result.push_str(&self.statement_generator.generate(statement, config)?);
-        }
-        result.push('}');
-        Ok(result)
+        self.statement_generator.block(block, config)
     }
-}
\ No newline at end of file
+}
@@ -3,8 +3,68 @@ use std::rc::Rc;
 use crate::{WgslIdentifierGenerator, WgslPathGenerator};
 
 use ligen_transformer::prelude::*;
+use ligen_idl::{Literal, Structure};
 use ligen_ir::Expression;
 
+const VARIABLE: &str = "ligen::wgsl::variable";
+const STRUCTURE: &str = "ligen::wgsl::structure";
+
+/// Records that the variable `name` has the WGSL type `type_`, for the expressions after it.
+pub fn declare_variable(config: &mut Config, name: &str, type_: &str) {
+    config.set(format!("{VARIABLE}::{name}"), type_);
+}
+
+/// Records the fields of the structure `name`, whose constructor takes them in declaration order.
+pub fn declare_structure(config: &mut Config, name: &str, structure: &Structure) {
+    let fields = structure
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| Literal::String(field.identifier.as_ref().map(ToString::to_string).unwrap_or_else(|| index.to_string())))
+        .collect();
+    config.set(format!("{STRUCTURE}::{name}"), Literal::Array(fields));
+}
+
+/// The WGSL built-in functions Rust has as methods, taking the receiver first, like `a.dot(b)`
+/// is `dot(a, b)`.
+fn builtin(method: &str) -> Option<&'static str> {
+    Some(match method {
+        "abs" => "abs",
+        "min" => "min",
+        "max" => "max",
+        "clamp" => "clamp",
+        "sqrt" => "sqrt",
+        "floor" => "floor",
+        "ceil" => "ceil",
+        "round" => "round",
+        "trunc" => "trunc",
+        "fract" => "fract",
+        "signum" => "sign",
+        "sin" => "sin",
+        "cos" => "cos",
+        "tan" => "tan",
+        "asin" => "asin",
+        "acos" => "acos",
+        "atan" => "atan",
+        "atan2" => "atan2",
+        "exp" => "exp",
+        "exp2" => "exp2",
+        "ln" => "log",
+        "log2" => "log2",
+        "powf" => "pow",
+        "mul_add" => "fma",
+        "to_degrees" => "degrees",
+        "to_radians" => "radians",
+        "dot" => "dot",
+        "cross" => "cross",
+        "length" => "length",
+        "distance" => "distance",
+        "normalize" => "normalize",
+        "lerp" => "mix",
+        _ => return None,
+    })
+}
+
 pub struct WgslExpressionGenerator {
     pub path_generator: Rc<WgslPathGenerator>,
     pub identifier_generator: WgslIdentifierGenerator
@@ -18,6 +78,62 @@ impl Default for WgslExpressionGenerator {
     }
 }
 
+impl WgslExpressionGenerator {
+    /// The concrete WGSL type of `expression`, if it can be worked out without inferring types:
+    /// it's a suffixed literal, a cast, a variable declared with a type, or an operation on them.
+    /// Integer and float literals without a suffix are abstract, so they have none.
+    pub fn type_of(&self, expression: &Expression, config: &Config) -> Option<String> {
+        match expression {
+            Expression::Literal(Literal::UnsignedInteger(_)) => Some("u32".into()),
+            Expression::Literal(Literal::Boolean(_)) => Some("bool".into()),
+            Expression::Path(path) if path.segments.len() == 1 => config
+                .get(format!("{VARIABLE}::{}", path.first().identifier))
+                .and_then(|type_| type_.as_string())
+                .cloned(),
+            Expression::Cast(cast_expression) => self.path_generator.path_segment_generator.type_generator.generate(&cast_expression.type_, config).ok(),
+            Expression::Parenthesized(expression) => self.type_of(expression, config),
+            Expression::Unary(unary_expression) if unary_expression.operator != "*" => self.type_of(&unary_expression.operand, config),
+            Expression::Binary(binary_expression) => match binary_expression.operator.name.as_str() {
+                "==" | "!=" | "<" | "<=" | ">" | ">=" | "&&" | "||" => Some("bool".into()),
+                _ => self.type_of(&binary_expression.left, config).or_else(|| self.type_of(&binary_expression.right, config)),
+            },
+            _ => None,
+        }
+    }
+
+    /// Generates `expression` as a value of the WGSL type `type_`. An integer literal without a
+    /// suffix gets the type's, as it would be an `i32` where nothing else gives it a type, like in
+    /// a `var` declaration.
+    pub fn generate_as(&self, expression: &Expression, type_: Option<&str>, config: &Config) -> Result<String> {
+        match (expression, type_) {
+            (Expression::Literal(Literal::Integer(integer)), Some("u32")) => Ok(format!("{integer}u")),
+            (Expression::Literal(Literal::Integer(integer)), Some("i32")) => Ok(format!("{integer}i")),
+            (Expression::Literal(Literal::Integer(integer)), Some("f32")) => Ok(format!("{integer}f")),
+            (Expression::Literal(Literal::Integer(integer)), Some("f16")) => Ok(format!("{integer}h")),
+            _ => self.generate(expression, config),
+        }
+    }
+
+    fn arguments<'a>(&self, arguments: impl IntoIterator<Item = &'a Expression>, config: &Config) -> Result<String> {
+        let arguments = arguments
+            .into_iter()
+            .map(|argument| self.generate(argument, config))
+            .collect::<Result<Vec<String>>>()?;
+        Ok(arguments.join(", "))
+    }
+
+    fn literal(&self, literal: &Literal) -> Result<String> {
+        Ok(match literal {
+            Literal::Boolean(boolean) => boolean.to_string(),
+            Literal::Integer(integer) => integer.to_string(),
+            Literal::UnsignedInteger(integer) => format!("{integer}u"),
+            // Debug keeps the decimal point, so `1.0` isn't printed as the integer `1`.
+            Literal::Float(float) => format!("{float:?}"),
+            literal => return Err(Error::Message(format!("WGSL doesn't support the literal: {literal:?}"))),
+        })
+    }
+}
+
 impl Generator<&Expression, String> for WgslExpressionGenerator {
     fn generate(&self, expression: &Expression, config: &Config) -> Result<String> {
         let mut result = String::new();
@@ -25,6 +141,9 @@ impl Generator<&Expression, String> for WgslExpressionGenerator {
             Expression::Path(path) => {
                 result.push_str(&self.path_generator.generate(path, config)?);
             },
+            Expression::Literal(literal) => {
+                result.push_str(&self.literal(literal)?);
+            },
             Expression::Binary(binary_expression) => {
                 let left = self.generate(&binary_expression.left, config)?;
                 let right = self.generate(&binary_expression.right, config)?;
@@ -34,7 +153,72 @@ impl Generator<&Expression, String> for WgslExpressionGenerator {
             Expression::Parenthesized(parenthesized_expression) => {
                 result.push_str(&format!("({})", self.generate(parenthesized_expression, config)?));
             },
-            _ => todo!(),
+            Expression::Unary(unary_expression) => {
+                let operator = self.identifier_generator.generate(&unary_expression.operator, config)?;
+                let operand = self.generate(&unary_expression.operand, config)?;
+                result.push_str(&format!("{operator}{operand}"));
+            },
+            Expression::Call(call_expression) => {
+                let function = self.generate(&call_expression.function, config)?;
+                let arguments = self.arguments(&call_expression.arguments, config)?;
+                result.push_str(&format!("{function}({arguments})"));
+            },
+            // WGSL has no methods. The length of a runtime-sized array is `arrayLength`, which takes
+            // a pointer to it, and the other methods are built-in functions.
+            Expression::MethodCall(method_call_expression) => {
+                let method = method_call_expression.method.name.as_str();
+                match (method, builtin(method)) {
+                    ("len", _) if method_call_expression.arguments.is_empty() => {
+                        let receiver = self.generate(&method_call_expression.receiver, config)?;
+                        result.push_str(&format!("arrayLength(&{receiver})"));
+                    },
+                    (_, Some(function)) => {
+                        let arguments = self.arguments(std::iter::once(method_call_expression.receiver.as_ref()).chain(&method_call_expression.arguments), config)?;
+                        result.push_str(&format!("{function}({arguments})"));
+                    },
+                    (method, None) => return Err(Error::Message(format!("WGSL has no equivalent of the method: {method}"))),
+                }
+            },
+            Expression::Field(field_expression) => {
+                let base = self.generate(&field_expression.base, config)?;
+                let member = self.identifier_generator.generate(&field_expression.member, config)?;
+                result.push_str(&format!("{base}.{member}"));
+            },
+            Expression::Index(index_expression) => {
+                let base = self.generate(&index_expression.base, config)?;
+                let index = self.generate(&index_expression.index, config)?;
+                result.push_str(&format!("{base}[{index}]"));
+            },
+            // Conversions are written as constructors, like `f32(a)`.
+            Expression::Cast(cast_expression) => {
+                let type_ = self.path_generator.path_segment_generator.type_generator.generate(&cast_expression.type_, config)?;
+                let expression = self.generate(&cast_expression.expression, config)?;
+                result.push_str(&format!("{type_}({expression})"));
+            },
+            // Structures are constructed with their values in the order their fields are declared
+            // in, which the module's declaration of the structure has.
+            Expression::Structure(structure_expression) => {
+                let path = self.path_generator.generate(&structure_expression.path, config)?;
+                let name = structure_expression.path.last().identifier.to_string();
+                let values = match config.get(format!("{STRUCTURE}::{name}")) {
+                    Some(Literal::Array(fields)) => fields
+                        .iter()
+                        .filter_map(Literal::as_string)
+                        .map(|field| {
+                            structure_expression
+                                .fields
+                                .iter()
+                                .find(|value| &value.identifier.name == field)
+                                .map(|value| &value.value)
+                                .ok_or_else(|| Error::Message(format!("{name} is constructed without its field {field}.")))
+                        })
+                        .collect::<Result<Vec<_>>>()?,
+                    _ if structure_expression.fields.len() <= 1 => structure_expression.fields.iter().map(|field| &field.value).collect(),
+                    _ => return Err(Error::Message(format!("{name} isn't declared in the module, so the order of its fields isn't known."))),
+                };
+                let values = self.arguments(values, config)?;
+                result.push_str(&format!("{path}({values})"));
+            },
         }
         Ok(result)
     }
@@ -6,7 +6,7 @@ use std::rc::Rc;
 use ligen_transformer::prelude::*;
 use ligen_idl::Function;
 use ligen_ir::Block;
-use crate::{WgslBlockGenerator, WgslIdentifierGenerator, WgslPathGenerator, WgslTypeGenerator};
+use crate::{declare_variable, WgslBlockGenerator, WgslIdentifierGenerator, WgslPathGenerator, WgslTypeGenerator};
 
 pub struct WgslFunctionGenerator {
     pub identifier_generator: WgslIdentifierGenerator,
@@ -42,7 +42,12 @@ impl Generator<&Function<Block>, String> for WgslFunctionGenerator {
         if let Some(output) = &function.output {
             result.push_str(&format!(" -> {}", self.type_generator.generate(output, config)?));
         }
-        result.push_str(&format!(" {}", self.block_generator.generate(&function.body, config)?));
+        let mut config = config.clone();
+        for input in &function.inputs {
+            let type_ = self.type_generator.generate(&input.type_, &config)?;
+            declare_variable(&mut config, &input.identifier.name, &type_);
+        }
+        result.push_str(&format!(" {}", self.block_generator.generate(&function.body, &config)?));
         Ok(result)
     }
 }
@@ -64,4 +69,63 @@ mod tests {
         let result = generator.generate(&function, &Config::default()).unwrap();
         assert_eq!(result, "fn div(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {return a / b;}");
     }
+
+    #[test]
+    fn statements() -> Result<()> {
+        use ligen_idl::{Literal, Mutability, Path, Type};
+        use ligen_ir::{Assignment, CastExpression, FieldExpression, For, If, IndexExpression, Let, MethodCallExpression, UnaryExpression};
+        let generator = WgslFunctionGenerator::default();
+        let value = IndexExpression::new(Path::from("input"), CastExpression::new(FieldExpression::new(Path::from("id"), "x"), Type::u32()));
+        let block = Block::from([
+            Let::new(Mutability::Mutable, "sum", Some(Type::f32()), Some(Literal::Float(0.0).into())).into(),
+            For {
+                identifier: "i".into(),
+                start: Literal::UnsignedInteger(0).into(),
+                end: Path::from("count").into(),
+                inclusive: false,
+                body: Statement::from(Assignment::new(Path::from("sum"), "+=", MethodCallExpression::new(value, "max", vec![Literal::Float(1.0).into()]))).into(),
+            }.into(),
+            If::new(
+                BinaryExpression::new(Path::from("sum"), "<", Literal::Float(0.0)),
+                Statement::return_(Some(UnaryExpression::new("-", Path::from("sum")))),
+                None,
+            ).into(),
+            Statement::return_(Some(Path::from("sum"))),
+        ]);
+        let function = Function::new("total", vec![Parameter::new("count", Type::u32())], Some(Type::f32()), block);
+        let result = generator.generate(&function, &Config::default())?;
+        assert_eq!(result, "fn total(count: u32) -> f32 {var sum: f32 = 0.0;for (var i = 0u; i < count; i++) {sum += max(input[u32(id.x)], 1.0);}if sum < 0.0 {return -sum;}return sum;}");
+        Ok(())
+    }
+
+    fn rust(function: &str) -> Result<Function<Block>> {
+        let function = syn::parse_str::<syn::ItemFn>(function).map_err(|error| Error::Message(error.to_string()))?;
+        ligen_rust_parser::RustFunctionParser::default().transform(function, &Config::default())
+    }
+
+    #[test]
+    fn from_rust() -> Result<()> {
+        let function = rust("fn mean(values: array<f32>, count: u32) -> f32 {
+            let mut sum = 0.0;
+            for i in 0..count {
+                sum += values[i].abs();
+            }
+            sum / values.len() as f32
+        }")?;
+        let result = WgslFunctionGenerator::default().generate(&function, &Config::default())?;
+        assert_eq!(result, "fn mean(values: array<f32>, count: u32) -> f32 {var sum = 0.0;for (var i = 0u; i < count; i++) {sum += abs(values[i]);}return sum / f32(arrayLength(&values));}");
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported() -> Result<()> {
+        let generator = WgslFunctionGenerator::default();
+        let cast = rust("fn index(x: f32) -> u32 { let index = x as usize; 0 }")?;
+        let error = generator.generate(&cast, &Config::default()).unwrap_err();
+        assert_eq!(error.to_string(), "Message: WGSL doesn't support the type: usize");
+        let method = rust("fn first(values: array<f32>) -> f32 { values.first() }")?;
+        let error = generator.generate(&method, &Config::default()).unwrap_err();
+        assert_eq!(error.to_string(), "Message: WGSL has no equivalent of the method: first");
+        Ok(())
+    }
 }
\ No newline at end of file
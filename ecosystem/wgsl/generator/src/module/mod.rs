@@ -1,7 +1,7 @@
 use ligen_transformer::prelude::*;
-use ligen_idl::Module;
+use ligen_idl::{KindDefinition, Module};
 use ligen_ir::Block;
-use crate::WgslFunctionGenerator;
+use crate::{declare_structure, WgslFunctionGenerator};
 
 #[derive(Default)]
 pub struct WgslModuleGenerator {
@@ -10,12 +10,46 @@ pub struct WgslModuleGenerator {
 
 impl Generator<&Module<Block>, String> for WgslModuleGenerator {
     fn generate(&self, function: &Module<Block>, config: &Config) -> Result<String> {
+        // WGSL has no unions, and a shader can't reinterpret their storage as another type.
+        if let Some(union) = function.types.iter().find(|definition| matches!(definition.definition, KindDefinition::Union(_))) {
+            return Err(Error::Message(format!("WGSL doesn't support unions: {}", union.identifier)));
+        }
+        let mut config = config.clone();
+        for definition in &function.types {
+            if let KindDefinition::Structure(structure) = &definition.definition {
+                declare_structure(&mut config, &definition.identifier.name, structure);
+            }
+        }
         let mut result = String::new();
         for function in &function.functions {
-            let function = self.function_generator.generate(function, config)?;
+            let function = self.function_generator.generate(function, &config)?;
             result.push('\n');
             result.push_str(&function);
         }
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ligen_idl::{Field, Function, Structure, Type, TypeDefinition};
+
+    use super::*;
+
+    #[test]
+    fn structures() -> Result<()> {
+        let function = syn::parse_str::<syn::ItemFn>("fn sample(x: f32) -> Sample { Sample { weight: 1.0, value: x } }")
+            .map_err(|error| Error::Message(error.to_string()))?;
+        let function: Function<Block> = ligen_rust_parser::RustFunctionParser::default().transform(function, &Config::default())?;
+        let field = |identifier: &str| Field { identifier: Some(identifier.into()), type_: Type::f32(), ..Default::default() };
+        let sample = Structure { fields: vec![field("value"), field("weight")] };
+        let sample = TypeDefinition { identifier: "Sample".into(), definition: KindDefinition::Structure(sample), ..Default::default() };
+        let mut module = Module { types: vec![sample], functions: vec![function], ..Default::default() };
+        // The constructor takes the fields in the order they're declared in, not written in.
+        let result = WgslModuleGenerator::default().generate(&module, &Config::default())?;
+        assert_eq!(result, "\nfn sample(x: f32) -> Sample {return Sample(x, 1.0);}");
+        module.types.clear();
+        assert!(WgslModuleGenerator::default().generate(&module, &Config::default()).is_err());
+        Ok(())
+    }
+}
@@ -1,11 +1,52 @@
+use std::rc::Rc;
+
 use ligen_transformer::prelude::*;
-use ligen_ir::Statement;
+use ligen_idl::Mutability;
+use ligen_ir::{Block, Let, Statement};
 
-use crate::WgslExpressionGenerator;
+use crate::{declare_variable, WgslExpressionGenerator, WgslIdentifierGenerator, WgslPathGenerator};
 
-#[derive(Default)]
 pub struct WgslStatementGenerator {
     pub expression_generator: WgslExpressionGenerator,
+    pub identifier_generator: WgslIdentifierGenerator,
+    pub path_generator: Rc<WgslPathGenerator>,
+}
+
+impl Default for WgslStatementGenerator {
+    fn default() -> Self {
+        let expression_generator = WgslExpressionGenerator::default();
+        let identifier_generator = Default::default();
+        let path_generator = WgslPathGenerator::new();
+        Self { expression_generator, identifier_generator, path_generator }
+    }
+}
+
+impl WgslStatementGenerator {
+    /// Generates `block`, which statements like `if` and the loops nest. The variables it declares
+    /// with a known type are in scope for the statements after them.
+    pub fn block(&self, block: &Block, config: &Config) -> Result<String> {
+        let mut config = config.clone();
+        let mut result = String::new();
+        result.push('{');
+        for statement in &block.statements {
+            result.push_str(&self.generate(statement, &config)?);
+            if let Statement::Let(let_statement) = statement {
+                if let Some(type_) = self.let_type(let_statement, &config) {
+                    declare_variable(&mut config, &let_statement.identifier.name, &type_);
+                }
+            }
+        }
+        result.push('}');
+        Ok(result)
+    }
+
+    /// The WGSL type of the variable `let_statement` declares, if it's known.
+    fn let_type(&self, let_statement: &Let, config: &Config) -> Option<String> {
+        match &let_statement.type_ {
+            Some(type_) => self.path_generator.path_segment_generator.type_generator.generate(type_, config).ok(),
+            None => let_statement.value.as_ref().and_then(|value| self.expression_generator.type_of(value, config)),
+        }
+    }
 }
 
 impl Generator<&Statement, String> for WgslStatementGenerator {
@@ -18,7 +59,64 @@ impl Generator<&Statement, String> for WgslStatementGenerator {
                     result.push(' ');
                     result.push_str(&self.expression_generator.generate(value, config)?);
                 }
-            }
+            },
+            // Only `var`s can be mutated or declared without a value.
+            Statement::Let(let_statement) => {
+                let keyword = match (&let_statement.mutability, &let_statement.value) {
+                    (Mutability::Constant, Some(_)) => "let",
+                    _ => "var",
+                };
+                result.push_str(&format!("{keyword} {}", self.identifier_generator.generate(&let_statement.identifier, config)?));
+                if let Some(type_) = &let_statement.type_ {
+                    result.push_str(&format!(": {}", self.path_generator.path_segment_generator.type_generator.generate(type_, config)?));
+                }
+                if let Some(value) = &let_statement.value {
+                    result.push_str(&format!(" = {}", self.expression_generator.generate(value, config)?));
+                }
+            },
+            Statement::Assignment(assignment) => {
+                let target = self.expression_generator.generate(&assignment.target, config)?;
+                let operator = self.identifier_generator.generate(&assignment.operator, config)?;
+                let value = self.expression_generator.generate(&assignment.value, config)?;
+                result.push_str(&format!("{target} {operator} {value}"));
+            },
+            Statement::If(if_statement) => {
+                let condition = self.expression_generator.generate(&if_statement.condition, config)?;
+                result.push_str(&format!("if {condition} {}", self.block(&if_statement.then, config)?));
+                if let Some(else_) = &if_statement.else_ {
+                    match else_.statements.as_slice() {
+                        [else_if @ Statement::If(_)] => result.push_str(&format!(" else {}", self.generate(else_if, config)?)),
+                        _ => result.push_str(&format!(" else {}", self.block(else_, config)?)),
+                    }
+                }
+                return Ok(result);
+            },
+            Statement::Loop(loop_statement) => return Ok(format!("loop {}", self.block(&loop_statement.body, config)?)),
+            Statement::While(while_statement) => {
+                let condition = self.expression_generator.generate(&while_statement.condition, config)?;
+                return Ok(format!("while {condition} {}", self.block(&while_statement.body, config)?));
+            },
+            // The counter has the type of the bounds, so `0..count` counts from `0u` if `count` is a
+            // `u32`, instead of from the `i32` an abstract `0` would make it.
+            Statement::For(for_statement) => {
+                let identifier = self.identifier_generator.generate(&for_statement.identifier, config)?;
+                let type_ = self
+                    .expression_generator
+                    .type_of(&for_statement.end, config)
+                    .or_else(|| self.expression_generator.type_of(&for_statement.start, config));
+                let start = self.expression_generator.generate_as(&for_statement.start, type_.as_deref(), config)?;
+                let end = self.expression_generator.generate(&for_statement.end, config)?;
+                let comparison = if for_statement.inclusive { "<=" } else { "<" };
+                let mut config = config.clone();
+                if let Some(type_) = &type_ {
+                    declare_variable(&mut config, &identifier, type_);
+                }
+                let body = self.block(&for_statement.body, &config)?;
+                return Ok(format!("for (var {identifier} = {start}; {identifier} {comparison} {end}; {identifier}++) {body}"));
+            },
+            Statement::Break => result.push_str("break"),
+            Statement::Continue => result.push_str("continue"),
+            Statement::Expression(expression) => result.push_str(&self.expression_generator.generate(expression, config)?),
         }
         result.push(';');
         Ok(result)
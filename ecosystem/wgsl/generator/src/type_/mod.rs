@@ -16,7 +16,16 @@ impl WgslTypeGenerator {
 }
 
 impl Generator<&Type, String> for WgslTypeGenerator {
+    /// WGSL's scalars are `bool`, `i32`, `u32`, `f32` and `f16`. The other primitive types, like
+    /// `usize` and `f64`, are rejected instead of being narrowed to one of them.
     fn generate(&self, type_: &Type, config: &Config) -> Result<String> {
+        if type_.is_boolean() {
+            return Ok("bool".into());
+        }
+        let is_scalar = [Type::i32(), Type::u32(), Type::f32(), Type::f16()].contains(type_);
+        if !is_scalar && (type_.is_primitive() || type_.is(Type::usize()) || type_.is(Type::isize())) {
+            return Err(Error::Message(format!("WGSL doesn't support the type: {}", type_.path)));
+        }
         self.path_generator.upgrade().ok_or(Error::Message("Path generator not found".to_string()))?.generate(&type_.path, config)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,237 @@
+//! Diagnostics.
+
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+/// Diagnostic severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    /// Informative note.
+    Note,
+    /// Something was skipped or approximated, but the result is still usable.
+    Warning,
+    /// The result is incomplete or wrong.
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Note => write!(f, "note"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Source location, in 1-based lines and 0-based columns.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    /// Source file, if known.
+    pub file: Option<String>,
+    /// Start line.
+    pub line: usize,
+    /// Start column.
+    pub column: usize,
+    /// End line.
+    pub end_line: usize,
+    /// End column.
+    pub end_column: usize,
+}
+
+impl Span {
+    /// Creates a new span.
+    pub fn new(line: usize, column: usize, end_line: usize, end_column: usize) -> Self {
+        let file = None;
+        Self { file, line, column, end_line, end_column }
+    }
+
+    /// Creates a span from byte offsets into `source`.
+    pub fn from_offsets(source: &str, start: usize, end: usize) -> Self {
+        let (line, column) = Self::line_column(source, start);
+        let (end_line, end_column) = Self::line_column(source, end);
+        Self::new(line, column, end_line, end_column)
+    }
+
+    /// Sets the source file.
+    pub fn with_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    fn line_column(source: &str, offset: usize) -> (usize, usize) {
+        let offset = offset.min(source.len());
+        let before = source.get(..offset).unwrap_or_default();
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rfind('\n')
+            .map(|index| offset - index - 1)
+            .unwrap_or(offset);
+        (line, column)
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{file}:")?;
+        }
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// A structured message about the input, reported while transforming it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Severity.
+    pub severity: Severity,
+    /// Stable, machine-readable code, e.g. `skipped-method`.
+    pub code: String,
+    /// Human-readable message.
+    pub message: String,
+    /// Path of the symbol the diagnostic is about, if any.
+    pub symbol: Option<String>,
+    /// Source location, if known.
+    pub span: Option<Span>,
+}
+
+impl Diagnostic {
+    /// Creates a new diagnostic.
+    pub fn new(severity: Severity, code: impl Into<String>, message: impl Into<String>) -> Self {
+        let code = code.into();
+        let message = message.into();
+        let symbol = None;
+        let span = None;
+        Self { severity, code, message, symbol, span }
+    }
+
+    /// Creates a new note.
+    pub fn note(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(Severity::Note, code, message)
+    }
+
+    /// Creates a new warning.
+    pub fn warning(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, code, message)
+    }
+
+    /// Creates a new error.
+    pub fn error(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, code, message)
+    }
+
+    /// Sets the symbol path.
+    pub fn with_symbol(mut self, symbol: impl ToString) -> Self {
+        self.symbol = Some(symbol.to_string());
+        self
+    }
+
+    /// Sets the source location.
+    pub fn with_span(mut self, span: impl Into<Option<Span>>) -> Self {
+        self.span = span.into();
+        self
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)?;
+        match (&self.symbol, &self.span) {
+            (Some(symbol), Some(span)) => write!(f, " ({symbol} at {span})"),
+            (Some(symbol), None) => write!(f, " ({symbol})"),
+            (None, Some(span)) => write!(f, " (at {span})"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Diagnostics collector.
+///
+/// Cloning it shares the collection, so every clone of a `Config` reports to the same place.
+#[derive(Debug, Default, Clone)]
+pub struct Diagnostics {
+    diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+impl Diagnostics {
+    /// Creates a new, empty collector.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Reports a diagnostic.
+    pub fn report(&self, diagnostic: Diagnostic) {
+        self.lock().push(diagnostic);
+    }
+
+    /// Reports every diagnostic in `diagnostics`.
+    pub fn extend(&self, diagnostics: impl IntoIterator<Item = Diagnostic>) {
+        self.lock().extend(diagnostics);
+    }
+
+    /// Returns a snapshot of the reported diagnostics.
+    pub fn to_vec(&self) -> Vec<Diagnostic> {
+        self.lock().clone()
+    }
+
+    /// Takes the reported diagnostics, leaving the collector empty.
+    pub fn take(&self) -> Vec<Diagnostic> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    /// Number of reported diagnostics.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether nothing was reported.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Whether an error was reported.
+    pub fn has_errors(&self) -> bool {
+        self.lock().iter().any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Diagnostic>> {
+        // A panic while holding the lock can't leave a Vec half-pushed, so the data is still good.
+        self.diagnostics.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn span_from_offsets() {
+        let source = "first\nsecond line\nthird";
+        let start = source.find("line").unwrap();
+        let span = Span::from_offsets(source, start, start + 4);
+        assert_eq!(span, Span::new(2, 7, 2, 11));
+        assert_eq!(Span::from_offsets(source, 0, 0), Span::new(1, 0, 1, 0));
+    }
+
+    #[test]
+    fn clones_share_the_collection() {
+        let diagnostics = Diagnostics::new();
+        diagnostics.clone().report(Diagnostic::warning("skipped-method", "Unsupported receiver."));
+        assert_eq!(diagnostics.len(), 1);
+        assert!(!diagnostics.has_errors());
+        diagnostics.report(Diagnostic::error("invalid", "Invalid input."));
+        assert!(diagnostics.has_errors());
+        assert_eq!(diagnostics.take().len(), 2);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn display() {
+        let diagnostic = Diagnostic::warning("skipped-method", "Unsupported receiver.")
+            .with_symbol("library::Type::method")
+            .with_span(Span::new(3, 4, 3, 10).with_file("src/lib.rs"));
+        assert_eq!(diagnostic.to_string(), "warning[skipped-method]: Unsupported receiver. (library::Type::method at src/lib.rs:3:4)");
+    }
+}
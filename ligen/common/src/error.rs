@@ -20,6 +20,9 @@ pub enum Error {
     /// Any error.
     #[error("{0}")]
     Anyhow(anyhow::Error),
+    /// Structured diagnostic.
+    #[error("{0}")]
+    Diagnostic(crate::Diagnostic),
 }
 
 impl From<&str> for Error {
@@ -58,5 +61,11 @@ impl From<anyhow::Error> for Error {
     }
 }
 
+impl From<crate::Diagnostic> for Error {
+    fn from(diagnostic: crate::Diagnostic) -> Self {
+        Self::Diagnostic(diagnostic)
+    }
+}
+
 /// Library result.
 pub type Result<T> = std::result::Result<T, Error>;
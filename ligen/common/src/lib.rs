@@ -1,6 +1,8 @@
 pub mod error;
+pub mod diagnostic;
 
 pub use error::*;
+pub use diagnostic::*;
 pub use serde;
 pub use serde::{Serialize, Deserialize};
 pub use derive_more::Display;
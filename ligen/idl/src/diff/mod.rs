@@ -0,0 +1,561 @@
+//! Semantic diff between two versions of a `Library`.
+
+pub mod semver;
+pub use self::semver::*;
+
+use std::fmt::{Display, Formatter};
+
+use crate::prelude::*;
+use crate::{
+    Attributes, Field, Function, Identifier, Import, Interface, KindDefinition, Library, Method,
+    Module, Mutability, Object, Parameter, Path, Receiver, Synchrony, Type, TypeDefinition,
+    Variant, Visibility,
+};
+
+/// How a change affects the users of a library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Compatibility {
+    /// Existing users keep working.
+    Compatible,
+    /// Existing users may break.
+    Breaking,
+}
+
+/// What happened to an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// The item was added, or it gained an interface or a default value.
+    Added,
+    /// The item was removed.
+    Removed,
+    /// The item exists in both versions, but differs.
+    Changed,
+}
+
+/// The kind of item a change is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[allow(missing_docs)]
+pub enum ItemKind {
+    Module,
+    Import,
+    Object,
+    Type,
+    Field,
+    Variant,
+    Function,
+    Method,
+    Parameter,
+    Interface,
+}
+
+impl Display for ItemKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Module => "module",
+            Self::Import => "import",
+            Self::Object => "object",
+            Self::Type => "type",
+            Self::Field => "field",
+            Self::Variant => "variant",
+            Self::Function => "function",
+            Self::Method => "method",
+            Self::Parameter => "parameter",
+            Self::Interface => "interface",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A single API change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Change {
+    /// What happened to the item.
+    pub kind: ChangeKind,
+    /// Whether the change is breaking.
+    pub compatibility: Compatibility,
+    /// The kind of the item.
+    pub item: ItemKind,
+    /// Path of the item, starting at the root module.
+    pub path: Path,
+    /// Human-readable description.
+    pub description: String,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let compatibility = match self.compatibility {
+            Compatibility::Compatible => "compatible",
+            Compatibility::Breaking => "breaking",
+        };
+        write!(f, "{compatibility}: {} `{}`: {}", self.item, self.path, self.description)
+    }
+}
+
+/// The changes between two versions of a `Library`.
+///
+/// Only the public API is compared: private items are ignored, and making an item private is
+/// reported as its removal. Structures and enumerations follow Rust's rules, so adding a field to
+/// a structure with only public fields or a variant to an enumeration is breaking, unless the type
+/// is `#[non_exhaustive]`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Diff {
+    /// The changes, in the order the items appear.
+    pub changes: Vec<Change>,
+}
+
+impl Diff {
+    /// Compares the `old` and `new` versions of a library.
+    pub fn new(old: &Library, new: &Library) -> Self {
+        let mut diff = Self::default();
+        let path = Path::from(new.root_module.identifier.clone());
+        diff.module_items(&path, &old.root_module, &new.root_module);
+        diff
+    }
+
+    /// Compares the `old` and `new` versions of a module.
+    pub fn modules(old: &Module, new: &Module) -> Self {
+        let mut diff = Self::default();
+        diff.module_items(&Path::from(new.identifier.clone()), old, new);
+        diff
+    }
+
+    /// Returns `true` if any change is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(|change| change.compatibility == Compatibility::Breaking)
+    }
+
+    /// Returns the breaking changes.
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.compatibility == Compatibility::Breaking)
+    }
+
+    /// Returns `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn push(&mut self, kind: ChangeKind, compatibility: Compatibility, item: ItemKind, path: Path, description: impl Into<String>) {
+        let description = description.into();
+        self.changes.push(Change { kind, compatibility, item, path, description });
+    }
+
+    fn added(&mut self, item: ItemKind, path: Path, compatibility: Compatibility) {
+        self.push(ChangeKind::Added, compatibility, item, path, format!("{item} added"));
+    }
+
+    /// A compatible change adding to an existing item's API.
+    fn extended(&mut self, item: ItemKind, path: &Path, description: impl Into<String>) {
+        self.push(ChangeKind::Added, Compatibility::Compatible, item, path.clone(), description);
+    }
+
+    fn removed(&mut self, item: ItemKind, path: Path) {
+        self.push(ChangeKind::Removed, Compatibility::Breaking, item, path, format!("{item} removed"));
+    }
+
+    fn changed(&mut self, compatibility: Compatibility, item: ItemKind, path: &Path, description: impl Into<String>) {
+        self.push(ChangeKind::Changed, compatibility, item, path.clone(), description);
+    }
+
+    /// Pairs the public items of both versions by name, reporting the added and removed ones, and
+    /// calls `compare` for the ones in both.
+    fn pair<'a, T: HasVisibility + 'a>(
+        &mut self,
+        item: ItemKind,
+        path: &Path,
+        old: impl IntoIterator<Item = &'a T>,
+        new: impl IntoIterator<Item = &'a T>,
+        name: impl Fn(&T) -> String,
+        mut compare: impl FnMut(&mut Self, &Path, &'a T, &'a T),
+    ) {
+        let old = old.into_iter().filter(|item| item.is_public()).collect::<Vec<_>>();
+        let new = new.into_iter().filter(|item| item.is_public()).collect::<Vec<_>>();
+        for old_item in &old {
+            if !new.iter().any(|new_item| name(new_item) == name(old_item)) {
+                self.removed(item, path.clone().join(name(old_item)));
+            }
+        }
+        for new_item in &new {
+            match old.iter().find(|old_item| name(old_item) == name(new_item)) {
+                Some(old_item) => compare(self, &path.clone().join(name(new_item)), old_item, new_item),
+                None => self.added(item, path.clone().join(name(new_item)), Compatibility::Compatible),
+            }
+        }
+    }
+
+    fn module_items(&mut self, path: &Path, old: &Module, new: &Module) {
+        self.pair(ItemKind::Import, path, &old.imports, &new.imports, import_name, |_, _, _, _| ());
+        self.pair(ItemKind::Object, path, &old.objects, &new.objects, |object| object.identifier.to_string(), Self::object);
+        self.pair(ItemKind::Type, path, &old.types, &new.types, |type_| type_.identifier.to_string(), Self::type_definition);
+        self.pair(ItemKind::Function, path, &old.functions, &new.functions, |function| function.identifier.to_string(), Self::function);
+        self.interfaces(path, &old.interfaces, &new.interfaces);
+        self.pair(ItemKind::Module, path, &old.modules, &new.modules, |module| module.identifier.to_string(), Self::module_items);
+    }
+
+    fn object(&mut self, path: &Path, old: &Object, new: &Object) {
+        if old.type_ != new.type_ {
+            self.changed(Compatibility::Breaking, ItemKind::Object, path, format!("type changed from `{}` to `{}`", old.type_, new.type_));
+        }
+        if old.mutability != new.mutability {
+            self.changed(Compatibility::Breaking, ItemKind::Object, path, format!("changed from {} to {}", object_mutability(&old.mutability), object_mutability(&new.mutability)));
+        }
+        if old.literal != new.literal {
+            self.changed(Compatibility::Compatible, ItemKind::Object, path, format!("value changed from `{}` to `{}`", old.literal, new.literal));
+        }
+    }
+
+    fn type_definition(&mut self, path: &Path, old: &TypeDefinition, new: &TypeDefinition) {
+        if old.generics != new.generics {
+            self.changed(Compatibility::Breaking, ItemKind::Type, path, format!("generics changed from `{}` to `{}`", old.generics, new.generics));
+        }
+        for interface in old.interfaces.iter().filter(|interface| !new.interfaces.contains(interface)) {
+            self.changed(Compatibility::Breaking, ItemKind::Type, path, format!("no longer implements `{interface}`"));
+        }
+        for interface in new.interfaces.iter().filter(|interface| !old.interfaces.contains(interface)) {
+            self.extended(ItemKind::Type, path, format!("now implements `{interface}`"));
+        }
+        let exhaustive = !is_non_exhaustive(&new.attributes);
+        match (&old.definition, &new.definition) {
+            (KindDefinition::Structure(old_structure), KindDefinition::Structure(new_structure)) => {
+                let constructible = exhaustive && old_structure.fields.iter().all(|field| field.visibility == Visibility::Public);
+                let (old_fields, new_fields) = (Indexed::fields(&old_structure.fields), Indexed::fields(&new_structure.fields));
+                let before = self.changes.len();
+                self.pair(ItemKind::Field, path, &old_fields, &new_fields, Indexed::name, |diff, path, old, new| diff.field(path, old.1, new.1));
+                if constructible {
+                    for change in &mut self.changes[before..] {
+                        if change.item == ItemKind::Field && change.kind == ChangeKind::Added {
+                            change.compatibility = Compatibility::Breaking;
+                            change.description = "field added to a structure that can be constructed".into();
+                        }
+                    }
+                }
+            },
+            (KindDefinition::Enumeration(old_enumeration), KindDefinition::Enumeration(new_enumeration)) => {
+                let before = self.changes.len();
+                self.pair(ItemKind::Variant, path, &old_enumeration.variants, &new_enumeration.variants, |variant| variant.identifier.to_string(), |_, _, _, _| ());
+                if exhaustive {
+                    for change in &mut self.changes[before..] {
+                        if change.kind == ChangeKind::Added {
+                            change.compatibility = Compatibility::Breaking;
+                            change.description = "variant added to an exhaustive enumeration".into();
+                        }
+                    }
+                }
+            },
+            (KindDefinition::TypeAlias(old_alias), KindDefinition::TypeAlias(new_alias)) => {
+                if old_alias.type_ != new_alias.type_ {
+                    self.changed(Compatibility::Breaking, ItemKind::Type, path, format!("aliased type changed from `{}` to `{}`", old_alias.type_, new_alias.type_));
+                }
+            },
+            (KindDefinition::Union(old_union), KindDefinition::Union(new_union)) => {
+                let (old_fields, new_fields) = (Indexed::fields(&old_union.fields), Indexed::fields(&new_union.fields));
+                self.pair(ItemKind::Field, path, &old_fields, &new_fields, Indexed::name, |diff, path, old, new| diff.field(path, old.1, new.1));
+            },
+            (old_definition, new_definition) => {
+                self.changed(Compatibility::Breaking, ItemKind::Type, path, format!("changed from {} to {}", old_definition.kind_name(), new_definition.kind_name()));
+            },
+        }
+    }
+
+    fn field(&mut self, path: &Path, old: &Field, new: &Field) {
+        if old.type_ != new.type_ {
+            self.changed(Compatibility::Breaking, ItemKind::Field, path, format!("type changed from `{}` to `{}`", old.type_, new.type_));
+        }
+    }
+
+    fn function(&mut self, path: &Path, old: &Function, new: &Function) {
+        self.signature(ItemKind::Function, path, (&old.synchrony, &old.inputs, &old.output), (&new.synchrony, &new.inputs, &new.output));
+    }
+
+    fn method(&mut self, path: &Path, old: &Method, new: &Method) {
+        if old.receiver != new.receiver {
+            // Only a method taking `&mut self` can take `&self` instead without breaking its callers.
+            let compatibility = match (&old.receiver, &new.receiver) {
+                (Receiver::Mutable, Receiver::Shared) => Compatibility::Compatible,
+                _ => Compatibility::Breaking,
+            };
+            self.changed(compatibility, ItemKind::Method, path, format!("receiver changed from `{}` to `{}`", receiver(&old.receiver), receiver(&new.receiver)));
+        }
+        self.signature(ItemKind::Method, path, (&old.synchrony, &old.inputs, &old.output), (&new.synchrony, &new.inputs, &new.output));
+    }
+
+    fn signature(&mut self, item: ItemKind, path: &Path, old: Signature, new: Signature) {
+        let (old_synchrony, old_inputs, old_output) = old;
+        let (new_synchrony, new_inputs, new_output) = new;
+        if old_synchrony != new_synchrony {
+            let description = match new_synchrony {
+                Synchrony::Synchronous => "is no longer asynchronous",
+                Synchrony::Asynchronous => "is now asynchronous",
+            };
+            self.changed(Compatibility::Breaking, item, path, description);
+        }
+        if old_output != new_output {
+            let output = |output: &Option<Type>| output.as_ref().unwrap_or(&Type::void()).to_string();
+            self.changed(Compatibility::Breaking, item, path, format!("output changed from `{}` to `{}`", output(old_output), output(new_output)));
+        }
+        for (index, old_parameter) in old_inputs.iter().enumerate() {
+            let parameter_path = path.clone().join(old_parameter.identifier.clone());
+            match new_inputs.get(index) {
+                None => self.removed(ItemKind::Parameter, parameter_path),
+                Some(new_parameter) => self.parameter(&parameter_path, old_parameter, new_parameter),
+            }
+        }
+        for new_parameter in new_inputs.iter().skip(old_inputs.len()) {
+            let parameter_path = path.clone().join(new_parameter.identifier.clone());
+            if new_parameter.default_value.is_some() {
+                self.added(ItemKind::Parameter, parameter_path, Compatibility::Compatible);
+            } else {
+                self.push(ChangeKind::Added, Compatibility::Breaking, ItemKind::Parameter, parameter_path, "required parameter added");
+            }
+        }
+    }
+
+    fn parameter(&mut self, path: &Path, old: &Parameter, new: &Parameter) {
+        if old.identifier != new.identifier {
+            self.changed(Compatibility::Compatible, ItemKind::Parameter, path, format!("renamed to `{}`", new.identifier));
+        }
+        if old.type_ != new.type_ {
+            self.changed(Compatibility::Breaking, ItemKind::Parameter, path, format!("type changed from `{}` to `{}`", old.type_, new.type_));
+        }
+        match (&old.default_value, &new.default_value) {
+            (Some(_), None) => self.changed(Compatibility::Breaking, ItemKind::Parameter, path, "default value removed"),
+            (None, Some(_)) => self.extended(ItemKind::Parameter, path, "default value added"),
+            (Some(old_value), Some(new_value)) if old_value != new_value => self.changed(Compatibility::Compatible, ItemKind::Parameter, path, format!("default value changed from `{old_value}` to `{new_value}`")),
+            _ => (),
+        }
+    }
+
+    /// A type may have many interfaces, so their items are compared as if they were a single one.
+    fn interfaces(&mut self, path: &Path, old: &[Interface], new: &[Interface]) {
+        let mut identifiers = Vec::<&Identifier>::new();
+        for interface in old.iter().chain(new) {
+            if !identifiers.contains(&&interface.identifier) {
+                identifiers.push(&interface.identifier);
+            }
+        }
+        for identifier in identifiers {
+            let path = path.clone().join(identifier.clone());
+            let old = old.iter().filter(|interface| interface.identifier == *identifier && interface.is_public()).collect::<Vec<_>>();
+            let new = new.iter().filter(|interface| interface.identifier == *identifier && interface.is_public()).collect::<Vec<_>>();
+            self.pair(ItemKind::Object, &path, old.iter().flat_map(|interface| &interface.objects), new.iter().flat_map(|interface| &interface.objects), |object| object.identifier.to_string(), Self::object);
+            self.pair(ItemKind::Type, &path, old.iter().flat_map(|interface| &interface.types), new.iter().flat_map(|interface| &interface.types), |definition| definition.identifier.to_string(), Self::type_definition);
+            self.pair(ItemKind::Function, &path, old.iter().flat_map(|interface| &interface.functions), new.iter().flat_map(|interface| &interface.functions), |function| function.identifier.to_string(), Self::function);
+            self.pair(ItemKind::Method, &path, old.iter().flat_map(|interface| &interface.methods), new.iter().flat_map(|interface| &interface.methods), |method| method.identifier.to_string(), Self::method);
+            for interface in new.iter().flat_map(|interface| &interface.interfaces) {
+                if !old.iter().any(|old| old.interfaces.contains(interface)) {
+                    self.extended(ItemKind::Interface, &path, format!("now implements `{interface}`"));
+                }
+            }
+            for interface in old.iter().flat_map(|interface| &interface.interfaces) {
+                if !new.iter().any(|new| new.interfaces.contains(interface)) {
+                    self.changed(Compatibility::Breaking, ItemKind::Interface, &path, format!("no longer implements `{interface}`"));
+                }
+            }
+        }
+    }
+}
+
+impl Display for Diff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+type Signature<'a> = (&'a Synchrony, &'a Vec<Parameter>, &'a Option<Type>);
+
+/// Items compared by `Diff`, which ignores the private ones.
+trait HasVisibility {
+    fn is_public(&self) -> bool;
+}
+
+macro_rules! has_visibility {
+    ($($type_:ty),*) => {
+        $(impl HasVisibility for $type_ {
+            fn is_public(&self) -> bool {
+                self.visibility == Visibility::Public
+            }
+        })*
+    };
+}
+
+has_visibility!(Import, Object, TypeDefinition, Function, Method, Interface, Module, Field);
+
+impl HasVisibility for Variant {
+    fn is_public(&self) -> bool {
+        true
+    }
+}
+
+/// A field and its position, which identifies it when it's unnamed.
+struct Indexed<'a>(usize, &'a Field);
+
+impl<'a> Indexed<'a> {
+    fn fields(fields: &'a [Field]) -> Vec<Self> {
+        fields.iter().enumerate().map(|(index, field)| Indexed(index, field)).collect()
+    }
+
+    fn name(&self) -> String {
+        self.1.identifier.as_ref().map(ToString::to_string).unwrap_or_else(|| self.0.to_string())
+    }
+}
+
+impl HasVisibility for Indexed<'_> {
+    fn is_public(&self) -> bool {
+        self.1.is_public()
+    }
+}
+
+fn import_name(import: &Import) -> String {
+    match &import.renaming {
+        Some(renaming) => format!("{} as {renaming}", import.path),
+        None => import.path.to_string(),
+    }
+}
+
+fn receiver(receiver: &Receiver) -> String {
+    match receiver {
+        Receiver::Shared => "&self".into(),
+        Receiver::Mutable => "&mut self".into(),
+        Receiver::Owned => "self".into(),
+        Receiver::Boxed(type_) => format!("self: {type_}"),
+    }
+}
+
+fn object_mutability(mutability: &Mutability) -> &'static str {
+    match mutability {
+        Mutability::Constant => "constant",
+        Mutability::Mutable => "mutable",
+    }
+}
+
+fn is_non_exhaustive(attributes: &Attributes) -> bool {
+    attributes.iter().any(|attribute| {
+        attribute
+            .as_group()
+            .map(|group| group.path == Path::from("non_exhaustive"))
+            .unwrap_or(false)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Enumeration, Group, Literal, Structure};
+
+    fn structure(fields: Vec<(&str, Type)>) -> TypeDefinition {
+        let fields = fields
+            .into_iter()
+            .map(|(identifier, type_)| Field { identifier: Some(identifier.into()), type_, ..Default::default() })
+            .collect();
+        TypeDefinition {
+            identifier: "Point".into(),
+            definition: KindDefinition::Structure(Structure { fields }),
+            ..Default::default()
+        }
+    }
+
+    fn function(inputs: Vec<Parameter>) -> Function {
+        Function { identifier: "draw".into(), inputs, ..Default::default() }
+    }
+
+    fn module(types: Vec<TypeDefinition>, functions: Vec<Function>) -> Module {
+        Module { identifier: "lib".into(), types, functions, ..Default::default() }
+    }
+
+    fn summary(diff: &Diff) -> Vec<(ChangeKind, Compatibility, String)> {
+        diff.changes
+            .iter()
+            .map(|change| (change.kind, change.compatibility, change.path.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parameters() {
+        let old = module(vec![], vec![function(vec![Parameter::new("a", Type::i32())])]);
+        let mut optional = Parameter::new("b", Type::i32());
+        optional.default_value = Some(Literal::Integer(1));
+        let new = module(vec![], vec![function(vec![Parameter::new("a", Type::i32()), optional])]);
+        let diff = Diff::modules(&old, &new);
+        assert_eq!(summary(&diff), [(ChangeKind::Added, Compatibility::Compatible, "lib::draw::b".into())]);
+        assert!(!diff.is_breaking());
+
+        let new = module(vec![], vec![function(vec![Parameter::new("a", Type::i64()), Parameter::new("b", Type::i32())])]);
+        let diff = Diff::modules(&old, &new);
+        assert_eq!(summary(&diff), [
+            (ChangeKind::Changed, Compatibility::Breaking, "lib::draw::a".into()),
+            (ChangeKind::Added, Compatibility::Breaking, "lib::draw::b".into()),
+        ]);
+    }
+
+    #[test]
+    fn fields() {
+        let old = module(vec![structure(vec![("x", Type::f32()), ("y", Type::f32())])], vec![]);
+        let new = module(vec![structure(vec![("x", Type::f64())])], vec![]);
+        assert_eq!(summary(&Diff::modules(&old, &new)), [
+            (ChangeKind::Removed, Compatibility::Breaking, "lib::Point::y".into()),
+            (ChangeKind::Changed, Compatibility::Breaking, "lib::Point::x".into()),
+        ]);
+
+        let mut new = structure(vec![("x", Type::f32()), ("y", Type::f32()), ("z", Type::f32())]);
+        assert!(Diff::modules(&old, &module(vec![new.clone()], vec![])).is_breaking());
+        new.attributes = Group::from("non_exhaustive").into();
+        assert!(!Diff::modules(&old, &module(vec![new], vec![])).is_breaking());
+    }
+
+    #[test]
+    fn visibility() {
+        let old = module(vec![structure(vec![])], vec![function(vec![])]);
+        let mut new = old.clone();
+        new.functions[0].visibility = Visibility::Private;
+        new.types.clear();
+        let diff = Diff::modules(&old, &new);
+        assert_eq!(summary(&diff), [
+            (ChangeKind::Removed, Compatibility::Breaking, "lib::Point".into()),
+            (ChangeKind::Removed, Compatibility::Breaking, "lib::draw".into()),
+        ]);
+        let diff = Diff::modules(&new, &old);
+        assert_eq!(diff.changes.len(), 2);
+        assert!(!diff.is_breaking());
+    }
+
+    #[test]
+    fn variants() {
+        let enumeration = |variants: Vec<&str>| TypeDefinition {
+            identifier: "Color".into(),
+            definition: KindDefinition::Enumeration(Enumeration {
+                variants: variants.into_iter().map(|identifier| Variant { identifier: identifier.into(), ..Default::default() }).collect(),
+            }),
+            ..Default::default()
+        };
+        let old = module(vec![enumeration(vec!["Red"])], vec![]);
+        let new = module(vec![enumeration(vec!["Red", "Green"])], vec![]);
+        let diff = Diff::modules(&old, &new);
+        assert_eq!(summary(&diff), [(ChangeKind::Added, Compatibility::Breaking, "lib::Color::Green".into())]);
+        assert_eq!(diff.to_string(), "breaking: variant `lib::Color::Green`: variant added to an exhaustive enumeration\n");
+    }
+
+    #[test]
+    fn receivers() {
+        let counter = |receiver: Receiver, mutability: Mutability| Module {
+            identifier: "lib".into(),
+            interfaces: vec![Interface {
+                identifier: "Counter".into(),
+                methods: vec![Method { identifier: "get".into(), receiver, mutability, ..Default::default() }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let diff = Diff::modules(&counter(Receiver::Mutable, Mutability::Mutable), &counter(Receiver::Shared, Mutability::Constant));
+        assert_eq!(summary(&diff), [(ChangeKind::Changed, Compatibility::Compatible, "lib::Counter::get".into())]);
+        let diff = Diff::modules(&counter(Receiver::Shared, Mutability::Constant), &counter(Receiver::Owned, Mutability::Constant));
+        assert_eq!(diff.to_string(), "breaking: method `lib::Counter::get`: receiver changed from `&self` to `self`\n");
+        // `mut self` only makes the binding mutable in the body, the callers still pass `self`.
+        let diff = Diff::modules(&counter(Receiver::Owned, Mutability::Constant), &counter(Receiver::Owned, Mutability::Mutable));
+        assert!(diff.is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+//! Semantic versioning checks.
+
+use std::fmt::{Display, Formatter};
+
+use crate::prelude::*;
+use crate::{Library, Version};
+
+use super::{ChangeKind, Compatibility, Diff};
+
+/// How much a release changes, by Cargo's semver rules.
+///
+/// Cargo considers the left-most non-zero component the major one, so `0.1.0` to `0.2.0` is a
+/// `Major` bump and `0.1.0` to `0.1.1` is a `Minor` one. Every `0.0.z` release is `Major`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Bump {
+    /// The version didn't increase.
+    None,
+    /// Fixes only.
+    Patch,
+    /// Compatible additions.
+    Minor,
+    /// Breaking changes.
+    Major,
+}
+
+impl Bump {
+    /// The bump between the `old` and `new` versions.
+    pub fn between(old: &Version, new: &Version) -> Self {
+        let (old, new) = (&old.0, &new.0);
+        if new <= old {
+            Self::None
+        } else if old.major > 0 {
+            if new.major != old.major {
+                Self::Major
+            } else if new.minor != old.minor {
+                Self::Minor
+            } else {
+                Self::Patch
+            }
+        } else if old.minor > 0 {
+            if new.major != old.major || new.minor != old.minor {
+                Self::Major
+            } else if new.patch != old.patch {
+                Self::Minor
+            } else {
+                Self::Patch
+            }
+        } else if new.major != old.major || new.minor != old.minor || new.patch != old.patch {
+            Self::Major
+        } else {
+            Self::Patch
+        }
+    }
+
+    /// The bump the changes in `diff` require: `Minor` for additions and `Patch` for compatible
+    /// changes that don't add to the API, like relaxing a receiver from `mut self` to `self`.
+    pub fn required(diff: &Diff) -> Self {
+        if diff.is_breaking() {
+            Self::Major
+        } else if diff.changes.iter().any(|change| change.kind == ChangeKind::Added) {
+            Self::Minor
+        } else if !diff.changes.is_empty() {
+            Self::Patch
+        } else {
+            Self::None
+        }
+    }
+}
+
+impl Display for Bump {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::None => "none",
+            Self::Patch => "patch",
+            Self::Minor => "minor",
+            Self::Major => "major",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Checks that a release's version bump matches its API changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SemverCheck {
+    /// The old version.
+    pub old: Version,
+    /// The new version.
+    pub new: Version,
+    /// The bump the API changes require.
+    pub required: Bump,
+    /// The bump between the versions.
+    pub actual: Bump,
+    /// The API changes.
+    pub diff: Diff,
+}
+
+impl SemverCheck {
+    /// Checks the release from `old` to `new`, using their `Metadata::version`.
+    pub fn new(old: &Library, new: &Library) -> Self {
+        let diff = Diff::new(old, new);
+        let old = old.metadata.version.clone();
+        let new = new.metadata.version.clone();
+        let required = Bump::required(&diff);
+        let actual = Bump::between(&old, &new);
+        Self { old, new, required, actual, diff }
+    }
+
+    /// Returns `true` if the version bump is enough for the API changes.
+    pub fn is_ok(&self) -> bool {
+        self.actual >= self.required
+    }
+
+    /// Returns an error describing the failure, if the check failed.
+    pub fn check(&self) -> Result<()> {
+        if self.is_ok() {
+            Ok(())
+        } else {
+            Err(Error::Message(self.to_string()))
+        }
+    }
+}
+
+impl Display for SemverCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_ok() {
+            write!(f, "{} -> {} is a {} release, and the API changes require at most a {} one.", self.old, self.new, self.actual, self.required)
+        } else {
+            writeln!(f, "{} -> {} is a {} release, but the API changes require a {} one:", self.old, self.new, self.actual, self.required)?;
+            let changes = self.diff.changes.iter().filter(|change| match self.required {
+                Bump::Major => change.compatibility == Compatibility::Breaking,
+                Bump::Minor => change.kind == ChangeKind::Added,
+                _ => true,
+            });
+            for change in changes {
+                writeln!(f, "  {change}")?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Function, Interface, Method, Module, Receiver};
+
+    fn version(version: &str) -> Version {
+        Version::try_from(version).unwrap()
+    }
+
+    fn library(version: &str, functions: &[&str]) -> Library {
+        let functions = functions
+            .iter()
+            .map(|identifier| Function { identifier: (*identifier).into(), ..Default::default() })
+            .collect();
+        let mut library = Library { root_module: Module { identifier: "lib".into(), functions, ..Default::default() }, ..Default::default() };
+        library.metadata.version = self::version(version);
+        library
+    }
+
+    #[test]
+    fn bumps() {
+        assert_eq!(Bump::between(&version("1.2.3"), &version("2.0.0")), Bump::Major);
+        assert_eq!(Bump::between(&version("1.2.3"), &version("1.3.0")), Bump::Minor);
+        assert_eq!(Bump::between(&version("1.2.3"), &version("1.2.4")), Bump::Patch);
+        assert_eq!(Bump::between(&version("0.2.3"), &version("0.3.0")), Bump::Major);
+        assert_eq!(Bump::between(&version("0.2.3"), &version("0.2.4")), Bump::Minor);
+        assert_eq!(Bump::between(&version("0.0.3"), &version("0.0.4")), Bump::Major);
+        assert_eq!(Bump::between(&version("1.2.3"), &version("1.2.3")), Bump::None);
+        assert_eq!(Bump::between(&version("1.2.3"), &version("1.0.0")), Bump::None);
+    }
+
+    #[test]
+    fn checks() {
+        let old = library("1.0.0", &["a", "b"]);
+        assert!(SemverCheck::new(&old, &library("1.0.1", &["a", "b"])).is_ok());
+        assert!(!SemverCheck::new(&old, &library("1.0.1", &["a", "b", "c"])).is_ok());
+        assert!(SemverCheck::new(&old, &library("1.1.0", &["a", "b", "c"])).is_ok());
+        let check = SemverCheck::new(&old, &library("1.1.0", &["a"]));
+        assert!(check.check().is_err());
+        assert_eq!(check.required, Bump::Major);
+        assert!(check.to_string().contains("breaking: function `lib::b`: function removed"));
+        assert!(SemverCheck::new(&old, &library("2.0.0", &["a"])).is_ok());
+    }
+
+    #[test]
+    fn patches() {
+        let counter = |version: &str, receiver: Receiver| {
+            let mut library = library(version, &["a"]);
+            let method = Method { identifier: "get".into(), receiver, ..Default::default() };
+            library.root_module.interfaces.push(Interface { identifier: "Counter".into(), methods: vec![method], ..Default::default() });
+            library
+        };
+        let check = SemverCheck::new(&counter("1.0.0", Receiver::Mutable), &counter("1.0.1", Receiver::Shared));
+        assert_eq!(check.required, Bump::Patch);
+        assert!(check.is_ok());
+        assert_eq!(SemverCheck::new(&counter("1.0.0", Receiver::Mutable), &counter("1.0.0", Receiver::Mutable)).required, Bump::None);
+    }
+}
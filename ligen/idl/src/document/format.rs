@@ -0,0 +1,120 @@
+//! Document formats.
+
+use crate::prelude::*;
+use std::borrow::Cow;
+use std::path::Path;
+
+/// Document encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// Pretty-printed JSON.
+    #[default]
+    Json,
+    /// CBOR, a compact binary encoding of the same data model.
+    Cbor,
+}
+
+/// Document format: an encoding, optionally compressed with zstd.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Format {
+    /// Encoding.
+    pub encoding: Encoding,
+    /// Whether the encoded document is zstd-compressed.
+    pub compressed: bool,
+}
+
+impl Format {
+    /// Creates a new format.
+    pub fn new(encoding: Encoding, compressed: bool) -> Self {
+        Self { encoding, compressed }
+    }
+
+    /// Selects the format by file extension: `.cbor` is CBOR, anything else is JSON, and a
+    /// trailing `.zst` (e.g. `library.cbor.zst`) compresses it.
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let compressed = Self::extension(path) == Some("zst");
+        let path = if compressed { path.with_extension("") } else { path.to_path_buf() };
+        let encoding = match Self::extension(&path) {
+            Some("cbor") => Encoding::Cbor,
+            _ => Encoding::Json,
+        };
+        Self { encoding, compressed }
+    }
+
+    /// Encodes a value.
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        let bytes = match self.encoding {
+            Encoding::Json => serde_json::to_vec_pretty(value)?,
+            Encoding::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes)
+                    .map_err(|error| Error::Message(format!("Failed to encode CBOR: {error}")))?;
+                bytes
+            }
+        };
+        if self.compressed {
+            Ok(zstd::encode_all(bytes.as_slice(), zstd::DEFAULT_COMPRESSION_LEVEL)?)
+        } else {
+            Ok(bytes)
+        }
+    }
+
+    /// Decodes a value.
+    pub fn decode<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
+        self.deserialize(&self.decompress(bytes)?)
+    }
+
+    /// Decompresses the encoded bytes, if the format is compressed.
+    pub fn decompress<'a>(&self, bytes: &'a [u8]) -> Result<Cow<'a, [u8]>> {
+        if self.compressed {
+            Ok(Cow::Owned(zstd::decode_all(bytes)?))
+        } else {
+            Ok(Cow::Borrowed(bytes))
+        }
+    }
+
+    /// Deserializes a value from decompressed bytes.
+    pub fn deserialize<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
+        match self.encoding {
+            Encoding::Json => Ok(serde_json::from_slice(bytes)?),
+            Encoding::Cbor => ciborium::from_reader(bytes)
+                .map_err(|error| Error::Message(format!("Failed to decode CBOR: {error}"))),
+        }
+    }
+
+    fn extension(path: &Path) -> Option<&str> {
+        path.extension().and_then(|extension| extension.to_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_path() {
+        assert_eq!(Format::from_path("library.json"), Format::new(Encoding::Json, false));
+        assert_eq!(Format::from_path("library.lir"), Format::new(Encoding::Json, false));
+        assert_eq!(Format::from_path("library.cbor"), Format::new(Encoding::Cbor, false));
+        assert_eq!(Format::from_path("library.json.zst"), Format::new(Encoding::Json, true));
+        assert_eq!(Format::from_path("library.cbor.zst"), Format::new(Encoding::Cbor, true));
+    }
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        use crate::{Function, Identifier, Library, Module, Registry, Type};
+        let function = Function { identifier: "area".into(), output: Some(Type::f32()), ..Default::default() };
+        let root_module = Module { identifier: "shapes".into(), functions: vec![function], ..Default::default() };
+        let library = Library { identifier: "shapes".into(), root_module, ..Default::default() };
+        let registry = Registry { libraries: [(Identifier::from("shapes"), library.clone())].into() };
+        for encoding in [Encoding::Json, Encoding::Cbor] {
+            for compressed in [false, true] {
+                let format = Format::new(encoding, compressed);
+                assert_eq!(format.decode::<Library>(&format.encode(&library)?)?, library);
+                assert_eq!(format.decode::<Registry>(&format.encode(&registry)?)?, registry);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+//! Document migrations.
+
+use crate::prelude::*;
+use super::{DocumentKind, FORMAT, FORMAT_VERSION};
+use serde_json::Value;
+
+/// Upgrades the content of a document of the given kind by one format version.
+pub type Migration = fn(DocumentKind, Value) -> Result<Value>;
+
+/// Migrations indexed by the format version they upgrade from.
+pub const MIGRATIONS: [Migration; FORMAT_VERSION as usize] = [
+    from_unversioned,
+    with_receivers,
+];
+
+/// Version 0 documents are the bare content written before documents had an envelope.
+fn from_unversioned(_kind: DocumentKind, content: Value) -> Result<Value> {
+    Ok(content)
+}
+
+/// Version 1 methods have no `receiver`. A `Mutable` method took `&mut self`, the others `&self`.
+fn with_receivers(_kind: DocumentKind, mut content: Value) -> Result<Value> {
+    add_receivers(&mut content);
+    Ok(content)
+}
+
+fn add_receivers(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::Array(methods)) = object.get_mut("methods") {
+                for method in methods.iter_mut().filter_map(Value::as_object_mut) {
+                    let receiver = match method.get("mutability").and_then(Value::as_str) {
+                        Some("Mutable") => "Mutable",
+                        _ => "Shared",
+                    };
+                    method.entry("receiver").or_insert_with(|| receiver.into());
+                }
+            }
+            object.values_mut().for_each(add_receivers);
+        },
+        Value::Array(values) => values.iter_mut().for_each(add_receivers),
+        _ => (),
+    }
+}
+
+/// Migrates a document to the current format version and returns its content.
+pub fn migrate(kind: DocumentKind, document: Value) -> Result<Value> {
+    let (version, content) = unwrap(kind, document)?;
+    if version > FORMAT_VERSION {
+        return Err(Error::Message(format!("Document format version {version} is newer than the supported version {FORMAT_VERSION}.")));
+    }
+    MIGRATIONS[version as usize..]
+        .iter()
+        .try_fold(content, |content, migration| migration(kind, content))
+}
+
+fn unwrap(kind: DocumentKind, document: Value) -> Result<(u32, Value)> {
+    let Value::Object(mut document) = document else {
+        return Err(Error::Message("Document isn't a JSON object.".into()));
+    };
+    let Some(version) = document.remove("format_version") else {
+        return Ok((0, Value::Object(document)));
+    };
+    let version = version
+        .as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+        .ok_or_else(|| Error::Message(format!("Invalid document format version: {version}")))?;
+    let format = document.remove("format").unwrap_or_default();
+    if format != FORMAT {
+        return Err(Error::Message(format!("Unknown document format: {format}")));
+    }
+    let found = document.remove("kind").unwrap_or_default();
+    if found != serde_json::to_value(kind)? {
+        return Err(Error::Message(format!("Expected a {} document, found {found}.", serde_json::to_value(kind)?)));
+    }
+    let content = document
+        .remove("content")
+        .ok_or_else(|| Error::Message("Document has no content.".into()))?;
+    Ok((version, content))
+}
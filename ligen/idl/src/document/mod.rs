@@ -0,0 +1,246 @@
+//! Versioned IDL documents.
+//!
+//! Libraries and registries are saved wrapped in an envelope carrying the format version they
+//! were written with, so that documents saved by older versions of ligen can be upgraded by the
+//! migration pipeline instead of failing to deserialize.
+
+pub mod format;
+pub mod migration;
+
+pub use format::{Encoding, Format};
+
+use crate::prelude::*;
+use crate::{Library, Registry};
+use schemars::schema::RootSchema;
+use serde::de::DeserializeOwned;
+use migration::migrate;
+
+/// Format name written to every document.
+pub const FORMAT: &str = "ligen-idl";
+
+/// Current format version. Bump it and add a migration whenever a change to the IDL breaks
+/// previously saved documents.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// Document kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentKind {
+    /// A single `Library`.
+    Library,
+    /// A `Registry` of libraries.
+    Registry,
+}
+
+/// Versioned document envelope.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Document<T> {
+    /// Format name. Always `FORMAT`.
+    pub format: String,
+    /// Format version the content was written with.
+    pub format_version: u32,
+    /// Content kind.
+    pub kind: DocumentKind,
+    /// Document content.
+    pub content: T,
+}
+
+/// The envelope of a document, without its content. Unversioned documents have none of it.
+#[derive(Deserialize)]
+struct Header {
+    format: Option<String>,
+    format_version: Option<u32>,
+    kind: Option<DocumentKind>,
+}
+
+/// Types that can be saved as a versioned document.
+pub trait DocumentContent: Serialize + DeserializeOwned + JsonSchema {
+    /// Document kind.
+    const KIND: DocumentKind;
+}
+
+impl DocumentContent for Library {
+    const KIND: DocumentKind = DocumentKind::Library;
+}
+
+impl DocumentContent for Registry {
+    const KIND: DocumentKind = DocumentKind::Registry;
+}
+
+impl<T: DocumentContent> Document<T> {
+    /// Wraps `content` in a document of the current format version.
+    pub fn new(content: T) -> Self {
+        let format = FORMAT.to_string();
+        let format_version = FORMAT_VERSION;
+        let kind = T::KIND;
+        Self { format, format_version, kind, content }
+    }
+
+    /// JSON schema of the document.
+    pub fn schema() -> RootSchema {
+        schema_for!(Document<T>)
+    }
+
+    /// Serializes `content` to a pretty-printed JSON document.
+    pub fn to_json(content: &T) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&Self::envelope(content))?)
+    }
+
+    /// Deserializes a JSON document, migrating it to the current format version if needed.
+    pub fn from_json(json: &str) -> Result<T> {
+        Self::from_bytes(json.as_bytes(), Format::default())
+    }
+
+    /// Encodes `content` as a document in the given format.
+    pub fn to_bytes(content: &T, format: Format) -> Result<Vec<u8>> {
+        format.encode(&Self::envelope(content))
+    }
+
+    /// Decodes a document in the given format, migrating it to the current format version if needed.
+    ///
+    /// Documents of the current format version are decoded straight into their content. Only older
+    /// ones go through a JSON value, for the migrations to upgrade it.
+    pub fn from_bytes(bytes: &[u8], format: Format) -> Result<T> {
+        let bytes = format.decompress(bytes)?;
+        let header: Header = format.deserialize(&bytes)?;
+        if header.format_version != Some(FORMAT_VERSION) {
+            return Self::from_value(format.deserialize(&bytes)?);
+        }
+        if header.format.as_deref() != Some(FORMAT) {
+            return Err(Error::Message(format!("Unknown document format: {}", header.format.unwrap_or_default())));
+        }
+        if header.kind != Some(T::KIND) {
+            return Err(Error::Message(format!("Expected a {:?} document, found {:?}.", T::KIND, header.kind)));
+        }
+        let document: Document<T> = format.deserialize(&bytes)?;
+        Ok(document.content)
+    }
+
+    /// Saves `content` to a file, in the format selected by its extension.
+    pub fn save(content: &T, path: impl AsRef<std::path::Path>) -> Result<()> {
+        let path = path.as_ref();
+        let bytes = Self::to_bytes(content, Format::from_path(path))?;
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    /// Loads a document from a file, in the format selected by its extension.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<T> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)?;
+        Self::from_bytes(&bytes, Format::from_path(path))
+    }
+
+    /// Deserializes a JSON value, migrating it to the current format version if needed.
+    pub fn from_value(value: serde_json::Value) -> Result<T> {
+        let content = migrate(T::KIND, value)?;
+        Ok(serde_json::from_value(content)?)
+    }
+
+    fn envelope(content: &T) -> Document<&T> {
+        let format = FORMAT.to_string();
+        let format_version = FORMAT_VERSION;
+        let kind = T::KIND;
+        Document { format, format_version, kind, content }
+    }
+}
+
+impl Library {
+    /// JSON schema of saved library documents.
+    pub fn schema() -> RootSchema {
+        Document::<Self>::schema()
+    }
+}
+
+impl Registry {
+    /// JSON schema of saved registry documents.
+    pub fn schema() -> RootSchema {
+        Document::<Self>::schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Identifier;
+
+    fn library() -> Library {
+        Library { identifier: Identifier::new("library"), ..Default::default() }
+    }
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        let library = library();
+        let json = Document::to_json(&library)?;
+        let value: serde_json::Value = serde_json::from_str(&json)?;
+        assert_eq!(value["format"], FORMAT);
+        assert_eq!(value["format_version"], FORMAT_VERSION);
+        assert_eq!(value["kind"], "library");
+        assert_eq!(Document::<Library>::from_json(&json)?, library);
+        Ok(())
+    }
+
+    #[test]
+    fn unversioned_document() -> Result<()> {
+        let library = library();
+        let json = serde_json::to_string(&library)?;
+        assert_eq!(Document::<Library>::from_json(&json)?, library);
+        Ok(())
+    }
+
+    #[test]
+    fn newer_document() -> Result<()> {
+        let mut value = serde_json::to_value(Document::new(library()))?;
+        value["format_version"] = (FORMAT_VERSION + 1).into();
+        assert!(Document::<Library>::from_value(value).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn binary_round_trip() -> Result<()> {
+        let library = library();
+        let format = Format::new(Encoding::Cbor, true);
+        let bytes = Document::to_bytes(&library, format)?;
+        assert_eq!(Document::<Library>::from_bytes(&bytes, format)?, library);
+        Ok(())
+    }
+
+    #[test]
+    fn binary_migration() -> Result<()> {
+        let library = library();
+        let format = Format::new(Encoding::Cbor, true);
+        let mut value = serde_json::to_value(Document::new(library.clone()))?;
+        value["format_version"] = 1.into();
+        assert_eq!(Document::<Library>::from_bytes(&format.encode(&value)?, format)?, library);
+        let bytes = format.encode(&library)?;
+        assert_eq!(Document::<Library>::from_bytes(&bytes, format)?, library);
+        Ok(())
+    }
+
+    #[test]
+    fn mismatched_kind() -> Result<()> {
+        let json = Document::to_json(&library())?;
+        assert!(Document::<Registry>::from_json(&json).is_err());
+        let bytes = Document::to_bytes(&library(), Format::new(Encoding::Cbor, false))?;
+        assert!(Document::<Registry>::from_bytes(&bytes, Format::new(Encoding::Cbor, false)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn receivers() -> Result<()> {
+        use crate::{Interface, Method, Mutability, Receiver};
+        let method = |identifier: &str, mutability: Mutability| Method { identifier: identifier.into(), mutability, receiver: Receiver::Owned, ..Default::default() };
+        let mut library = library();
+        let methods = vec![method("get", Mutability::Constant), method("set", Mutability::Mutable)];
+        library.root_module.interfaces.push(Interface { identifier: "Counter".into(), methods, ..Default::default() });
+        let mut value = serde_json::to_value(Document::new(library))?;
+        value["format_version"] = 1.into();
+        for method in value["content"]["root_module"]["interfaces"][0]["methods"].as_array_mut().into_iter().flatten() {
+            method.as_object_mut().map(|method| method.remove("receiver"));
+        }
+        let library = Document::<Library>::from_value(value)?;
+        let receivers = library.root_module.interfaces[0].methods.iter().map(|method| method.receiver.clone()).collect::<Vec<_>>();
+        assert_eq!(receivers, vec![Receiver::Shared, Receiver::Mutable]);
+        Ok(())
+    }
+}
@@ -1,6 +1,21 @@
 use crate::prelude::*;
 use crate::{Synchrony, Attributes, Mutability, Parameter, Type, Visibility, Identifier};
 
+/// How a method takes its receiver, `self`.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub enum Receiver {
+    /// `&self`.
+    #[default]
+    Shared,
+    /// `&mut self`.
+    Mutable,
+    /// `self`, taking ownership.
+    Owned,
+    /// `self` behind a smart pointer, like `self: Box<Self>` or `self: Arc<Self>`. It's the
+    /// pointer type, with `Self` resolved.
+    Boxed(Type),
+}
+
 /// Method structure.
 #[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Method<Body = ()> {
@@ -8,6 +23,9 @@ pub struct Method<Body = ()> {
     pub attributes: Attributes,
     /// The owner mutability.
     pub mutability: Mutability,
+    /// How the owner is taken. Documents saved before it was recorded are migrated from the
+    /// `mutability`.
+    pub receiver: Receiver,
     /// Visibility field.
     pub visibility: Visibility,
     /// Synchrony field.
@@ -2,6 +2,7 @@
 pub mod mock;
 
 mod naming_convention;
+pub use naming_convention::*;
 
 use crate::path::PathSegment;
 use crate::{prelude::*, Mutability};
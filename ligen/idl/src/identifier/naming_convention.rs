@@ -74,6 +74,18 @@ impl Identifier {
         self.name.to_kebab_case().into()
     }
 
+    /// Convert the Identifier to the `convention`. `Unknown` keeps it as it is.
+    pub fn to_naming_convention(&self, convention: &NamingConvention) -> Self {
+        match convention {
+            NamingConvention::KebabCase => self.to_kebab_case(),
+            NamingConvention::SnakeCase => self.to_snake_case(),
+            NamingConvention::ScreamingSnakeCase => self.to_screaming_snake_case(),
+            NamingConvention::PascalCase => self.to_pascal_case(),
+            NamingConvention::CamelCase => self.to_camel_case(),
+            NamingConvention::Unknown => self.clone(),
+        }
+    }
+
     /// Get the words of the Identifier.
     pub fn words(&self) -> Vec<&str> {
         match self.naming_convention() {
@@ -1,7 +1,7 @@
 use is_tree::*;
 
 use crate::prelude::*;
-use crate::{Attributes, Function, Identifier, Method, Object, Path, Visibility};
+use crate::{Attributes, Function, Identifier, Method, Object, Path, TypeDefinition, Visibility};
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Interface<Block = ()> {
@@ -11,13 +11,17 @@ pub struct Interface<Block = ()> {
     pub visibility: Visibility,
     /// Interface identifier.
     pub identifier: Identifier,
-    /// Interface objects.
+    /// Interface objects, like associated constants.
     pub objects: Vec<Object>,
+    /// Associated types. Libraries saved before they were modeled have none.
+    #[serde(default)]
+    pub types: Vec<TypeDefinition>,
     /// Public functions.
     pub functions: Vec<Function<Block>>,
     /// Interface methods.
     pub methods: Vec<Method<Block>>,
-    /// Interfaces that this interface extends.
+    /// Interfaces that this interface extends. The `impl` block of a type has the trait it
+    /// implements, if any.
     pub interfaces: Vec<Path>,
 }
 
@@ -37,13 +41,13 @@ impl<Block> CountSymbols for Vec<Interface<Block>> {
 
 impl<Block> CountSymbols for &Interface<Block> {
     fn count_symbols(&self) -> usize {
-        self.objects.count_symbols() + self.functions.count_symbols() + self.methods.count_symbols()
+        self.objects.count_symbols() + self.types.count_symbols() + self.functions.count_symbols() + self.methods.count_symbols()
     }
 }
 
 impl<Block> CountSymbols for Interface<Block> {
     fn count_symbols(&self) -> usize {
-        self.objects.count_symbols() + self.functions.count_symbols() + self.methods.count_symbols()
+        self.objects.count_symbols() + self.types.count_symbols() + self.functions.count_symbols() + self.methods.count_symbols()
     }
 }
 
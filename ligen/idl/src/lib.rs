@@ -2,16 +2,20 @@
 
 pub mod prelude;
 
+pub mod diff;
+pub mod document;
 pub mod function;
 pub mod identifier;
 pub mod interface;
 pub mod library;
 pub mod literal;
 pub mod macro_attributes;
+pub mod merge;
 pub mod module;
 pub mod mutability;
 pub mod object;
 pub mod path;
+pub mod query;
 pub mod registry;
 pub mod source;
 pub mod types;
@@ -21,16 +25,19 @@ pub mod symbols;
 
 pub mod visitor;
 
-pub use function::{Function, Method, Parameter, Synchrony};
-pub use identifier::Identifier;
+pub use document::{Document, DocumentContent, DocumentKind};
+pub use function::{Function, Method, Parameter, Receiver, Synchrony};
+pub use identifier::{Identifier, NamingConvention};
 pub use interface::*;
 pub use library::*;
 pub use literal::Literal;
 pub use macro_attributes::*;
+pub use merge::{Merge, MergePolicy};
 pub use module::{Import, Module};
 pub use mutability::*;
 pub use object::Object;
 pub use path::{Path, PathSegment};
+pub use query::Query;
 pub use registry::*;
 pub use source::*;
 pub use types::*;
@@ -4,6 +4,7 @@ pub mod metadata;
 use is_tree::IsTree;
 pub use metadata::*;
 
+use crate::Document;
 use crate::Identifier;
 use crate::Module;
 use crate::prelude::*;
@@ -20,16 +21,13 @@ pub struct Library {
 }
 
 impl Library {
-    /// Save library to file.
+    /// Save library to file. The format is selected by the file extension.
     pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
-        let contents = serde_json::to_string_pretty(self)?;
-        std::fs::write(path, contents)?;
-        Ok(())
+        Document::save(self, path)
     }
 
-    /// Load library from file.
+    /// Load library from file. The format is selected by the file extension.
     pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
-        let json = std::fs::read_to_string(path)?;
-        Ok(serde_json::from_str(&json)?)
+        Document::load(path)
     }
 }
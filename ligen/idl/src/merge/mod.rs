@@ -0,0 +1,282 @@
+//! Merging of libraries and their items.
+
+use crate::prelude::*;
+use crate::{
+    Attributes, Function, Import, Interface, Library, Method, Module, Object, Path, Registry,
+    TypeDefinition,
+};
+
+/// What to do when both sides define the same item differently.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum MergePolicy {
+    /// Keep the item being merged into.
+    #[default]
+    PreferLeft,
+    /// Keep the item being merged.
+    PreferRight,
+    /// Fail with a `merge-conflict` diagnostic.
+    Error,
+}
+
+/// Merges two definitions of the same item.
+///
+/// Items are deduplicated by path, so items with the same identifier are merged into one, and all
+/// the `Interface`s of a type are folded into a single one. Attributes are merged. Items that only
+/// differ in their attributes aren't conflicts, other differences are resolved with the
+/// `MergePolicy`.
+pub trait Merge: Sized {
+    /// Merges `other` into `self`.
+    fn merge(&mut self, other: Self, policy: MergePolicy) -> Result<()> {
+        let path = Path::default();
+        self.merge_at(&path, other, policy)
+    }
+
+    /// Merges `other` into `self`, where `path` is the path of the parent item, for diagnostics.
+    fn merge_at(&mut self, path: &Path, other: Self, policy: MergePolicy) -> Result<()>;
+}
+
+impl Merge for Registry {
+    fn merge_at(&mut self, path: &Path, other: Self, policy: MergePolicy) -> Result<()> {
+        for (identifier, library) in other.libraries {
+            match self.libraries.get_mut(&identifier) {
+                Some(existing) => existing.merge_at(path, library, policy)?,
+                None => {
+                    self.libraries.insert(identifier, library);
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Merge for Library {
+    fn merge_at(&mut self, path: &Path, other: Self, policy: MergePolicy) -> Result<()> {
+        let path = path.clone().join(self.identifier.clone());
+        if self.metadata != other.metadata {
+            resolve(&path, "metadata", &mut self.metadata, other.metadata, policy)?;
+        }
+        let root_module = std::mem::take(&mut self.root_module);
+        self.root_module = merge_root(&path, root_module, other.root_module, policy)?;
+        Ok(())
+    }
+}
+
+/// The root modules may have different identifiers, such as `lib` and the library name.
+fn merge_root(path: &Path, mut left: Module, mut right: Module, policy: MergePolicy) -> Result<Module> {
+    right.identifier = left.identifier.clone();
+    left.merge_at(path, right, policy)?;
+    Ok(left)
+}
+
+impl Merge for Module {
+    fn merge_at(&mut self, path: &Path, other: Self, policy: MergePolicy) -> Result<()> {
+        let path = path.clone().join(self.identifier.clone());
+        if self.visibility != other.visibility {
+            resolve(&path, "visibility", &mut self.visibility, other.visibility, policy)?;
+        }
+        self.attributes.merge_at(&path, other.attributes, policy)?;
+        merge_items(&path, &mut self.imports, other.imports, policy, import_key, merge_import)?;
+        merge_items(&path, &mut self.objects, other.objects, policy, |object| object.identifier.to_string(), merge_object)?;
+        merge_items(&path, &mut self.types, other.types, policy, |type_| type_.identifier.to_string(), merge_type)?;
+        merge_items(&path, &mut self.functions, other.functions, policy, |function| function.identifier.to_string(), merge_function)?;
+        merge_items(&path, &mut self.interfaces, other.interfaces, policy, |interface| interface.identifier.to_string(), Interface::merge_at)?;
+        merge_items(&path, &mut self.modules, other.modules, policy, |module| module.identifier.to_string(), Module::merge_at)?;
+        Ok(())
+    }
+}
+
+impl Merge for Interface {
+    fn merge_at(&mut self, path: &Path, other: Self, policy: MergePolicy) -> Result<()> {
+        let path = path.clone().join(self.identifier.clone());
+        if self.visibility != other.visibility {
+            resolve(&path, "visibility", &mut self.visibility, other.visibility, policy)?;
+        }
+        self.attributes.merge_at(&path, other.attributes, policy)?;
+        for interface in other.interfaces {
+            if !self.interfaces.contains(&interface) {
+                self.interfaces.push(interface);
+            }
+        }
+        merge_items(&path, &mut self.objects, other.objects, policy, |object| object.identifier.to_string(), merge_object)?;
+        merge_items(&path, &mut self.types, other.types, policy, |type_| type_.identifier.to_string(), merge_type)?;
+        merge_items(&path, &mut self.functions, other.functions, policy, |function| function.identifier.to_string(), merge_function)?;
+        merge_items(&path, &mut self.methods, other.methods, policy, |method| method.identifier.to_string(), merge_method)?;
+        Ok(())
+    }
+}
+
+impl Merge for Attributes {
+    fn merge_at(&mut self, _path: &Path, other: Self, _policy: MergePolicy) -> Result<()> {
+        for attribute in other.attributes {
+            if !self.contains(attribute.clone()) {
+                self.push(attribute);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Merges `incoming` into `items`, also merging the duplicates already in `items`.
+fn merge_items<T>(
+    path: &Path,
+    items: &mut Vec<T>,
+    incoming: Vec<T>,
+    policy: MergePolicy,
+    key: impl Fn(&T) -> String,
+    merge: impl Fn(&mut T, &Path, T, MergePolicy) -> Result<()>,
+) -> Result<()> {
+    let existing = std::mem::take(items);
+    for item in existing.into_iter().chain(incoming) {
+        match items.iter_mut().find(|existing| key(existing) == key(&item)) {
+            Some(existing) => merge(existing, path, item, policy)?,
+            None => items.push(item),
+        }
+    }
+    Ok(())
+}
+
+fn import_key(import: &Import) -> String {
+    match &import.renaming {
+        Some(renaming) => format!("{} as {renaming}", import.path),
+        None => import.path.to_string(),
+    }
+}
+
+fn merge_import(left: &mut Import, path: &Path, mut right: Import, policy: MergePolicy) -> Result<()> {
+    let attributes = std::mem::take(&mut right.attributes);
+    left.attributes.merge_at(path, attributes, policy)?;
+    right.attributes = left.attributes.clone();
+    if *left != right {
+        resolve(path, &import_key(left), left, right, policy)?;
+    }
+    Ok(())
+}
+
+fn merge_object(left: &mut Object, path: &Path, right: Object, policy: MergePolicy) -> Result<()> {
+    if *left != right {
+        resolve(path, &left.identifier.to_string(), left, right, policy)?;
+    }
+    Ok(())
+}
+
+fn merge_type(left: &mut TypeDefinition, path: &Path, mut right: TypeDefinition, policy: MergePolicy) -> Result<()> {
+    let attributes = std::mem::take(&mut right.attributes);
+    left.attributes.merge_at(path, attributes, policy)?;
+    right.attributes = left.attributes.clone();
+    for interface in std::mem::take(&mut right.interfaces) {
+        if !left.interfaces.contains(&interface) {
+            left.interfaces.push(interface);
+        }
+    }
+    right.interfaces = left.interfaces.clone();
+    if *left != right {
+        resolve(path, &left.identifier.to_string(), left, right, policy)?;
+    }
+    Ok(())
+}
+
+fn merge_function(left: &mut Function, path: &Path, mut right: Function, policy: MergePolicy) -> Result<()> {
+    let attributes = std::mem::take(&mut right.attributes);
+    left.attributes.merge_at(path, attributes, policy)?;
+    right.attributes = left.attributes.clone();
+    if *left != right {
+        resolve(path, &left.identifier.to_string(), left, right, policy)?;
+    }
+    Ok(())
+}
+
+fn merge_method(left: &mut Method, path: &Path, mut right: Method, policy: MergePolicy) -> Result<()> {
+    let attributes = std::mem::take(&mut right.attributes);
+    left.attributes.merge_at(path, attributes, policy)?;
+    right.attributes = left.attributes.clone();
+    if *left != right {
+        resolve(path, &left.identifier.to_string(), left, right, policy)?;
+    }
+    Ok(())
+}
+
+/// Resolves a conflict between two different definitions of `name`, in the item at `path`.
+fn resolve<T>(path: &Path, name: &str, left: &mut T, right: T, policy: MergePolicy) -> Result<()> {
+    match policy {
+        MergePolicy::PreferLeft => Ok(()),
+        MergePolicy::PreferRight => {
+            *left = right;
+            Ok(())
+        },
+        MergePolicy::Error => {
+            let symbol = if path.is_empty() { name.to_string() } else { format!("{path}::{name}") };
+            let diagnostic = Diagnostic::error("merge-conflict", format!("Conflicting definitions of `{name}`."))
+                .with_symbol(symbol);
+            Err(diagnostic.into())
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Attribute, Named, Parameter, Type};
+
+    fn function(output: Type, documentation: &str) -> Function {
+        Function {
+            identifier: "area".into(),
+            inputs: vec![Parameter::new("scale", Type::f32())],
+            output: Some(output),
+            attributes: Attribute::from(Named::new("doc", documentation)).into(),
+            ..Default::default()
+        }
+    }
+
+    fn interface(methods: Vec<Method>, functions: Vec<Function>) -> Interface {
+        Interface { identifier: "Shape".into(), methods, functions, ..Default::default() }
+    }
+
+    fn module(functions: Vec<Function>, interfaces: Vec<Interface>) -> Module {
+        Module { identifier: "shapes".into(), functions, interfaces, ..Default::default() }
+    }
+
+    #[test]
+    fn deduplicates() -> Result<()> {
+        let mut left = module(vec![function(Type::f32(), "Area.")], vec![]);
+        let right = module(vec![function(Type::f32(), "Area in square meters.")], vec![]);
+        left.merge(right, MergePolicy::Error)?;
+        assert_eq!(left.functions.len(), 1);
+        assert_eq!(left.functions[0].attributes.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn folds_interfaces() -> Result<()> {
+        let new = Function { identifier: "new".into(), ..Default::default() };
+        let area = Method { identifier: "area".into(), ..Default::default() };
+        let mut left = module(vec![], vec![interface(vec![], vec![new]), interface(vec![area.clone()], vec![])]);
+        left.merge(module(vec![], vec![interface(vec![area], vec![])]), MergePolicy::Error)?;
+        assert_eq!(left.interfaces.len(), 1);
+        assert_eq!(left.interfaces[0].functions.len(), 1);
+        assert_eq!(left.interfaces[0].methods.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn policies() -> Result<()> {
+        let left = module(vec![function(Type::f32(), "Area.")], vec![]);
+        let right = module(vec![function(Type::f64(), "Area.")], vec![]);
+
+        let mut merged = left.clone();
+        merged.merge(right.clone(), MergePolicy::PreferLeft)?;
+        assert_eq!(merged.functions[0].output, Some(Type::f32()));
+
+        let mut merged = left.clone();
+        merged.merge(right.clone(), MergePolicy::PreferRight)?;
+        assert_eq!(merged.functions[0].output, Some(Type::f64()));
+
+        let mut merged = left;
+        let Err(Error::Diagnostic(diagnostic)) = merged.merge(right, MergePolicy::Error) else {
+            panic!("Expected a merge conflict.");
+        };
+        assert_eq!(diagnostic.code, "merge-conflict");
+        assert_eq!(diagnostic.symbol.as_deref(), Some("shapes::area"));
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ pub use crate::*;
 
 pub fn constant() -> Object {
     Object {
+        attributes: Default::default(),
         visibility: Visibility::Private,
         mutability: Mutability::Constant,
         identifier: "CONSTANT".into(),
@@ -9,3 +10,14 @@ pub fn constant() -> Object {
         literal: false.into(),
     }
 }
+
+pub fn static_() -> Object {
+    Object {
+        attributes: Default::default(),
+        visibility: Visibility::Public,
+        mutability: Mutability::Mutable,
+        identifier: "COUNTER".into(),
+        type_: Type::u32(),
+        literal: 0.into(),
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{prelude::*, Visibility};
-use crate::{Identifier, Literal, Mutability, Type};
+use crate::{Attributes, Identifier, Literal, Mutability, Type};
 
 #[cfg(any(test, feature = "mocks"))]
 pub mod mock;
@@ -7,6 +7,9 @@ pub mod mock;
 #[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
 /// Object struct
 pub struct Object {
+    /// Object's attributes. Libraries saved before they were modeled have none.
+    #[serde(default)]
+    pub attributes: Attributes,
     /// Object's visibility.
     pub visibility: Visibility,
     /// Object's mutability.
@@ -0,0 +1,557 @@
+//! Query language over the IDL.
+//!
+//! A query selects items by kind, optionally filtered by a path glob and a condition:
+//!
+//! ```text
+//! function lib::** where public and async and returns Option<_>
+//! structure where field Address
+//! type where implements Clone and not attribute deprecated
+//! ```
+//!
+//! Kinds are `module`, `import`, `object`, `type`, `structure`, `enumeration`, `alias`, `union`,
+//! `field`, `variant`, `function`, `method`, `parameter`, `interface` and `*` for any of them.
+//! Conditions combine predicates with `and`, `or`, `not` and parentheses. The predicates are:
+//!
+//! - `public`, `private`: the item's visibility.
+//! - `async`, `sync`: the function's or method's synchrony.
+//! - `name <glob>`: the item's identifier.
+//! - `attribute <glob>`: the path of one of the item's attributes.
+//! - `implements <glob>`: one of the interfaces of a type or interface.
+//! - `returns <type>`: the output of a function or method. No output is `void`.
+//! - `field <type>`: the type of one of the fields of a structure or union.
+//! - `parameter <type>`: the type of one of the parameters of a function or method.
+//! - `type <type>`: the type of an object, field, parameter or type alias.
+//!
+//! See `Glob` and `TypePattern` for the pattern syntax.
+
+pub mod pattern;
+mod parser;
+
+pub use pattern::*;
+
+use std::fmt::{Display, Formatter};
+
+use is_tree::{HasPath, TreeIterator};
+
+use crate::prelude::*;
+use crate::{
+    Attribute, Attributes, Field, Function, Identifier, Import, Interface, KindDefinition,
+    Library, Method, Module, Object, Parameter, Path, Registry, Structure, Synchrony, Type,
+    TypeDefinition, Union, Variant, Visibility, Visitors,
+};
+
+/// The kinds of items a query selects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum Kind {
+    /// Any item.
+    Item,
+    Module,
+    Import,
+    Object,
+    /// Any type definition.
+    Type,
+    Structure,
+    Enumeration,
+    TypeAlias,
+    Union,
+    Field,
+    Variant,
+    /// Functions of modules and interfaces.
+    Function,
+    Method,
+    Parameter,
+    Interface,
+}
+
+/// A query condition.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub enum Condition {
+    Predicate(Predicate),
+    Not(Box<Condition>),
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+}
+
+/// A query predicate. See the module documentation for their meaning.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub enum Predicate {
+    Public,
+    Private,
+    Asynchronous,
+    Synchronous,
+    Name(Glob),
+    Attribute(Glob),
+    Implements(Glob),
+    Returns(TypePattern),
+    Field(TypePattern),
+    Parameter(TypePattern),
+    Type(TypePattern),
+}
+
+/// A parsed query. It's serialized as its source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Query {
+    /// The kind of items to select.
+    pub kind: Kind,
+    /// The glob the item paths must match.
+    pub path: Option<Glob>,
+    /// The condition the items must satisfy.
+    pub condition: Option<Condition>,
+}
+
+/// An item matched by a query.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase", tag = "kind", content = "item")]
+#[allow(missing_docs)]
+pub enum Item {
+    Module(Module),
+    Import(Import),
+    Object(Object),
+    Type(TypeDefinition),
+    Field(Field),
+    Variant(Variant),
+    Function(Function),
+    Method(Method),
+    Parameter(Parameter),
+    Interface(Interface),
+}
+
+/// A query match.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Match {
+    /// The item's path, starting with the library identifier.
+    pub path: Path,
+    /// The item.
+    #[serde(flatten)]
+    pub item: Item,
+}
+
+#[derive(Clone, Copy)]
+enum ItemRef<'a> {
+    Module(&'a Module),
+    Import(&'a Import),
+    Object(&'a Object),
+    Type(&'a TypeDefinition),
+    Field(&'a Field),
+    Variant(&'a Variant),
+    Function(&'a Function),
+    Method(&'a Method),
+    Parameter(&'a Parameter),
+    Interface(&'a Interface),
+}
+
+impl Query {
+    /// Parses a query.
+    pub fn parse(source: impl AsRef<str>) -> Result<Self> {
+        parser::parse(source.as_ref())
+    }
+
+    /// Finds the items of `library` matching the query.
+    pub fn find(&self, library: &Library) -> Vec<Match> {
+        let mut matches = Vec::new();
+        for visitor in TreeIterator::<Visitors>::new(library) {
+            if let Some(visitor) = visitor.as_module() {
+                let path = Path::from(visitor.path());
+                self.module(&path, visitor.value, &mut matches);
+            }
+        }
+        matches
+    }
+
+    /// Finds the items of all the libraries of `registry` matching the query.
+    pub fn find_in_registry(&self, registry: &Registry) -> Vec<Match> {
+        let mut libraries = registry.libraries.values().collect::<Vec<_>>();
+        libraries.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+        libraries.into_iter().flat_map(|library| self.find(library)).collect()
+    }
+
+    /// Submodules are visited by `find`, so they aren't recursed into.
+    fn module(&self, path: &Path, module: &Module, matches: &mut Vec<Match>) {
+        self.candidate(path.clone(), ItemRef::Module(module), matches);
+        for import in &module.imports {
+            let identifier = import.renaming.clone().unwrap_or_else(|| import.path.last().identifier.clone());
+            self.candidate(path.clone().join(identifier), ItemRef::Import(import), matches);
+        }
+        self.objects(path, &module.objects, matches);
+        self.types(path, &module.types, matches);
+        self.functions(path, &module.functions, matches);
+        for interface in &module.interfaces {
+            let path = path.clone().join(interface.identifier.clone());
+            self.candidate(path.clone(), ItemRef::Interface(interface), matches);
+            self.objects(&path, &interface.objects, matches);
+            self.types(&path, &interface.types, matches);
+            self.functions(&path, &interface.functions, matches);
+            for method in &interface.methods {
+                let path = path.clone().join(method.identifier.clone());
+                self.candidate(path.clone(), ItemRef::Method(method), matches);
+                self.parameters(&path, &method.inputs, matches);
+            }
+        }
+    }
+
+    fn objects(&self, path: &Path, objects: &[Object], matches: &mut Vec<Match>) {
+        for object in objects {
+            self.candidate(path.clone().join(object.identifier.clone()), ItemRef::Object(object), matches);
+        }
+    }
+
+    fn types(&self, path: &Path, types: &[TypeDefinition], matches: &mut Vec<Match>) {
+        for definition in types {
+            let path = path.clone().join(definition.identifier.clone());
+            self.candidate(path.clone(), ItemRef::Type(definition), matches);
+            match &definition.definition {
+                KindDefinition::Structure(Structure { fields }) | KindDefinition::Union(Union { fields }) => {
+                    for (index, field) in fields.iter().enumerate() {
+                        let identifier = field.identifier.clone().unwrap_or_else(|| index.to_string().into());
+                        self.candidate(path.clone().join(identifier), ItemRef::Field(field), matches);
+                    }
+                },
+                KindDefinition::Enumeration(enumeration) => {
+                    for variant in &enumeration.variants {
+                        self.candidate(path.clone().join(variant.identifier.clone()), ItemRef::Variant(variant), matches);
+                    }
+                },
+                KindDefinition::TypeAlias(_) => (),
+            }
+        }
+    }
+
+    fn functions(&self, path: &Path, functions: &[Function], matches: &mut Vec<Match>) {
+        for function in functions {
+            let path = path.clone().join(function.identifier.clone());
+            self.candidate(path.clone(), ItemRef::Function(function), matches);
+            self.parameters(&path, &function.inputs, matches);
+        }
+    }
+
+    fn parameters(&self, path: &Path, parameters: &[Parameter], matches: &mut Vec<Match>) {
+        for parameter in parameters {
+            self.candidate(path.clone().join(parameter.identifier.clone()), ItemRef::Parameter(parameter), matches);
+        }
+    }
+
+    fn candidate(&self, path: Path, item: ItemRef, matches: &mut Vec<Match>) {
+        let selected = self.kind.selects(item)
+            && self.path.as_ref().is_none_or(|glob| glob.matches_path(&path))
+            && self.condition.as_ref().is_none_or(|condition| condition.evaluate(item));
+        if selected {
+            matches.push(Match { path, item: item.into() });
+        }
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(path) = &self.path {
+            write!(f, " {path}")?;
+        }
+        if let Some(condition) = &self.condition {
+            write!(f, " where {condition}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Query> for String {
+    fn from(query: Query) -> Self {
+        query.to_string()
+    }
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Item => "*",
+            Self::Module => "module",
+            Self::Import => "import",
+            Self::Object => "object",
+            Self::Type => "type",
+            Self::Structure => "structure",
+            Self::Enumeration => "enumeration",
+            Self::TypeAlias => "alias",
+            Self::Union => "union",
+            Self::Field => "field",
+            Self::Variant => "variant",
+            Self::Function => "function",
+            Self::Method => "method",
+            Self::Parameter => "parameter",
+            Self::Interface => "interface",
+        };
+        f.write_str(name)
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Nested `and`s and `or`s are parenthesized, so the output parses back to the same condition.
+        let operand = |condition: &Condition| match condition {
+            Self::And(_) | Self::Or(_) => format!("({condition})"),
+            _ => condition.to_string(),
+        };
+        match self {
+            Self::Predicate(predicate) => write!(f, "{predicate}"),
+            Self::Not(condition) => write!(f, "not {}", operand(condition.as_ref())),
+            Self::And(conditions) => f.write_str(&conditions.iter().map(operand).collect::<Vec<_>>().join(" and ")),
+            Self::Or(conditions) => f.write_str(&conditions.iter().map(operand).collect::<Vec<_>>().join(" or ")),
+        }
+    }
+}
+
+impl Display for Predicate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Public => f.write_str("public"),
+            Self::Private => f.write_str("private"),
+            Self::Asynchronous => f.write_str("async"),
+            Self::Synchronous => f.write_str("sync"),
+            Self::Name(glob) => write!(f, "name {glob}"),
+            Self::Attribute(glob) => write!(f, "attribute {glob}"),
+            Self::Implements(glob) => write!(f, "implements {glob}"),
+            Self::Returns(pattern) => write!(f, "returns {pattern}"),
+            Self::Field(pattern) => write!(f, "field {pattern}"),
+            Self::Parameter(pattern) => write!(f, "parameter {pattern}"),
+            Self::Type(pattern) => write!(f, "type {pattern}"),
+        }
+    }
+}
+
+impl Kind {
+    fn selects(&self, item: ItemRef) -> bool {
+        match (self, item) {
+            (Self::Item, _) => true,
+            (Self::Module, ItemRef::Module(_))
+            | (Self::Import, ItemRef::Import(_))
+            | (Self::Object, ItemRef::Object(_))
+            | (Self::Type, ItemRef::Type(_))
+            | (Self::Field, ItemRef::Field(_))
+            | (Self::Variant, ItemRef::Variant(_))
+            | (Self::Function, ItemRef::Function(_))
+            | (Self::Method, ItemRef::Method(_))
+            | (Self::Parameter, ItemRef::Parameter(_))
+            | (Self::Interface, ItemRef::Interface(_)) => true,
+            (Self::Structure, ItemRef::Type(definition)) => matches!(definition.definition, KindDefinition::Structure(_)),
+            (Self::Enumeration, ItemRef::Type(definition)) => matches!(definition.definition, KindDefinition::Enumeration(_)),
+            (Self::TypeAlias, ItemRef::Type(definition)) => matches!(definition.definition, KindDefinition::TypeAlias(_)),
+            (Self::Union, ItemRef::Type(definition)) => matches!(definition.definition, KindDefinition::Union(_)),
+            _ => false,
+        }
+    }
+}
+
+impl Condition {
+    fn evaluate(&self, item: ItemRef) -> bool {
+        match self {
+            Self::Predicate(predicate) => predicate.evaluate(item),
+            Self::Not(condition) => !condition.evaluate(item),
+            Self::And(conditions) => conditions.iter().all(|condition| condition.evaluate(item)),
+            Self::Or(conditions) => conditions.iter().any(|condition| condition.evaluate(item)),
+        }
+    }
+}
+
+impl Predicate {
+    fn evaluate(&self, item: ItemRef) -> bool {
+        match self {
+            Self::Public => item.visibility() == Visibility::Public,
+            Self::Private => item.visibility() == Visibility::Private,
+            Self::Asynchronous => item.synchrony() == Some(Synchrony::Asynchronous),
+            Self::Synchronous => item.synchrony() == Some(Synchrony::Synchronous),
+            Self::Name(glob) => item.identifier().is_some_and(|identifier| glob.matches(&identifier.name)),
+            Self::Attribute(glob) => item.attributes().is_some_and(|attributes| {
+                attributes.iter().any(|attribute| match attribute {
+                    Attribute::Group(group) => glob.matches_path(&group.path),
+                    Attribute::Named(named) => glob.matches_path(&named.path),
+                    Attribute::Literal(_) => false,
+                })
+            }),
+            Self::Implements(glob) => {
+                let interfaces = match item {
+                    ItemRef::Type(definition) => &definition.interfaces,
+                    ItemRef::Interface(interface) => &interface.interfaces,
+                    _ => return false,
+                };
+                interfaces.iter().any(|interface| glob.matches_path(interface) || glob.matches(&interface.last().identifier.name))
+            },
+            Self::Returns(pattern) => {
+                let output = match item {
+                    ItemRef::Function(function) => &function.output,
+                    ItemRef::Method(method) => &method.output,
+                    _ => return false,
+                };
+                pattern.matches(output.as_ref().unwrap_or(&Type::void()))
+            },
+            Self::Field(pattern) => match item {
+                ItemRef::Type(TypeDefinition { definition: KindDefinition::Structure(Structure { fields }) | KindDefinition::Union(Union { fields }), .. }) => {
+                    fields.iter().any(|field| pattern.matches(&field.type_))
+                },
+                _ => false,
+            },
+            Self::Parameter(pattern) => {
+                let inputs = match item {
+                    ItemRef::Function(function) => &function.inputs,
+                    ItemRef::Method(method) => &method.inputs,
+                    _ => return false,
+                };
+                inputs.iter().any(|parameter| pattern.matches(&parameter.type_))
+            },
+            Self::Type(pattern) => match item {
+                ItemRef::Object(object) => pattern.matches(&object.type_),
+                ItemRef::Field(field) => pattern.matches(&field.type_),
+                ItemRef::Parameter(parameter) => pattern.matches(&parameter.type_),
+                ItemRef::Type(TypeDefinition { definition: KindDefinition::TypeAlias(alias), .. }) => pattern.matches(&alias.type_),
+                _ => false,
+            },
+        }
+    }
+}
+
+impl<'a> ItemRef<'a> {
+    /// Variants and parameters are as visible as their parent.
+    fn visibility(&self) -> Visibility {
+        match self {
+            Self::Module(module) => module.visibility,
+            Self::Import(import) => import.visibility,
+            Self::Object(object) => object.visibility,
+            Self::Type(definition) => definition.visibility,
+            Self::Field(field) => field.visibility,
+            Self::Function(function) => function.visibility,
+            Self::Method(method) => method.visibility,
+            Self::Interface(interface) => interface.visibility,
+            Self::Variant(_) | Self::Parameter(_) => Visibility::Public,
+        }
+    }
+
+    fn synchrony(&self) -> Option<Synchrony> {
+        match self {
+            Self::Function(function) => Some(function.synchrony),
+            Self::Method(method) => Some(method.synchrony),
+            _ => None,
+        }
+    }
+
+    fn identifier(&self) -> Option<&'a Identifier> {
+        match self {
+            Self::Module(module) => Some(&module.identifier),
+            Self::Import(import) => import.renaming.as_ref().or(import.path.segments.last().map(|segment| &segment.identifier)),
+            Self::Object(object) => Some(&object.identifier),
+            Self::Type(definition) => Some(&definition.identifier),
+            Self::Field(field) => field.identifier.as_ref(),
+            Self::Variant(variant) => Some(&variant.identifier),
+            Self::Function(function) => Some(&function.identifier),
+            Self::Method(method) => Some(&method.identifier),
+            Self::Parameter(parameter) => Some(&parameter.identifier),
+            Self::Interface(interface) => Some(&interface.identifier),
+        }
+    }
+
+    fn attributes(&self) -> Option<&'a Attributes> {
+        match self {
+            Self::Module(module) => Some(&module.attributes),
+            Self::Import(import) => Some(&import.attributes),
+            Self::Object(object) => Some(&object.attributes),
+            Self::Type(definition) => Some(&definition.attributes),
+            Self::Field(field) => Some(&field.attributes),
+            Self::Variant(variant) => Some(&variant.attributes),
+            Self::Function(function) => Some(&function.attributes),
+            Self::Method(method) => Some(&method.attributes),
+            Self::Parameter(parameter) => Some(&parameter.attributes),
+            Self::Interface(interface) => Some(&interface.attributes),
+        }
+    }
+}
+
+impl From<ItemRef<'_>> for Item {
+    fn from(item: ItemRef<'_>) -> Self {
+        match item {
+            ItemRef::Module(module) => Self::Module(module.clone()),
+            ItemRef::Import(import) => Self::Import(import.clone()),
+            ItemRef::Object(object) => Self::Object(object.clone()),
+            ItemRef::Type(definition) => Self::Type(definition.clone()),
+            ItemRef::Field(field) => Self::Field(field.clone()),
+            ItemRef::Variant(variant) => Self::Variant(variant.clone()),
+            ItemRef::Function(function) => Self::Function(function.clone()),
+            ItemRef::Method(method) => Self::Method(method.clone()),
+            ItemRef::Parameter(parameter) => Self::Parameter(parameter.clone()),
+            ItemRef::Interface(interface) => Self::Interface(interface.clone()),
+        }
+    }
+}
+
+impl Item {
+    /// The name of the item's kind.
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            Self::Module(_) => "module",
+            Self::Import(_) => "import",
+            Self::Object(_) => "object",
+            Self::Type(_) => "type",
+            Self::Field(_) => "field",
+            Self::Variant(_) => "variant",
+            Self::Function(_) => "function",
+            Self::Method(_) => "method",
+            Self::Parameter(_) => "parameter",
+            Self::Interface(_) => "interface",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Named, Structure};
+
+    fn library() -> Library {
+        let address = TypeDefinition {
+            identifier: "Address".into(),
+            interfaces: vec!["std::clone::Clone".into()],
+            ..Default::default()
+        };
+        let user = TypeDefinition {
+            identifier: "User".into(),
+            attributes: Attribute::from(Named::new("serde::rename", "user")).into(),
+            definition: KindDefinition::Structure(Structure { fields: vec![Field { identifier: Some("address".into()), type_: Type::from("Address"), ..Default::default() }] }),
+            ..Default::default()
+        };
+        let fetch = Function {
+            identifier: "fetch".into(),
+            synchrony: Synchrony::Asynchronous,
+            inputs: vec![Parameter::new("id", Type::u64())],
+            output: Some(Type::option(Type::from("User"))),
+            ..Default::default()
+        };
+        let store = Function {
+            identifier: "store".into(),
+            visibility: Visibility::Private,
+            synchrony: Synchrony::Asynchronous,
+            output: Some(Type::option(Type::from("User"))),
+            ..Default::default()
+        };
+        let models = Module { identifier: "models".into(), types: vec![address, user], ..Default::default() };
+        let root_module = Module { identifier: "lib".into(), functions: vec![fetch, store], modules: vec![models], ..Default::default() };
+        Library { identifier: "users".into(), root_module, ..Default::default() }
+    }
+
+    fn paths(query: &str) -> Vec<String> {
+        Query::parse(query)
+            .unwrap()
+            .find(&library())
+            .into_iter()
+            .map(|found| found.path.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn queries() {
+        assert_eq!(paths("function where public and async and returns Option<_>"), ["users::lib::fetch"]);
+        assert_eq!(paths("structure where field Address"), ["users::lib::models::User"]);
+        assert_eq!(paths("type users::lib::models::* where implements Clone"), ["users::lib::models::Address"]);
+        assert_eq!(paths("* where attribute serde::*"), ["users::lib::models::User"]);
+        assert_eq!(paths("parameter where type u64"), ["users::lib::fetch::id"]);
+        assert_eq!(paths("function where not (public or name fetch)"), ["users::lib::store"]);
+    }
+}
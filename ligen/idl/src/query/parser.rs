@@ -0,0 +1,294 @@
+//! Query parser.
+
+use crate::prelude::*;
+use crate::Identifier;
+
+use super::{Condition, Glob, Kind, Predicate, Query, TypePattern};
+
+const PUNCTUATION: [char; 6] = ['(', ')', '<', '>', ',', '&'];
+
+struct Token<'a> {
+    text: &'a str,
+    start: usize,
+}
+
+pub(crate) fn parse(source: &str) -> Result<Query> {
+    let mut parser = Parser::new(source);
+    let query = parser.query()?;
+    parser.end()?;
+    Ok(query)
+}
+
+pub(crate) fn parse_type_pattern(source: &str) -> Result<TypePattern> {
+    let mut parser = Parser::new(source);
+    let pattern = parser.type_pattern()?;
+    parser.end()?;
+    Ok(pattern)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token<'a>>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        let mut tokens = Vec::new();
+        let mut characters = source.char_indices().peekable();
+        while let Some((start, character)) = characters.next() {
+            if character.is_whitespace() {
+                continue;
+            }
+            let mut end = start + character.len_utf8();
+            if !PUNCTUATION.contains(&character) {
+                while let Some((index, character)) = characters.peek().copied() {
+                    if character.is_whitespace() || PUNCTUATION.contains(&character) {
+                        break;
+                    }
+                    end = index + character.len_utf8();
+                    characters.next();
+                }
+            }
+            tokens.push(Token { text: &source[start..end], start });
+        }
+        Self { source, tokens, position: 0 }
+    }
+
+    fn end(&self) -> Result<()> {
+        match self.peek() {
+            Some(token) => Err(self.error(token.start, format!("Unexpected `{}`.", token.text))),
+            None => Ok(()),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.position)
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        let found = self.peek().is_some_and(|token| token.text == text);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn error(&self, start: usize, message: impl Into<String>) -> Error {
+        let end = self.source[start..]
+            .char_indices()
+            .nth(1)
+            .map(|(index, _)| start + index)
+            .unwrap_or(self.source.len());
+        Diagnostic::error("query-syntax", message)
+            .with_span(Span::from_offsets(self.source, start, end))
+            .into()
+    }
+
+    fn end_error(&self, expected: &str) -> Error {
+        match self.peek() {
+            Some(token) => self.error(token.start, format!("Expected {expected}, found `{}`.", token.text)),
+            None => self.error(self.source.len(), format!("Expected {expected}.")),
+        }
+    }
+
+    fn word(&mut self, expected: &str) -> Result<&'a str> {
+        match self.peek() {
+            Some(token) if !token.text.starts_with(PUNCTUATION) => {
+                let text = token.text;
+                self.position += 1;
+                Ok(text)
+            },
+            _ => Err(self.end_error(expected)),
+        }
+    }
+
+    fn query(&mut self) -> Result<Query> {
+        let start = self.peek().map(|token| token.start).unwrap_or_default();
+        let kind = match self.word("an item kind")? {
+            "*" | "item" => Kind::Item,
+            "module" => Kind::Module,
+            "import" => Kind::Import,
+            "object" => Kind::Object,
+            "type" => Kind::Type,
+            "structure" => Kind::Structure,
+            "enumeration" => Kind::Enumeration,
+            "alias" => Kind::TypeAlias,
+            "union" => Kind::Union,
+            "field" => Kind::Field,
+            "variant" => Kind::Variant,
+            "function" => Kind::Function,
+            "method" => Kind::Method,
+            "parameter" => Kind::Parameter,
+            "interface" => Kind::Interface,
+            kind => return Err(self.error(start, format!("Unknown item kind `{kind}`."))),
+        };
+        let path = match self.peek() {
+            Some(token) if token.text != "where" => Some(Glob(self.word("a path")?.to_string())),
+            _ => None,
+        };
+        let condition = if self.eat("where") {
+            Some(self.or()?)
+        } else {
+            None
+        };
+        Ok(Query { kind, path, condition })
+    }
+
+    fn or(&mut self) -> Result<Condition> {
+        let mut conditions = vec![self.and()?];
+        while self.eat("or") {
+            conditions.push(self.and()?);
+        }
+        Ok(if conditions.len() == 1 { conditions.remove(0) } else { Condition::Or(conditions) })
+    }
+
+    fn and(&mut self) -> Result<Condition> {
+        let mut conditions = vec![self.not()?];
+        while self.eat("and") {
+            conditions.push(self.not()?);
+        }
+        Ok(if conditions.len() == 1 { conditions.remove(0) } else { Condition::And(conditions) })
+    }
+
+    fn not(&mut self) -> Result<Condition> {
+        if self.eat("not") {
+            Ok(Condition::Not(Box::new(self.not()?)))
+        } else if self.eat("(") {
+            let condition = self.or()?;
+            if !self.eat(")") {
+                return Err(self.end_error("`)`"));
+            }
+            Ok(condition)
+        } else {
+            self.predicate().map(Condition::Predicate)
+        }
+    }
+
+    fn predicate(&mut self) -> Result<Predicate> {
+        let start = self.peek().map(|token| token.start).unwrap_or(self.source.len());
+        let predicate = match self.word("a predicate")? {
+            "public" => Predicate::Public,
+            "private" => Predicate::Private,
+            "async" => Predicate::Asynchronous,
+            "sync" => Predicate::Synchronous,
+            "name" => Predicate::Name(Glob(self.word("a name")?.to_string())),
+            "attribute" => Predicate::Attribute(Glob(self.word("an attribute path")?.to_string())),
+            "implements" => Predicate::Implements(Glob(self.word("an interface path")?.to_string())),
+            "returns" => Predicate::Returns(self.type_pattern()?),
+            "field" => Predicate::Field(self.type_pattern()?),
+            "parameter" => Predicate::Parameter(self.type_pattern()?),
+            "type" => Predicate::Type(self.type_pattern()?),
+            predicate => return Err(self.error(start, format!("Unknown predicate `{predicate}`."))),
+        };
+        Ok(predicate)
+    }
+
+    fn type_pattern(&mut self) -> Result<TypePattern> {
+        if self.eat("&") {
+            let identifier = if self.eat("mut") {
+                Identifier::mutable_reference()
+            } else {
+                Identifier::constant_reference()
+            };
+            return Ok(TypePattern::constructor(identifier, vec![self.type_pattern()?]));
+        }
+        let path = self.word("a type")?;
+        if path == "_" {
+            return Ok(TypePattern::Any);
+        }
+        let segments = path.split("::").map(|segment| Glob(segment.to_string())).collect();
+        let generics = if self.eat("<") {
+            let mut generics = Vec::new();
+            if !self.eat(">") {
+                loop {
+                    generics.push(self.type_pattern()?);
+                    if self.eat(">") {
+                        break;
+                    }
+                    if !self.eat(",") {
+                        return Err(self.end_error("`,` or `>`"));
+                    }
+                }
+            }
+            Some(generics)
+        } else {
+            None
+        };
+        Ok(TypePattern::Path(segments, generics))
+    }
+}
+
+impl std::str::FromStr for Query {
+    type Err = Error;
+    fn from_str(source: &str) -> Result<Self> {
+        parse(source)
+    }
+}
+
+impl TryFrom<&str> for Query {
+    type Error = Error;
+    fn try_from(source: &str) -> Result<Self> {
+        parse(source)
+    }
+}
+
+impl TryFrom<String> for Query {
+    type Error = Error;
+    fn try_from(source: String) -> Result<Self> {
+        parse(&source)
+    }
+}
+
+impl std::str::FromStr for TypePattern {
+    type Err = Error;
+    fn from_str(source: &str) -> Result<Self> {
+        parse_type_pattern(source)
+    }
+}
+
+impl TryFrom<String> for TypePattern {
+    type Error = Error;
+    fn try_from(source: String) -> Result<Self> {
+        parse_type_pattern(&source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries() -> Result<()> {
+        let query = parse("function lib::** where public and async and returns Option<_>")?;
+        assert_eq!(query.kind, Kind::Function);
+        assert_eq!(query.path, Some(Glob("lib::**".into())));
+        assert_eq!(query.condition, Some(Condition::And(vec![
+            Condition::Predicate(Predicate::Public),
+            Condition::Predicate(Predicate::Asynchronous),
+            Condition::Predicate(Predicate::Returns(TypePattern::Path(vec![Glob("Option".into())], Some(vec![TypePattern::Any])))),
+        ])));
+        let query = parse("structure where not (field Address or field &mut _)")?;
+        assert!(matches!(query.condition, Some(Condition::Not(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn display() -> Result<()> {
+        let source = "function lib::** where public and (async or not returns Option<&mut _>)";
+        let query = parse(source)?;
+        assert_eq!(query.to_string(), "function lib::** where public and (async or not returns Option<&mut _>)");
+        assert_eq!(parse(&query.to_string())?, query);
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        let Err(Error::Diagnostic(diagnostic)) = parse("function where returns Option<_") else {
+            panic!("Expected a syntax error.");
+        };
+        assert_eq!(diagnostic.span, Some(Span::new(1, 31, 1, 31)));
+        assert!(parse("function where flying").is_err());
+        assert!(parse("gadget").is_err());
+    }
+}
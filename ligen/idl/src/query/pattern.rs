@@ -0,0 +1,171 @@
+//! Glob and type patterns.
+
+use std::fmt::{Display, Formatter};
+
+use crate::prelude::*;
+use crate::{Identifier, Path, PathSegment, Type};
+
+/// A glob where `*` matches within a path segment, `**` matches across segments and `?` matches
+/// a single character.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Glob(pub String);
+
+impl Glob {
+    /// Returns `true` if `text` matches the glob.
+    pub fn matches(&self, text: &str) -> bool {
+        let pattern = self.0.chars().collect::<Vec<_>>();
+        let text = text.chars().collect::<Vec<_>>();
+        glob(&pattern, &text)
+    }
+
+    /// Returns `true` if `path` matches the glob.
+    pub fn matches_path(&self, path: &Path) -> bool {
+        self.matches(&path.to_string())
+    }
+}
+
+impl Display for Glob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+fn glob(pattern: &[char], text: &[char]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        ['*', '*', rest @ ..] => (0..=text.len()).any(|index| glob(rest, &text[index..])),
+        ['*', rest @ ..] => (0..=text.len())
+            .take_while(|index| *index == 0 || text[index - 1] != ':')
+            .any(|index| glob(rest, &text[index..])),
+        ['?', rest @ ..] => !text.is_empty() && glob(rest, &text[1..]),
+        [character, rest @ ..] => text.first() == Some(character) && glob(rest, &text[1..]),
+    }
+}
+
+/// A `Type` pattern, such as `Option<_>` or `&mut models::*`.
+///
+/// `_` matches any type. Path segments are globs and are matched against the end of the type's
+/// path, so `Address` matches `models::Address`. Generic arguments are only compared if the
+/// pattern has them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum TypePattern {
+    /// `_`.
+    Any,
+    /// A path with optional generic arguments for its last segment.
+    Path(Vec<Glob>, Option<Vec<TypePattern>>),
+}
+
+impl TypePattern {
+    /// Returns `true` if `type_` matches the pattern.
+    pub fn matches(&self, type_: &Type) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Path(segments, generics) => {
+                let path = &type_.path.segments;
+                if path.len() < segments.len() {
+                    return false;
+                }
+                let matches = segments
+                    .iter()
+                    .zip(&path[path.len() - segments.len()..])
+                    .all(|(glob, segment)| glob.matches(&segment.identifier.name));
+                matches && generics.as_ref().is_none_or(|generics| {
+                    let types = &type_.path.last().generics.types;
+                    types.len() == generics.len()
+                        && generics.iter().zip(types).all(|(pattern, type_)| pattern.matches(type_))
+                })
+            },
+        }
+    }
+
+    /// The `Type` the pattern describes, if it has no wildcards.
+    pub fn to_type(&self) -> Option<Type> {
+        match self {
+            Self::Any => None,
+            Self::Path(segments, generics) => {
+                if segments.iter().any(|segment| segment.0.contains(['*', '?'])) {
+                    return None;
+                }
+                let generics = generics
+                    .iter()
+                    .flatten()
+                    .map(TypePattern::to_type)
+                    .collect::<Option<Vec<_>>>()?;
+                let mut segments = segments
+                    .iter()
+                    .map(|segment| PathSegment::from(segment.0.as_str()))
+                    .collect::<Vec<_>>();
+                if let Some(last) = segments.last_mut() {
+                    last.generics = generics.into();
+                }
+                Some(Path { segments }.into())
+            },
+        }
+    }
+
+    /// A pattern matching `identifier`'s constructor, such as `Reference`, with `types`.
+    pub(crate) fn constructor(identifier: Identifier, types: Vec<TypePattern>) -> Self {
+        Self::Path(vec![Glob(identifier.name)], Some(types))
+    }
+}
+
+impl Display for TypePattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Any => f.write_str("_"),
+            Self::Path(segments, Some(generics)) if segments.len() == 1 && generics.len() == 1 && segments[0].0 == Identifier::constant_reference().name => {
+                write!(f, "&{}", generics[0])
+            },
+            Self::Path(segments, Some(generics)) if segments.len() == 1 && generics.len() == 1 && segments[0].0 == Identifier::mutable_reference().name => {
+                write!(f, "&mut {}", generics[0])
+            },
+            Self::Path(segments, generics) => {
+                let path = segments.iter().map(ToString::to_string).collect::<Vec<_>>().join("::");
+                f.write_str(&path)?;
+                if let Some(generics) = generics {
+                    let generics = generics.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+                    write!(f, "<{generics}>")?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl From<TypePattern> for String {
+    fn from(pattern: TypePattern) -> Self {
+        pattern.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn globs() {
+        assert!(Glob("lib::*::area".into()).matches("lib::shapes::area"));
+        assert!(!Glob("lib::*".into()).matches("lib::shapes::area"));
+        assert!(Glob("lib::**".into()).matches("lib::shapes::area"));
+        assert!(Glob("ar?a".into()).matches("area"));
+    }
+
+    #[test]
+    fn types() {
+        let option = TypePattern::Path(vec![Glob("Option".into())], Some(vec![TypePattern::Any]));
+        assert!(option.matches(&Type::option(Type::from("models::Address"))));
+        assert!(!option.matches(&Type::vector(Type::i32())));
+        let address = TypePattern::Path(vec![Glob("Address".into())], None);
+        assert!(address.matches(&Type::from("models::Address")));
+        assert!(!address.matches(&Type::option(Type::from("models::Address"))));
+    }
+
+    #[test]
+    fn to_type() {
+        let pattern = "Option<&mut models::Address>".parse::<TypePattern>().unwrap();
+        assert_eq!(pattern.to_type(), Some(Type::option(Type::mutable_reference(Type::from("models::Address")))));
+        assert_eq!("Option<_>".parse::<TypePattern>().unwrap().to_type(), None);
+    }
+}
@@ -0,0 +1,344 @@
+//! Dependency graph of the type definitions of a `Registry`.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::{Identifier, Library, Location, Module, Registry, Resolver, Type};
+
+/// A `TypeDefinition` using another one in its fields or aliased type, or in their generic
+/// arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Dependency {
+    /// The type using the other.
+    pub from: Location,
+    /// The type used.
+    pub to: Location,
+    /// Whether it's used through a reference, pointer or heap allocation, like `&T`, `Box<T>` or
+    /// `Vec<T>`. These only need the type to be declared, so they can break cycles.
+    pub indirect: bool,
+}
+
+/// A set of types depending on each other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle {
+    /// The types, in declaration order.
+    pub types: Vec<Location>,
+    /// The dependencies between them.
+    pub dependencies: Vec<Dependency>,
+}
+
+impl Cycle {
+    /// The dependencies a generator can break with forward declarations.
+    pub fn indirect(&self) -> impl Iterator<Item = &Dependency> {
+        self.dependencies.iter().filter(|dependency| dependency.indirect)
+    }
+
+    /// Whether the types contain each other by value, so some field must be boxed, because
+    /// forward declarations can't break the cycle.
+    pub fn needs_boxing(&self) -> bool {
+        let index = |location: &Location| self.types.iter().position(|type_| type_ == location);
+        let edges = self
+            .dependencies
+            .iter()
+            .filter(|dependency| !dependency.indirect)
+            .filter_map(|dependency| Some((index(&dependency.from)?, index(&dependency.to)?)))
+            .collect::<Vec<_>>();
+        components(self.types.len(), &edges)
+            .iter()
+            .any(|component| is_cyclic(component, &edges))
+    }
+}
+
+/// Dependencies between the `TypeDefinition`s of a `Registry`.
+///
+/// Module types are in parse order, but C headers, WGSL and TypeScript need types declared before
+/// they are used. `order` sorts them so, and `cycles` finds the ones that need forward
+/// declarations or boxing.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TypeGraph {
+    /// The types, in declaration order: libraries by identifier, then modules depth-first.
+    pub types: Vec<Location>,
+    /// The dependencies between them. Unresolved types and generic parameters aren't included.
+    pub dependencies: Vec<Dependency>,
+}
+
+impl TypeGraph {
+    /// Builds the graph of every type in `registry`.
+    pub fn new(registry: &Registry) -> Self {
+        let resolver = Resolver::new(registry);
+        let mut graph = Self::default();
+        let mut libraries = registry.libraries.values().collect::<Vec<_>>();
+        libraries.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+        for library in libraries {
+            graph.module(&resolver, &Location::root(library.identifier.clone()), &library.root_module);
+        }
+        graph
+    }
+
+    fn module(&mut self, resolver: &Resolver, location: &Location, module: &Module) {
+        for definition in &module.types {
+            let from = location.join(definition.identifier.clone());
+            let generics = definition
+                .generics
+                .types
+                .iter()
+                .map(|type_| type_.path.last().identifier.clone())
+                .collect::<Vec<_>>();
+            for type_ in definition.definition.types() {
+                self.type_(resolver, location, &from, type_, &generics, false);
+            }
+            self.types.push(from);
+        }
+        for child in &module.modules {
+            self.module(resolver, &location.join(child.identifier.clone()), child);
+        }
+    }
+
+    fn type_(&mut self, resolver: &Resolver, scope: &Location, from: &Location, type_: &Type, generics: &[Identifier], indirect: bool) {
+        let is_generic = matches!(type_.path.segments.as_slice(), [segment] if generics.contains(&segment.identifier));
+        let to = (!is_generic)
+            .then(|| resolver.resolve(scope, &type_.path))
+            .flatten()
+            .filter(|to| resolver.type_definition(to).is_some());
+        if let Some(to) = to {
+            let dependency = Dependency { from: from.clone(), to, indirect };
+            if !self.dependencies.contains(&dependency) {
+                self.dependencies.push(dependency);
+            }
+        }
+        let indirect = indirect || is_indirection(type_);
+        for segment in &type_.path.segments {
+            for argument in &segment.generics.types {
+                self.type_(resolver, scope, from, argument, generics, indirect);
+            }
+        }
+    }
+
+    /// The dependencies of the type at `location`.
+    pub fn dependencies_of<'a>(&'a self, location: &'a Location) -> impl Iterator<Item = &'a Dependency> {
+        self.dependencies.iter().filter(move |dependency| dependency.from == *location)
+    }
+
+    /// The types sorted so each comes after the types it contains by value.
+    ///
+    /// Types used through an indirection only need a forward declaration, so they can come
+    /// later. The order is stable: it only differs from the declaration order where it must. The
+    /// types of a cycle that `needs_boxing` are kept together, in declaration order.
+    pub fn order(&self) -> Vec<&Location> {
+        let edges = self.edges(|dependency| !dependency.indirect);
+        let components = components(self.types.len(), &edges);
+        let mut component_of = vec![0; self.types.len()];
+        for (index, component) in components.iter().enumerate() {
+            for &type_ in component {
+                component_of[type_] = index;
+            }
+        }
+        let mut dependents = vec![Vec::new(); components.len()];
+        let mut pending = vec![0; components.len()];
+        for &(from, to) in &edges {
+            let (from, to) = (component_of[from], component_of[to]);
+            if from != to {
+                dependents[to].push(from);
+                pending[from] += 1;
+            }
+        }
+        // Components are picked by their first type, so the declaration order is kept when possible.
+        let first = |component: usize| components[component].iter().min().copied().unwrap_or_default();
+        let mut ready = (0..components.len())
+            .filter(|&component| pending[component] == 0)
+            .map(|component| Reverse((first(component), component)))
+            .collect::<BinaryHeap<_>>();
+        let mut order = Vec::new();
+        while let Some(Reverse((_, component))) = ready.pop() {
+            let mut types = components[component].clone();
+            types.sort();
+            order.extend(types.into_iter().map(|type_| &self.types[type_]));
+            for &dependent in &dependents[component] {
+                pending[dependent] -= 1;
+                if pending[dependent] == 0 {
+                    ready.push(Reverse((first(dependent), dependent)));
+                }
+            }
+        }
+        order
+    }
+
+    /// The sets of types depending on each other, directly or through indirections.
+    pub fn cycles(&self) -> Vec<Cycle> {
+        let edges = self.edges(|_| true);
+        let mut cycles = components(self.types.len(), &edges)
+            .into_iter()
+            .filter(|component| is_cyclic(component, &edges))
+            .map(|mut component| {
+                component.sort();
+                let types = component.iter().map(|&type_| self.types[type_].clone()).collect::<Vec<_>>();
+                let dependencies = self
+                    .dependencies
+                    .iter()
+                    .filter(|dependency| types.contains(&dependency.from) && types.contains(&dependency.to))
+                    .cloned()
+                    .collect();
+                Cycle { types, dependencies }
+            })
+            .collect::<Vec<_>>();
+        cycles.sort_by_key(|cycle| self.types.iter().position(|type_| cycle.types.first() == Some(type_)));
+        cycles
+    }
+
+    fn edges(&self, filter: impl Fn(&Dependency) -> bool) -> Vec<(usize, usize)> {
+        let index = self
+            .types
+            .iter()
+            .enumerate()
+            .map(|(index, location)| (location, index))
+            .collect::<HashMap<_, _>>();
+        self.dependencies
+            .iter()
+            .filter(|dependency| filter(dependency))
+            .filter_map(|dependency| Some((*index.get(&dependency.from)?, *index.get(&dependency.to)?)))
+            .collect()
+    }
+}
+
+impl From<&Library> for TypeGraph {
+    fn from(library: &Library) -> Self {
+        let libraries = [(library.identifier.clone(), library.clone())].into();
+        Self::new(&Registry { libraries })
+    }
+}
+
+/// Whether `type_` holds its generic arguments through a reference, pointer or heap allocation.
+fn is_indirection(type_: &Type) -> bool {
+    let identifier = &type_.path.last().identifier;
+    type_.is_constant_reference()
+        || type_.is_mutable_reference()
+        || type_.is_vector()
+        || *identifier == Identifier::slice()
+        || *identifier == Identifier::dictionary()
+        || ["Box", "Rc", "Arc", "Weak", "Pointer", "MutablePointer"].contains(&identifier.name.as_str())
+}
+
+fn is_cyclic(component: &[usize], edges: &[(usize, usize)]) -> bool {
+    match component {
+        [type_] => edges.contains(&(*type_, *type_)),
+        _ => true,
+    }
+}
+
+/// Strongly connected components of the graph with `nodes` and `edges`, by Tarjan's algorithm.
+fn components(nodes: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    struct Tarjan<'a> {
+        successors: Vec<Vec<usize>>,
+        index: Vec<Option<usize>>,
+        lowlink: Vec<usize>,
+        stack: Vec<usize>,
+        on_stack: Vec<bool>,
+        next: usize,
+        components: &'a mut Vec<Vec<usize>>,
+    }
+
+    impl Tarjan<'_> {
+        fn visit(&mut self, node: usize) {
+            self.index[node] = Some(self.next);
+            self.lowlink[node] = self.next;
+            self.next += 1;
+            self.stack.push(node);
+            self.on_stack[node] = true;
+            for successor in self.successors[node].clone() {
+                match self.index[successor] {
+                    None => {
+                        self.visit(successor);
+                        self.lowlink[node] = self.lowlink[node].min(self.lowlink[successor]);
+                    },
+                    Some(index) if self.on_stack[successor] => self.lowlink[node] = self.lowlink[node].min(index),
+                    Some(_) => (),
+                }
+            }
+            if Some(self.lowlink[node]) == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    let mut successors = vec![Vec::new(); nodes];
+    for &(from, to) in edges {
+        successors[from].push(to);
+    }
+    let mut components = Vec::new();
+    let mut tarjan = Tarjan {
+        successors,
+        index: vec![None; nodes],
+        lowlink: vec![0; nodes],
+        stack: Vec::new(),
+        on_stack: vec![false; nodes],
+        next: 0,
+        components: &mut components,
+    };
+    for node in 0..nodes {
+        if tarjan.index[node].is_none() {
+            tarjan.visit(node);
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Field, KindDefinition, PathSegment, Structure, TypeDefinition};
+
+    fn structure(identifier: &str, fields: Vec<Type>) -> TypeDefinition {
+        let fields = fields.into_iter().map(|type_| Field { type_, ..Default::default() }).collect();
+        TypeDefinition {
+            identifier: identifier.into(),
+            definition: KindDefinition::Structure(Structure { fields }),
+            ..Default::default()
+        }
+    }
+
+    fn names(locations: &[&Location]) -> Vec<String> {
+        locations.iter().map(|location| location.to_string()).collect()
+    }
+
+    #[test]
+    fn order() {
+        let types = vec![
+            structure("Scene", vec![Type::vector(Type::from("Shape")), Type::from("Camera")]),
+            structure("Camera", vec![Type::from("Transform")]),
+            structure("Transform", vec![Type::f32()]),
+            structure("Shape", vec![Type::from("Transform")]),
+        ];
+        let library = Library { identifier: "scene".into(), root_module: Module { types, ..Default::default() }, ..Default::default() };
+        let graph = TypeGraph::from(&library);
+        assert_eq!(graph.dependencies_of(&Location::new("scene", "Scene")).filter(|dependency| dependency.indirect).count(), 1);
+        assert_eq!(names(&graph.order()), ["scene::Transform", "scene::Camera", "scene::Scene", "scene::Shape"]);
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn cycles() {
+        let types = vec![
+            structure("Node", vec![Type::option(Type::from(PathSegment::new("Box", Type::from("Node")))), Type::from("Tree")]),
+            structure("Tree", vec![Type::vector(Type::from("Node"))]),
+            structure("Even", vec![Type::option(Type::from("Odd"))]),
+            structure("Odd", vec![Type::from("Even")]),
+        ];
+        let library = Library { identifier: "nodes".into(), root_module: Module { types, ..Default::default() }, ..Default::default() };
+        let graph = TypeGraph::from(&library);
+        let cycles = graph.cycles();
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(cycles[0].types, [Location::new("nodes", "Node"), Location::new("nodes", "Tree")]);
+        assert!(!cycles[0].needs_boxing());
+        assert_eq!(cycles[0].indirect().count(), 2);
+        assert!(cycles[1].needs_boxing());
+        assert_eq!(names(&graph.order()), ["nodes::Tree", "nodes::Node", "nodes::Even", "nodes::Odd"]);
+    }
+}
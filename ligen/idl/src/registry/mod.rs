@@ -1,14 +1,23 @@
-use std::collections::HashMap;
+pub mod graph;
+pub mod resolver;
+pub use graph::*;
+pub use resolver::*;
+
+use std::collections::{BTreeMap, HashMap};
 
 use is_tree::IsTree;
 use schemars::JsonSchema;
+use serde::{Deserializer, Serializer};
 
-use crate::{prelude::*, Identifier, Library};
+use crate::{prelude::*, Document, Identifier, Library};
 
 #[allow(missing_docs)]
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, IsTree, JsonSchema)]
 #[tree(branches)]
 pub struct Registry {
+    /// Libraries by identifier. They're saved keyed by their names, since JSON keys must be strings.
+    #[serde(serialize_with = "serialize_libraries", deserialize_with = "deserialize_libraries")]
+    #[schemars(with = "HashMap<String, Library>")]
     pub libraries: HashMap<Identifier, Library>,
 }
 
@@ -16,4 +25,71 @@ impl Registry {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Save registry to file. The format is selected by the file extension.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        Document::save(self, path)
+    }
+
+    /// Load registry from file. The format is selected by the file extension.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        Document::load(path)
+    }
+}
+
+fn serialize_libraries<S: Serializer>(libraries: &HashMap<Identifier, Library>, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    // Sorted, so saving the same registry always writes the same document.
+    let libraries = libraries
+        .iter()
+        .map(|(identifier, library)| (identifier.name.as_str(), library))
+        .collect::<BTreeMap<_, _>>();
+    libraries.serialize(serializer)
+}
+
+fn deserialize_libraries<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<HashMap<Identifier, Library>, D::Error> {
+    let libraries = HashMap::<String, Library>::deserialize(deserializer)?;
+    Ok(libraries.into_iter().map(|(name, library)| (Identifier::from(name), library)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{Encoding, Format};
+
+    fn registry() -> Registry {
+        let library = |name: &str| Library { identifier: name.into(), ..Default::default() };
+        let libraries = ["core", "shapes"].map(|name| (Identifier::from(name), library(name)));
+        Registry { libraries: libraries.into_iter().collect() }
+    }
+
+    #[test]
+    fn string_keys() -> Result<()> {
+        let value = serde_json::to_value(registry())?;
+        assert_eq!(value["libraries"]["shapes"]["identifier"]["name"], "shapes");
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        let registry = registry();
+        for encoding in [Encoding::Json, Encoding::Cbor] {
+            for compressed in [false, true] {
+                let format = Format::new(encoding, compressed);
+                let bytes = Document::to_bytes(&registry, format)?;
+                assert_eq!(Document::<Registry>::from_bytes(&bytes, format)?, registry);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn save_and_load() -> Result<()> {
+        let registry = registry();
+        let path = std::env::temp_dir().join(format!("ligen-registry-{}.cbor.zst", std::process::id()));
+        registry.save(&path)?;
+        let loaded = Registry::load(&path);
+        std::fs::remove_file(&path)?;
+        assert_eq!(loaded?, registry);
+        Ok(())
+    }
 }
@@ -0,0 +1,457 @@
+//! Registry-wide symbol resolution.
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use crate::prelude::*;
+use crate::{Function, Identifier, Library, Method, Module, Path, Registry, Type, TypeDefinition, Visibility};
+
+/// Absolute location of an item in a `Registry`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Location {
+    /// Identifier of the library defining the item.
+    pub library: Identifier,
+    /// Path from the library's root module, which isn't included.
+    pub path: Path,
+}
+
+impl Location {
+    /// Creates a new `Location`.
+    pub fn new(library: impl Into<Identifier>, path: impl Into<Path>) -> Self {
+        let library = library.into();
+        let path = path.into();
+        Self { library, path }
+    }
+
+    /// Location of a library's root module.
+    pub fn root(library: impl Into<Identifier>) -> Self {
+        Self::new(library, Path::default())
+    }
+
+    /// Location of the item `identifier` inside this one.
+    pub fn join(&self, identifier: impl Into<Identifier>) -> Self {
+        Self::new(self.library.clone(), self.path.clone().join(identifier.into()))
+    }
+
+    /// Location of the parent module, if any.
+    pub fn parent(&self) -> Option<Self> {
+        (!self.path.is_empty()).then(|| Self::new(self.library.clone(), self.path.clone().without_last()))
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.library)
+        } else {
+            write!(f, "{}::{}", self.library, self.path)
+        }
+    }
+}
+
+/// A type reference found in the registry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    /// Item holding the reference.
+    pub symbol: Location,
+    /// Module the reference is resolved from.
+    pub scope: Location,
+    /// Path as written.
+    pub path: Path,
+    /// Location of the `TypeDefinition`, if it was resolved.
+    pub definition: Option<Location>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Module,
+    Type,
+    Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Unresolved {
+    /// The path starts in a library that isn't in the registry.
+    External(Identifier),
+    Missing,
+}
+
+/// Resolves paths to their definitions across all the libraries of a `Registry`.
+///
+/// Paths are resolved as Rust does: relative to the module they are written in, starting with
+/// `crate`, `self`, `super`, an item or import of that module, or the identifier of a library.
+/// Imports, glob imports and `pub use` chains are followed. Items of other libraries are only
+/// visible if they are public.
+pub struct Resolver<'a> {
+    registry: &'a Registry,
+}
+
+impl<'a> Resolver<'a> {
+    /// Creates a resolver for `registry`.
+    pub fn new(registry: &'a Registry) -> Self {
+        Self { registry }
+    }
+
+    /// Resolves `path`, written in the module at `scope`, to the location of its definition.
+    pub fn resolve(&self, scope: &Location, path: &Path) -> Option<Location> {
+        self.resolve_path(scope, path, &scope.library, &mut HashSet::new())
+            .ok()
+            .map(|(_, location)| location)
+    }
+
+    /// Resolves `type_`, written in the module at `scope`, to its `TypeDefinition`.
+    pub fn resolve_type(&self, scope: &Location, type_: &Type) -> Option<&'a TypeDefinition> {
+        match self.resolve_path(scope, &type_.path, &scope.library, &mut HashSet::new()) {
+            Ok((Kind::Type, location)) => self.type_definition(&location),
+            _ => None,
+        }
+    }
+
+    /// Gets the library with `identifier`. Rust paths spell `-` as `_`, so both are accepted.
+    pub fn library(&self, identifier: &Identifier) -> Option<&'a Library> {
+        self.registry.libraries.get(identifier).or_else(|| {
+            self.registry
+                .libraries
+                .values()
+                .find(|library| library.identifier.name.replace('-', "_") == identifier.name)
+        })
+    }
+
+    /// Gets the module at `location`.
+    pub fn module(&self, location: &Location) -> Option<&'a Module> {
+        let library = self.library(&location.library)?;
+        location.path.segments.iter().try_fold(&library.root_module, |module, segment| {
+            module.modules.iter().find(|module| module.identifier == segment.identifier)
+        })
+    }
+
+    /// Gets the `TypeDefinition` at `location`.
+    pub fn type_definition(&self, location: &Location) -> Option<&'a TypeDefinition> {
+        let identifier = &location.path.segments.last()?.identifier;
+        self.module(&location.parent()?)?
+            .types
+            .iter()
+            .find(|type_| type_.identifier == *identifier)
+    }
+
+    /// Resolves every type reference in the registry, reporting the unresolved ones to
+    /// `diagnostics`. Builtin types, generic parameters and `Self` aren't references.
+    pub fn resolve_all(&self, diagnostics: &Diagnostics) -> Vec<Reference> {
+        let mut libraries = self.registry.libraries.values().collect::<Vec<_>>();
+        libraries.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+        libraries
+            .into_iter()
+            .flat_map(|library| self.resolve_library(library, diagnostics))
+            .collect()
+    }
+
+    /// Resolves the type references of `library`, which can refer to the other libraries in the
+    /// registry, reporting the unresolved ones to `diagnostics`.
+    pub fn resolve_library(&self, library: &Library, diagnostics: &Diagnostics) -> Vec<Reference> {
+        let mut references = Vec::new();
+        self.module_references(&library.root_module, &Location::root(library.identifier.clone()), &mut references);
+        for reference in &references {
+            if reference.definition.is_some() {
+                continue;
+            }
+            let diagnostic = match self.resolve_path(&reference.scope, &reference.path, &reference.scope.library, &mut HashSet::new()) {
+                Err(Unresolved::External(library)) => Diagnostic::note(
+                    "external-reference",
+                    format!("`{}` refers to `{library}`, which isn't in the registry.", reference.path)
+                ),
+                Ok(_) => Diagnostic::warning(
+                    "unresolved-reference",
+                    format!("`{}` doesn't refer to a type.", reference.path)
+                ),
+                Err(Unresolved::Missing) => Diagnostic::warning(
+                    "unresolved-reference",
+                    format!("Couldn't resolve `{}`.", reference.path)
+                ),
+            };
+            diagnostics.report(diagnostic.with_symbol(reference.symbol.to_string()));
+        }
+        references
+    }
+
+    fn module_references(&self, module: &Module, scope: &Location, references: &mut Vec<Reference>) {
+        let mut add = |symbol: Location, type_: &Type, generics: &[Identifier]| {
+            for path in type_paths(type_, generics) {
+                let definition = match self.resolve_path(scope, &path, &scope.library, &mut HashSet::new()) {
+                    Ok((Kind::Type, location)) => Some(location),
+                    _ => None,
+                };
+                references.push(Reference { symbol: symbol.clone(), scope: scope.clone(), path, definition });
+            }
+        };
+        for object in &module.objects {
+            add(scope.join(object.identifier.clone()), &object.type_, &[]);
+        }
+        for function in &module.functions {
+            for type_ in function_types(function) {
+                add(scope.join(function.identifier.clone()), type_, &[]);
+            }
+        }
+        for definition in &module.types {
+            let symbol = scope.join(definition.identifier.clone());
+            let generics = definition
+                .generics
+                .types
+                .iter()
+                .map(|type_| type_.path.last().identifier.clone())
+                .collect::<Vec<_>>();
+            for type_ in definition.definition.types() {
+                add(symbol.clone(), type_, &generics);
+            }
+        }
+        for interface in &module.interfaces {
+            let symbol = scope.join(interface.identifier.clone());
+            for object in &interface.objects {
+                add(symbol.join(object.identifier.clone()), &object.type_, &[]);
+            }
+            for definition in &interface.types {
+                for type_ in definition.definition.types() {
+                    add(symbol.join(definition.identifier.clone()), type_, &[]);
+                }
+            }
+            for function in &interface.functions {
+                for type_ in function_types(function) {
+                    add(symbol.join(function.identifier.clone()), type_, &[]);
+                }
+            }
+            for method in &interface.methods {
+                for type_ in method_types(method) {
+                    add(symbol.join(method.identifier.clone()), type_, &[]);
+                }
+            }
+        }
+        for child in &module.modules {
+            self.module_references(child, &scope.join(child.identifier.clone()), references);
+        }
+    }
+
+    /// `origin` is the library the path was written in, used for visibility. `visited` holds the
+    /// imports being followed, to break import cycles.
+    fn resolve_path(&self, scope: &Location, path: &Path, origin: &Identifier, visited: &mut HashSet<(Location, Identifier)>) -> std::result::Result<(Kind, Location), Unresolved> {
+        let mut segments = path.segments.iter().map(|segment| &segment.identifier);
+        let first = segments.next().ok_or(Unresolved::Missing)?;
+        let mut current = match first.name.as_str() {
+            "crate" => (Kind::Module, Location::root(scope.library.clone())),
+            "self" => (Kind::Module, scope.clone()),
+            "super" => (Kind::Module, scope.parent().ok_or(Unresolved::Missing)?),
+            _ => match self.lookup(scope, first, origin, visited) {
+                Ok(item) => item,
+                Err(Unresolved::Missing) => {
+                    let library = self
+                        .library(first)
+                        .or_else(|| self.library(&scope.library).filter(|library| library.root_module.identifier == *first));
+                    match library {
+                        Some(library) => (Kind::Module, Location::root(library.identifier.clone())),
+                        None if path.segments.len() > 1 => return Err(Unresolved::External(first.clone())),
+                        None => return Err(Unresolved::Missing),
+                    }
+                },
+                Err(error) => return Err(error),
+            },
+        };
+        for segment in segments {
+            current = match current {
+                (Kind::Module, location) if segment.name == "super" => (Kind::Module, location.parent().ok_or(Unresolved::Missing)?),
+                (Kind::Module, location) => self.lookup(&location, segment, origin, visited)?,
+                _ => return Err(Unresolved::Missing),
+            };
+        }
+        Ok(current)
+    }
+
+    /// Looks `identifier` up among the items and imports of the module at `module`.
+    fn lookup(&self, module: &Location, identifier: &Identifier, origin: &Identifier, visited: &mut HashSet<(Location, Identifier)>) -> std::result::Result<(Kind, Location), Unresolved> {
+        let definition = self.module(module).ok_or(Unresolved::Missing)?;
+        let visible = |visibility: &Visibility| module.library == *origin || *visibility == Visibility::Public;
+        let location = module.join(identifier.clone());
+        if definition.types.iter().any(|type_| type_.identifier == *identifier && visible(&type_.visibility)) {
+            return Ok((Kind::Type, location));
+        }
+        if definition.modules.iter().any(|module| module.identifier == *identifier && visible(&module.visibility)) {
+            return Ok((Kind::Module, location));
+        }
+        if definition.functions.iter().any(|function| function.identifier == *identifier && visible(&function.visibility))
+            || definition.objects.iter().any(|object| object.identifier == *identifier && visible(&object.visibility)) {
+            return Ok((Kind::Value, location));
+        }
+        if !visited.insert((module.clone(), identifier.clone())) {
+            return Err(Unresolved::Missing);
+        }
+        let imports = definition
+            .imports
+            .iter()
+            .filter(|import| visible(&import.visibility) && !import.path.is_empty());
+        let (globs, imports): (Vec<_>, Vec<_>) = imports.partition(|import| import.path.last().identifier == "*");
+        let mut result = Err(Unresolved::Missing);
+        for import in imports {
+            let name = import.renaming.as_ref().unwrap_or(&import.path.last().identifier);
+            if name == identifier {
+                result = self.resolve_path(module, &import.path, &module.library, visited);
+                if result.is_ok() {
+                    return result;
+                }
+            }
+        }
+        for glob in globs {
+            let path = glob.path.clone().without_last();
+            if let Ok((Kind::Module, target)) = self.resolve_path(module, &path, &module.library, visited) {
+                if let Ok(item) = self.lookup(&target, identifier, origin, visited) {
+                    return Ok(item);
+                }
+            }
+        }
+        visited.remove(&(module.clone(), identifier.clone()));
+        result
+    }
+}
+
+fn function_types(function: &Function) -> impl Iterator<Item = &Type> {
+    function
+        .inputs
+        .iter()
+        .map(|parameter| &parameter.type_)
+        .chain(function.output.iter())
+}
+
+fn method_types(method: &Method) -> impl Iterator<Item = &Type> {
+    method
+        .inputs
+        .iter()
+        .map(|parameter| &parameter.type_)
+        .chain(method.output.iter())
+}
+
+/// The paths referenced by `type_`, including the ones in its generic arguments.
+fn type_paths(type_: &Type, generics: &[Identifier]) -> Vec<Path> {
+    let mut paths = Vec::new();
+    for segment in &type_.path.segments {
+        for type_ in &segment.generics.types {
+            paths.extend(type_paths(type_, generics));
+        }
+    }
+    let is_reference = match type_.path.segments.as_slice() {
+        [] => false,
+        [segment] => {
+            let identifier = &segment.identifier;
+            !is_builtin(identifier) && !generics.contains(identifier)
+        },
+        _ => true,
+    };
+    if is_reference {
+        paths.insert(0, type_.path.clone());
+    }
+    paths
+}
+
+fn is_builtin(identifier: &Identifier) -> bool {
+    let builtins = [
+        Identifier::mutable_reference(), Identifier::constant_reference(), Identifier::union(), Identifier::variadic(),
+        Identifier::tuple(), Identifier::dictionary(), Identifier::slice(), Identifier::array(),
+        Identifier::vector(), Identifier::date_time(), Identifier::option(), Identifier::opaque(),
+        Identifier::boolean(), Identifier::character(), Identifier::i8(), Identifier::i16(),
+        Identifier::i32(), Identifier::i64(), Identifier::i128(), Identifier::isize(),
+        Identifier::u8(), Identifier::u16(), Identifier::u32(), Identifier::u64(),
+        Identifier::u128(), Identifier::usize(), Identifier::f16(), Identifier::f32(),
+        Identifier::f64(), Identifier::f128(), Identifier::string(), Identifier::void(),
+        Identifier::infer(), Identifier::function(), "Self".into(),
+    ];
+    builtins.contains(identifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Field, Import, KindDefinition, Structure};
+
+    fn structure(identifier: &str, visibility: Visibility, fields: Vec<Type>) -> TypeDefinition {
+        let fields = fields
+            .into_iter()
+            .map(|type_| Field { type_, ..Default::default() })
+            .collect();
+        TypeDefinition {
+            identifier: identifier.into(),
+            visibility,
+            definition: KindDefinition::Structure(Structure { fields }),
+            ..Default::default()
+        }
+    }
+
+    fn import(path: &str, visibility: Visibility) -> Import {
+        Import { path: path.into(), visibility, ..Default::default() }
+    }
+
+    fn registry() -> Registry {
+        let shapes = Library {
+            identifier: "shapes-core".into(),
+            root_module: Module {
+                identifier: "lib".into(),
+                imports: vec![import("geometry::Point", Visibility::Public), import("geometry::Hidden", Visibility::Private)],
+                modules: vec![Module {
+                    identifier: "geometry".into(),
+                    types: vec![
+                        structure("Point", Visibility::Public, vec![Type::f32()]),
+                        structure("Hidden", Visibility::Private, vec![]),
+                    ],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let drawing = Library {
+            identifier: "drawing".into(),
+            root_module: Module {
+                identifier: "lib".into(),
+                imports: vec![
+                    import("shapes_core::*", Visibility::Private),
+                    import("std::collections::HashMap", Visibility::Private),
+                    import("self::Cycle", Visibility::Private),
+                ],
+                types: vec![structure("Line", Visibility::Public, vec![
+                    Type::from("Point"),
+                    Type::option(Type::from("shapes_core::geometry::Point")),
+                    Type::from("HashMap"),
+                    Type::from("Missing"),
+                    Type::from("shapes_core::Hidden"),
+                    Type::from("Cycle"),
+                ])],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut registry = Registry::new();
+        registry.libraries.insert(shapes.identifier.clone(), shapes);
+        registry.libraries.insert(drawing.identifier.clone(), drawing);
+        registry
+    }
+
+    #[test]
+    fn re_exports() {
+        let registry = registry();
+        let resolver = Resolver::new(&registry);
+        let point = Location::new("shapes-core", "geometry::Point");
+        assert_eq!(resolver.resolve(&Location::root("drawing"), &"Point".into()), Some(point.clone()));
+        assert_eq!(resolver.resolve(&Location::root("drawing"), &"shapes_core::Point".into()), Some(point.clone()));
+        assert_eq!(resolver.resolve(&Location::root("shapes-core"), &"crate::Hidden".into()), Some(Location::new("shapes-core", "geometry::Hidden")));
+        assert_eq!(resolver.resolve(&Location::root("drawing"), &"shapes_core::Hidden".into()), None);
+        let definition = resolver.resolve_type(&Location::new("shapes-core", "geometry"), &Type::from("super::Point"));
+        assert_eq!(definition.map(|definition| &definition.identifier), Some(&Identifier::from("Point")));
+    }
+
+    #[test]
+    fn unresolved_references() {
+        let registry = registry();
+        let diagnostics = Diagnostics::new();
+        let references = Resolver::new(&registry).resolve_all(&diagnostics);
+        let resolved = references.iter().filter(|reference| reference.definition.is_some()).count();
+        assert_eq!(resolved, 2);
+        let diagnostics = diagnostics.take();
+        let codes = diagnostics.iter().map(|diagnostic| diagnostic.code.as_str()).collect::<Vec<_>>();
+        assert_eq!(codes, ["external-reference", "unresolved-reference", "unresolved-reference", "unresolved-reference"]);
+        assert!(diagnostics.iter().all(|diagnostic| diagnostic.symbol.as_deref() == Some("drawing::Line")));
+    }
+}
@@ -35,6 +35,9 @@ impl Symbols {
         interface.objects.iter().for_each(|object| {
             symbols.push(path.clone().join(object.identifier.clone()));
         });
+        for type_ in interface.types.iter() {
+            symbols.push(path.clone().join(type_.identifier.clone()));
+        }
         interface.methods.iter().for_each(|method| {
             symbols.push(path.clone().join(method.identifier.clone()));
         });
@@ -2,6 +2,6 @@ pub mod type_definition;
 pub mod type_;
 pub mod generics;
 
-pub use type_definition::{TypeDefinition, KindDefinition, Enumeration, Structure, TypeAlias, Field, Variant, structure, enumeration, type_alias};
+pub use type_definition::{TypeDefinition, KindDefinition, Enumeration, Structure, TypeAlias, Union, Field, Variant, structure, enumeration, type_alias, union};
 pub use type_::*;
 pub use generics::*;
\ No newline at end of file
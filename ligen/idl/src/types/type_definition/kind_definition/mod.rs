@@ -1,19 +1,23 @@
 pub mod structure;
 pub mod enumeration;
 pub mod type_alias;
+pub mod union;
 
 use crate::prelude::*;
+use crate::Type;
 
 pub use structure::{Structure, Field};
 pub use enumeration::{Enumeration, Variant};
 pub use type_alias::TypeAlias;
+pub use union::Union;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[allow(missing_docs)]
 pub enum KindDefinition {
     Structure(Structure),
     Enumeration(Enumeration),
-    TypeAlias(TypeAlias)
+    TypeAlias(TypeAlias),
+    Union(Union)
 }
 
 impl KindDefinition {
@@ -22,7 +26,8 @@ impl KindDefinition {
         match self {
             Self::Structure(_) => "Structure",
             Self::Enumeration(_) => "Enumeration",
-            Self::TypeAlias(_) => "TypeAlias"
+            Self::TypeAlias(_) => "TypeAlias",
+            Self::Union(_) => "Union"
         }
     }
 
@@ -31,7 +36,8 @@ impl KindDefinition {
         match self {
             Self::Structure(structure) => structure.fields.is_empty(),
             Self::Enumeration(enumeration) => enumeration.variants.is_empty(),
-            Self::TypeAlias(_) => false
+            Self::TypeAlias(_) => false,
+            Self::Union(union) => union.fields.is_empty()
         }
     }
 
@@ -40,10 +46,21 @@ impl KindDefinition {
         match self {
             Self::Structure(structure) => structure.fields.len(),
             Self::Enumeration(enumeration) => enumeration.variants.len(),
-            Self::TypeAlias(_) => 0
+            Self::TypeAlias(_) => 0,
+            Self::Union(union) => union.fields.len()
         }
     
     }
+
+    /// Returns the types the kind is made of.
+    pub fn types(&self) -> Vec<&Type> {
+        match self {
+            Self::Structure(structure) => structure.fields.iter().map(|field| &field.type_).collect(),
+            Self::Enumeration(_) => Vec::new(),
+            Self::TypeAlias(alias) => vec![&alias.type_],
+            Self::Union(union) => union.fields.iter().map(|field| &field.type_).collect()
+        }
+    }
 }
 
 impl Default for KindDefinition {
@@ -68,4 +85,9 @@ impl From<TypeAlias> for KindDefinition {
     fn from(value: TypeAlias) -> Self {
         Self::TypeAlias(value)
     }
-}
\ No newline at end of file
+}
+impl From<Union> for KindDefinition {
+    fn from(union: Union) -> Self {
+        Self::Union(union)
+    }
+}
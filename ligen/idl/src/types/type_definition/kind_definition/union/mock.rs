@@ -0,0 +1,24 @@
+use crate::*;
+
+pub fn union() -> TypeDefinition {
+    TypeDefinition {
+        identifier: "Union".into(),
+        definition: Union {
+            fields: vec! [
+                Field {
+                    attributes: Default::default(),
+                    visibility: Visibility::Public,
+                    identifier: Some("integer".into()),
+                    type_: Type::u32()
+                },
+                Field {
+                    attributes: Default::default(),
+                    visibility: Visibility::Public,
+                    identifier: Some("float".into()),
+                    type_: Type::f32()
+                }
+            ]
+        }.into(),
+        ..Default::default()
+    }
+}
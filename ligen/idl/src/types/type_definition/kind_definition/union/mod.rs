@@ -0,0 +1,14 @@
+//! Union representation.
+
+#[cfg(any(test, feature = "mocks"))]
+pub mod mock;
+
+use crate::prelude::*;
+use crate::Field;
+
+/// Union representation.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Union {
+    /// Union fields, sharing the same storage.
+    pub fields: Vec<Field>,
+}
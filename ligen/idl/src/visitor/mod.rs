@@ -9,4 +9,7 @@ visitor! {
             Module visits [Module]
         )
     }
-}
\ No newline at end of file
+}
+pub mod mutable;
+
+pub use mutable::{visit_library_mut, visit_module_mut, visit_type_mut, Context, ItemMut, VisitMut};
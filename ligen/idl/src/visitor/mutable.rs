@@ -0,0 +1,352 @@
+//! Mutable traversal of the whole IDL tree.
+
+use crate::{
+    Attributes, Field, Function, Identifier, Import, Interface, KindDefinition, Library, Location,
+    Method, Module, Object, Parameter, Path, Receiver, Type, TypeDefinition, Variant,
+};
+
+/// Where a visited item is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Context {
+    /// Path of the item, starting with the library and root module identifiers. It's the same
+    /// path `Query` matches and the `Visitors` tree has.
+    pub path: Path,
+    /// Location of the module the item is in, or of the module itself.
+    pub module: Location,
+}
+
+impl Context {
+    /// Context of the root module of `library`.
+    pub fn root(library: &Library) -> Self {
+        let path = Path::from(library.identifier.clone()).join(library.root_module.identifier.clone());
+        let module = Location::root(library.identifier.clone());
+        Self { path, module }
+    }
+
+    /// Context of the item `identifier`, inside this one.
+    pub fn join(&self, identifier: impl Into<Identifier>) -> Self {
+        let path = self.path.clone().join(identifier.into());
+        let module = self.module.clone();
+        Self { path, module }
+    }
+
+    /// Context of the submodule `identifier` of this module.
+    pub fn submodule(&self, identifier: impl Into<Identifier>) -> Self {
+        let identifier = identifier.into();
+        let path = self.path.clone().join(identifier.clone());
+        let module = self.module.join(identifier);
+        Self { path, module }
+    }
+}
+
+/// A mutable reference to an item.
+#[allow(missing_docs)]
+pub enum ItemMut<'a> {
+    Module(&'a mut Module),
+    Import(&'a mut Import),
+    Object(&'a mut Object),
+    Type(&'a mut TypeDefinition),
+    Field(&'a mut Field),
+    Variant(&'a mut Variant),
+    Function(&'a mut Function),
+    Method(&'a mut Method),
+    Parameter(&'a mut Parameter),
+    Interface(&'a mut Interface),
+}
+
+impl<'a> ItemMut<'a> {
+    /// The name of the item's kind, as in `query::Item::kind_name`.
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            Self::Module(_) => "module",
+            Self::Import(_) => "import",
+            Self::Object(_) => "object",
+            Self::Type(_) => "type",
+            Self::Field(_) => "field",
+            Self::Variant(_) => "variant",
+            Self::Function(_) => "function",
+            Self::Method(_) => "method",
+            Self::Parameter(_) => "parameter",
+            Self::Interface(_) => "interface",
+        }
+    }
+
+    /// The item's identifier. Imports only have one if they are renamed, and tuple fields don't.
+    pub fn identifier(&mut self) -> Option<&mut Identifier> {
+        match self {
+            Self::Module(module) => Some(&mut module.identifier),
+            Self::Import(import) => import.renaming.as_mut(),
+            Self::Object(object) => Some(&mut object.identifier),
+            Self::Type(definition) => Some(&mut definition.identifier),
+            Self::Field(field) => field.identifier.as_mut(),
+            Self::Variant(variant) => Some(&mut variant.identifier),
+            Self::Function(function) => Some(&mut function.identifier),
+            Self::Method(method) => Some(&mut method.identifier),
+            Self::Parameter(parameter) => Some(&mut parameter.identifier),
+            Self::Interface(interface) => Some(&mut interface.identifier),
+        }
+    }
+
+    /// The item's attributes.
+    pub fn attributes(self) -> Option<&'a mut Attributes> {
+        match self {
+            Self::Module(module) => Some(&mut module.attributes),
+            Self::Import(import) => Some(&mut import.attributes),
+            Self::Object(object) => Some(&mut object.attributes),
+            Self::Type(definition) => Some(&mut definition.attributes),
+            Self::Field(field) => Some(&mut field.attributes),
+            Self::Variant(variant) => Some(&mut variant.attributes),
+            Self::Function(function) => Some(&mut function.attributes),
+            Self::Method(method) => Some(&mut method.attributes),
+            Self::Parameter(parameter) => Some(&mut parameter.attributes),
+            Self::Interface(interface) => Some(&mut interface.attributes),
+        }
+    }
+}
+
+/// A mutable visitor over the whole IDL tree.
+///
+/// The `Visitors` tree of `is_tree` only has the library and its modules, and borrows them
+/// immutably, so this visitor walks the items itself. Modules are visited at the same paths as
+/// in that tree. Items are visited before their children. Closures taking a `&Context` and an `ItemMut` are
+/// visitors too, so a pass can be as short as:
+///
+/// ```
+/// # use ligen_idl::{Context, ItemMut, Library, Visibility, visit_library_mut};
+/// # let mut library = Library::default();
+/// // Removes the private items.
+/// visit_library_mut(&mut |_: &Context, item: ItemMut| match item {
+///     ItemMut::Function(function) => function.visibility == Visibility::Public,
+///     _ => true,
+/// }, &mut library);
+/// ```
+pub trait VisitMut {
+    /// Visits an item. Returning `false` removes it and skips its children. The root module
+    /// can't be removed.
+    fn visit_item(&mut self, context: &Context, item: ItemMut<'_>) -> bool {
+        let _ = (context, item);
+        true
+    }
+
+    /// Visits a `Type` used by the item at `context`. Returning `false` skips its generic
+    /// arguments.
+    fn visit_type(&mut self, context: &Context, type_: &mut Type) -> bool {
+        let _ = (context, type_);
+        true
+    }
+}
+
+impl<F: FnMut(&Context, ItemMut<'_>) -> bool> VisitMut for F {
+    fn visit_item(&mut self, context: &Context, item: ItemMut<'_>) -> bool {
+        self(context, item)
+    }
+}
+
+/// Visits `library` and all its items.
+pub fn visit_library_mut<V: VisitMut + ?Sized>(visitor: &mut V, library: &mut Library) {
+    let context = Context::root(library);
+    visit_module_mut(visitor, &context, &mut library.root_module);
+}
+
+/// Visits `module`, at `context`, and all its items. Returns `false` if it should be removed.
+pub fn visit_module_mut<V: VisitMut + ?Sized>(visitor: &mut V, context: &Context, module: &mut Module) -> bool {
+    if !visitor.visit_item(context, ItemMut::Module(module)) {
+        return false;
+    }
+    module.imports.retain_mut(|import| {
+        let identifier = import.renaming.clone().unwrap_or_else(|| import.path.last().identifier.clone());
+        visitor.visit_item(&context.join(identifier), ItemMut::Import(import))
+    });
+    visit_objects(visitor, context, &mut module.objects);
+    module.types.retain_mut(|definition| visit_type_definition(visitor, &context.join(definition.identifier.clone()), definition));
+    visit_functions(visitor, context, &mut module.functions);
+    module.interfaces.retain_mut(|interface| visit_interface(visitor, &context.join(interface.identifier.clone()), interface));
+    module.modules.retain_mut(|child| visit_module_mut(visitor, &context.submodule(child.identifier.clone()), child));
+    true
+}
+
+/// Visits `type_` and its generic arguments.
+pub fn visit_type_mut<V: VisitMut + ?Sized>(visitor: &mut V, context: &Context, type_: &mut Type) {
+    if visitor.visit_type(context, type_) {
+        for segment in &mut type_.path.segments {
+            for generic in &mut segment.generics.types {
+                visit_type_mut(visitor, context, generic);
+            }
+        }
+    }
+}
+
+fn visit_objects<V: VisitMut + ?Sized>(visitor: &mut V, context: &Context, objects: &mut Vec<Object>) {
+    objects.retain_mut(|object| {
+        let context = context.join(object.identifier.clone());
+        if !visitor.visit_item(&context, ItemMut::Object(object)) {
+            return false;
+        }
+        visit_type_mut(visitor, &context, &mut object.type_);
+        true
+    });
+}
+
+fn visit_type_definition<V: VisitMut + ?Sized>(visitor: &mut V, context: &Context, definition: &mut TypeDefinition) -> bool {
+    if !visitor.visit_item(context, ItemMut::Type(definition)) {
+        return false;
+    }
+    match &mut definition.definition {
+        KindDefinition::Structure(structure) => visit_fields(visitor, context, &mut structure.fields),
+        KindDefinition::Enumeration(enumeration) => {
+            enumeration
+                .variants
+                .retain_mut(|variant| visitor.visit_item(&context.join(variant.identifier.clone()), ItemMut::Variant(variant)));
+        },
+        KindDefinition::TypeAlias(alias) => visit_type_mut(visitor, context, &mut alias.type_),
+        KindDefinition::Union(union) => visit_fields(visitor, context, &mut union.fields),
+    }
+    true
+}
+
+fn visit_fields<V: VisitMut + ?Sized>(visitor: &mut V, context: &Context, fields: &mut Vec<Field>) {
+    let mut index = 0;
+    fields.retain_mut(|field| {
+        let identifier = field.identifier.clone().unwrap_or_else(|| index.to_string().into());
+        index += 1;
+        let context = context.join(identifier);
+        if !visitor.visit_item(&context, ItemMut::Field(field)) {
+            return false;
+        }
+        visit_type_mut(visitor, &context, &mut field.type_);
+        true
+    });
+}
+
+fn visit_functions<V: VisitMut + ?Sized>(visitor: &mut V, context: &Context, functions: &mut Vec<Function>) {
+    functions.retain_mut(|function| {
+        let context = context.join(function.identifier.clone());
+        if !visitor.visit_item(&context, ItemMut::Function(function)) {
+            return false;
+        }
+        visit_parameters(visitor, &context, &mut function.inputs);
+        if let Some(output) = &mut function.output {
+            visit_type_mut(visitor, &context, output);
+        }
+        true
+    });
+}
+
+fn visit_interface<V: VisitMut + ?Sized>(visitor: &mut V, context: &Context, interface: &mut Interface) -> bool {
+    if !visitor.visit_item(context, ItemMut::Interface(interface)) {
+        return false;
+    }
+    visit_objects(visitor, context, &mut interface.objects);
+    interface.types.retain_mut(|definition| visit_type_definition(visitor, &context.join(definition.identifier.clone()), definition));
+    visit_functions(visitor, context, &mut interface.functions);
+    interface.methods.retain_mut(|method| {
+        let context = context.join(method.identifier.clone());
+        if !visitor.visit_item(&context, ItemMut::Method(method)) {
+            return false;
+        }
+        if let Receiver::Boxed(type_) = &mut method.receiver {
+            visit_type_mut(visitor, &context, type_);
+        }
+        visit_parameters(visitor, &context, &mut method.inputs);
+        if let Some(output) = &mut method.output {
+            visit_type_mut(visitor, &context, output);
+        }
+        true
+    });
+    true
+}
+
+fn visit_parameters<V: VisitMut + ?Sized>(visitor: &mut V, context: &Context, parameters: &mut Vec<Parameter>) {
+    parameters.retain_mut(|parameter| {
+        let context = context.join(parameter.identifier.clone());
+        if !visitor.visit_item(&context, ItemMut::Parameter(parameter)) {
+            return false;
+        }
+        visit_type_mut(visitor, &context, &mut parameter.type_);
+        true
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Structure, Visibility, Visitors};
+    use is_tree::{HasPath, TreeIterator};
+
+    struct Counter {
+        items: Vec<String>,
+        types: usize,
+    }
+
+    impl VisitMut for Counter {
+        fn visit_item(&mut self, context: &Context, item: ItemMut<'_>) -> bool {
+            self.items.push(format!("{} {}", item.kind_name(), context.path));
+            true
+        }
+
+        fn visit_type(&mut self, _context: &Context, _type_: &mut Type) -> bool {
+            self.types += 1;
+            true
+        }
+    }
+
+    fn library() -> Library {
+        let point = TypeDefinition {
+            identifier: "Point".into(),
+            definition: KindDefinition::Structure(Structure { fields: vec![Field { identifier: Some("x".into()), type_: Type::f32(), ..Default::default() }] }),
+            ..Default::default()
+        };
+        let origin = Function { identifier: "origin".into(), output: Some(Type::option(Type::from("Point"))), ..Default::default() };
+        let reset = Function { identifier: "reset".into(), visibility: Visibility::Private, ..Default::default() };
+        let shapes = Module { identifier: "shapes".into(), types: vec![point], functions: vec![origin, reset], ..Default::default() };
+        let root_module = Module { identifier: "lib".into(), modules: vec![shapes], ..Default::default() };
+        Library { identifier: "geometry".into(), root_module, ..Default::default() }
+    }
+
+    #[test]
+    fn visits() {
+        let mut counter = Counter { items: Vec::new(), types: 0 };
+        visit_library_mut(&mut counter, &mut library());
+        assert_eq!(counter.items, [
+            "module geometry::lib",
+            "module geometry::lib::shapes",
+            "type geometry::lib::shapes::Point",
+            "field geometry::lib::shapes::Point::x",
+            "function geometry::lib::shapes::origin",
+            "function geometry::lib::shapes::reset",
+        ]);
+        assert_eq!(counter.types, 3);
+    }
+
+    #[test]
+    fn visitors_tree_paths() {
+        let mut library = library();
+        let mut modules = Vec::new();
+        visit_library_mut(&mut |context: &Context, item: ItemMut| {
+            if let ItemMut::Module(_) = item {
+                modules.push(context.path.to_string());
+            }
+            true
+        }, &mut library);
+        let mut tree = TreeIterator::<Visitors>::new(&library)
+            .filter_map(|visitor| visitor.as_module().map(|visitor| Path::from(visitor.path()).to_string()))
+            .collect::<Vec<_>>();
+        modules.sort();
+        tree.sort();
+        assert_eq!(modules, tree);
+    }
+
+    #[test]
+    fn removes() {
+        let mut library = library();
+        let mut modules = Vec::new();
+        visit_library_mut(&mut |context: &Context, item: ItemMut| {
+            if let ItemMut::Module(_) = item {
+                modules.push(context.module.clone());
+            }
+            !matches!(item, ItemMut::Function(function) if function.visibility == Visibility::Private)
+        }, &mut library);
+        assert_eq!(modules, [Location::root("geometry"), Location::new("geometry", "shapes")]);
+        assert_eq!(library.root_module.modules[0].functions.len(), 1);
+    }
+}
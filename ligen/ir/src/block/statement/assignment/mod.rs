@@ -0,0 +1,23 @@
+use crate::prelude::*;
+
+use ligen_idl::Identifier;
+use crate::Expression;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Assignment {
+    /// Assigned place field.
+    pub target: Expression,
+    /// Operator field, `=` or a compound one like `+=`.
+    pub operator: Identifier,
+    /// Assigned value field.
+    pub value: Expression,
+}
+
+impl Assignment {
+    pub fn new(target: impl Into<Expression>, operator: impl Into<Identifier>, value: impl Into<Expression>) -> Self {
+        let target = target.into();
+        let operator = operator.into();
+        let value = value.into();
+        Self { target, operator, value }
+    }
+}
@@ -0,0 +1,19 @@
+use crate::prelude::*;
+
+use crate::Expression;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CallExpression {
+    /// Called function field.
+    pub function: Box<Expression>,
+    /// Arguments field.
+    pub arguments: Vec<Expression>,
+}
+
+impl CallExpression {
+    pub fn new(function: impl Into<Expression>, arguments: impl IntoIterator<Item = Expression>) -> Self {
+        let function = Box::new(function.into());
+        let arguments = arguments.into_iter().collect();
+        Self { function, arguments }
+    }
+}
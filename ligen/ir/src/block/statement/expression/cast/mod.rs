@@ -0,0 +1,20 @@
+use crate::prelude::*;
+
+use ligen_idl::Type;
+use crate::Expression;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CastExpression {
+    /// Converted expression field.
+    pub expression: Box<Expression>,
+    /// Target type field.
+    pub type_: Type,
+}
+
+impl CastExpression {
+    pub fn new(expression: impl Into<Expression>, type_: impl Into<Type>) -> Self {
+        let expression = Box::new(expression.into());
+        let type_ = type_.into();
+        Self { expression, type_ }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::prelude::*;
+
+use ligen_idl::Identifier;
+use crate::Expression;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FieldExpression {
+    /// Accessed expression field.
+    pub base: Box<Expression>,
+    /// Field identifier, or index for tuples.
+    pub member: Identifier,
+}
+
+impl FieldExpression {
+    pub fn new(base: impl Into<Expression>, member: impl Into<Identifier>) -> Self {
+        let base = Box::new(base.into());
+        let member = member.into();
+        Self { base, member }
+    }
+}
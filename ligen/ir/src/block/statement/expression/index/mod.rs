@@ -0,0 +1,19 @@
+use crate::prelude::*;
+
+use crate::Expression;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IndexExpression {
+    /// Indexed expression field.
+    pub base: Box<Expression>,
+    /// Index field.
+    pub index: Box<Expression>,
+}
+
+impl IndexExpression {
+    pub fn new(base: impl Into<Expression>, index: impl Into<Expression>) -> Self {
+        let base = Box::new(base.into());
+        let index = Box::new(index.into());
+        Self { base, index }
+    }
+}
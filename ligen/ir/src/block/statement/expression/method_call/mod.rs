@@ -0,0 +1,23 @@
+use crate::prelude::*;
+
+use ligen_idl::Identifier;
+use crate::Expression;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MethodCallExpression {
+    /// Receiver field.
+    pub receiver: Box<Expression>,
+    /// Method identifier field.
+    pub method: Identifier,
+    /// Arguments field, without the receiver.
+    pub arguments: Vec<Expression>,
+}
+
+impl MethodCallExpression {
+    pub fn new(receiver: impl Into<Expression>, method: impl Into<Identifier>, arguments: impl IntoIterator<Item = Expression>) -> Self {
+        let receiver = Box::new(receiver.into());
+        let method = method.into();
+        let arguments = arguments.into_iter().collect();
+        Self { receiver, method, arguments }
+    }
+}
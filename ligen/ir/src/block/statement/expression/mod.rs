@@ -1,7 +1,22 @@
 use crate::prelude::*;
 
 pub mod binary;
+pub mod unary;
+pub mod call;
+pub mod method_call;
+pub mod field;
+pub mod index;
+pub mod cast;
+pub mod structure;
+
 pub use binary::*;
+pub use unary::*;
+pub use call::*;
+pub use method_call::*;
+pub use field::*;
+pub use index::*;
+pub use cast::*;
+pub use structure::*;
 
 use ligen_idl::{Literal, Path, Identifier};
 
@@ -15,6 +30,20 @@ pub enum Expression {
     Binary(BinaryExpression),
     /// Parenthesized expression.
     Parenthesized(Box<Expression>),
+    /// Unary expression, like `-a` or `!a`.
+    Unary(UnaryExpression),
+    /// Function call expression.
+    Call(CallExpression),
+    /// Method call expression.
+    MethodCall(MethodCallExpression),
+    /// Field access expression.
+    Field(FieldExpression),
+    /// Indexing expression.
+    Index(IndexExpression),
+    /// Cast expression, like `a as f32`.
+    Cast(CastExpression),
+    /// Structure construction expression.
+    Structure(StructureExpression),
 }
 
 impl From<Identifier> for Expression {
@@ -40,3 +69,45 @@ impl From<BinaryExpression> for Expression {
         Self::Binary(binary_expression)
     }
 }
+
+impl From<UnaryExpression> for Expression {
+    fn from(unary_expression: UnaryExpression) -> Self {
+        Self::Unary(unary_expression)
+    }
+}
+
+impl From<CallExpression> for Expression {
+    fn from(call_expression: CallExpression) -> Self {
+        Self::Call(call_expression)
+    }
+}
+
+impl From<MethodCallExpression> for Expression {
+    fn from(method_call_expression: MethodCallExpression) -> Self {
+        Self::MethodCall(method_call_expression)
+    }
+}
+
+impl From<FieldExpression> for Expression {
+    fn from(field_expression: FieldExpression) -> Self {
+        Self::Field(field_expression)
+    }
+}
+
+impl From<IndexExpression> for Expression {
+    fn from(index_expression: IndexExpression) -> Self {
+        Self::Index(index_expression)
+    }
+}
+
+impl From<CastExpression> for Expression {
+    fn from(cast_expression: CastExpression) -> Self {
+        Self::Cast(cast_expression)
+    }
+}
+
+impl From<StructureExpression> for Expression {
+    fn from(structure_expression: StructureExpression) -> Self {
+        Self::Structure(structure_expression)
+    }
+}
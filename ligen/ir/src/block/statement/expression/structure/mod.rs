@@ -0,0 +1,37 @@
+use crate::prelude::*;
+
+use ligen_idl::{Identifier, Path};
+use crate::Expression;
+
+/// Structure construction, like `Point { x: 1.0, y: 2.0 }`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StructureExpression {
+    /// Structure path field.
+    pub path: Path,
+    /// Field values, in the order they are written.
+    pub fields: Vec<FieldValue>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FieldValue {
+    /// Field identifier field.
+    pub identifier: Identifier,
+    /// Value field.
+    pub value: Expression,
+}
+
+impl StructureExpression {
+    pub fn new(path: impl Into<Path>, fields: impl IntoIterator<Item = FieldValue>) -> Self {
+        let path = path.into();
+        let fields = fields.into_iter().collect();
+        Self { path, fields }
+    }
+}
+
+impl FieldValue {
+    pub fn new(identifier: impl Into<Identifier>, value: impl Into<Expression>) -> Self {
+        let identifier = identifier.into();
+        let value = value.into();
+        Self { identifier, value }
+    }
+}
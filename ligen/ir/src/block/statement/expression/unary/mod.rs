@@ -0,0 +1,20 @@
+use crate::prelude::*;
+
+use ligen_idl::Identifier;
+use crate::Expression;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UnaryExpression {
+    /// Operator field.
+    pub operator: Identifier,
+    /// Operand field.
+    pub operand: Box<Expression>,
+}
+
+impl UnaryExpression {
+    pub fn new(operator: impl Into<Identifier>, operand: impl Into<Expression>) -> Self {
+        let operator = operator.into();
+        let operand = Box::new(operand.into());
+        Self { operator, operand }
+    }
+}
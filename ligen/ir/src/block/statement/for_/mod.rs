@@ -0,0 +1,19 @@
+use crate::prelude::*;
+
+use ligen_idl::Identifier;
+use crate::{Block, Expression};
+
+/// "for <identifier> in <start>..<end> { <body> }", counting over a range.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct For {
+    /// Counter identifier field.
+    pub identifier: Identifier,
+    /// First value field.
+    pub start: Expression,
+    /// Bound field.
+    pub end: Expression,
+    /// Whether the bound is included, as in `start..=end`.
+    pub inclusive: bool,
+    /// Body field.
+    pub body: Block,
+}
@@ -0,0 +1,21 @@
+use crate::prelude::*;
+
+use crate::{Block, Expression};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct If {
+    /// Condition field.
+    pub condition: Expression,
+    /// Block run when the condition holds.
+    pub then: Block,
+    /// Block run otherwise. An `else if` is a block with a single `If`.
+    pub else_: Option<Block>,
+}
+
+impl If {
+    pub fn new(condition: impl Into<Expression>, then: impl Into<Block>, else_: Option<Block>) -> Self {
+        let condition = condition.into();
+        let then = then.into();
+        Self { condition, then, else_ }
+    }
+}
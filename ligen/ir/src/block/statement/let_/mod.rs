@@ -0,0 +1,23 @@
+use crate::prelude::*;
+
+use ligen_idl::{Identifier, Mutability, Type};
+use crate::Expression;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Let {
+    /// Binding mutability field.
+    pub mutability: Mutability,
+    /// Binding identifier field.
+    pub identifier: Identifier,
+    /// Declared type field.
+    pub type_: Option<Type>,
+    /// Initial value field.
+    pub value: Option<Expression>,
+}
+
+impl Let {
+    pub fn new(mutability: Mutability, identifier: impl Into<Identifier>, type_: Option<Type>, value: Option<Expression>) -> Self {
+        let identifier = identifier.into();
+        Self { mutability, identifier, type_, value }
+    }
+}
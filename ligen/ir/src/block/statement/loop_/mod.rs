@@ -0,0 +1,10 @@
+use crate::prelude::*;
+
+use crate::Block;
+
+/// "loop { <body> }", left with `break`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Loop {
+    /// Body field.
+    pub body: Block,
+}
@@ -1,15 +1,45 @@
 use crate::prelude::*;
 
 pub mod return_;
+pub mod let_;
+pub mod assignment;
+pub mod if_;
+pub mod loop_;
+pub mod while_;
+pub mod for_;
 pub mod expression;
 
 pub use return_::*;
+pub use let_::*;
+pub use assignment::*;
+pub use if_::*;
+pub use loop_::*;
+pub use while_::*;
+pub use for_::*;
 pub use expression::*;
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum Statement {
     /// "return <expr>;" statement.
-    Return(Return)
+    Return(Return),
+    /// "let <identifier> = <expr>;" statement.
+    Let(Let),
+    /// "<place> = <expr>;" statement.
+    Assignment(Assignment),
+    /// "if <condition> { } else { }" statement.
+    If(If),
+    /// "loop { }" statement.
+    Loop(Loop),
+    /// "while <condition> { }" statement.
+    While(While),
+    /// "for <identifier> in <range> { }" statement.
+    For(For),
+    /// "break;" statement.
+    Break,
+    /// "continue;" statement.
+    Continue,
+    /// "<expr>;" statement, like a call.
+    Expression(Expression),
 }
 
 impl Statement {
@@ -18,4 +48,46 @@ impl Statement {
             value: expression.map(Into::into),
         })
     }
-}
\ No newline at end of file
+}
+
+impl From<Let> for Statement {
+    fn from(let_: Let) -> Self {
+        Self::Let(let_)
+    }
+}
+
+impl From<Assignment> for Statement {
+    fn from(assignment: Assignment) -> Self {
+        Self::Assignment(assignment)
+    }
+}
+
+impl From<If> for Statement {
+    fn from(if_: If) -> Self {
+        Self::If(if_)
+    }
+}
+
+impl From<Loop> for Statement {
+    fn from(loop_: Loop) -> Self {
+        Self::Loop(loop_)
+    }
+}
+
+impl From<While> for Statement {
+    fn from(while_: While) -> Self {
+        Self::While(while_)
+    }
+}
+
+impl From<For> for Statement {
+    fn from(for_: For) -> Self {
+        Self::For(for_)
+    }
+}
+
+impl From<Expression> for Statement {
+    fn from(expression: Expression) -> Self {
+        Self::Expression(expression)
+    }
+}
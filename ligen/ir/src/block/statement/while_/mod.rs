@@ -0,0 +1,12 @@
+use crate::prelude::*;
+
+use crate::{Block, Expression};
+
+/// "while <condition> { <body> }".
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct While {
+    /// Condition field.
+    pub condition: Expression,
+    /// Body field.
+    pub body: Block,
+}
@@ -4,17 +4,24 @@ pub use group::*;
 use crate::prelude::*;
 
 use ligen_idl::{Literal, Path};
+use ligen_utils::transformers::rules::Rules;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     #[serde(flatten)]
-    map: Group
+    map: Group,
+    #[serde(default, skip_serializing_if = "Rules::is_empty")]
+    rules: Rules,
+    #[serde(skip)]
+    diagnostics: Diagnostics
 }
 
 impl Default for Config {
     fn default() -> Self {
         let map = Default::default();
-        let mut config = Self { map };
+        let rules = Default::default();
+        let diagnostics = Default::default();
+        let mut config = Self { map, rules, diagnostics };
         config.set_only_parse_symbols(false);
         config
     }
@@ -25,6 +32,21 @@ impl Config {
         self.map.iter()
     }
 
+    /// Diagnostics reported while transforming with this config. Clones share the same collector.
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+
+    /// Rewrite rules to apply to the parsed libraries, from the `[rules]` section.
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// Sets the rewrite rules.
+    pub fn set_rules(&mut self, rules: Rules) {
+        self.rules = rules;
+    }
+
     /// Sets whether to parse all symbols or only the ones that are explicitly marked as such.
     pub fn set_only_parse_symbols(&mut self, value: bool) {
         self.set("ligen::only-parse-symbols", value);
@@ -122,4 +144,31 @@ mod tests {
         assert_eq!(config.get(["ligen", "parse-all"].as_slice()), Some(&false.into()));
         assert_eq!(config.get(["ligen", "default-name"].as_slice()), Some(&"library".into()));
     }
+
+    #[test]
+    fn rules() {
+        let config = Config::try_from(r#"
+            [ligen]
+            parse-all = false
+
+            [[rules.hide]]
+            select = "function where private"
+
+            [[rules.rename]]
+            select = "type lib::models::*"
+            convention = "PascalCase""#
+        ).unwrap();
+        assert_eq!(config.get("ligen::parse-all"), Some(&false.into()));
+        assert_eq!(config.rules().hide.len(), 1);
+        assert_eq!(config.rules().rename[0].select.to_string(), "type lib::models::*");
+        assert!(Config::try_from("[[rules.hide]]\nselect = \"function where\"").is_err());
+        assert!(Config::try_from("[[rules.replace]]\nfrom = \"Instant\"\nto = \"u*\"").is_err());
+    }
+
+    #[test]
+    fn shared_diagnostics() {
+        let config = config();
+        config.clone().diagnostics().report(Diagnostic::warning("skipped-function", "Unsupported function."));
+        assert_eq!(config.diagnostics().len(), 1);
+    }
 }
@@ -19,8 +19,13 @@ pub trait FileGenerator<Input> {
     /// Generation base path.
     fn base_path(&self) -> PathBuf;
 
-    /// Generate files.
-    fn generate_files(&self, input: Input, file_set: &mut FileSet) -> Result<()>;
+    /// Generate files, with the config the generator was called with.
+    fn generate_files(&self, input: Input, file_set: &mut FileSet, config: &Config) -> Result<()>;
+
+    /// The rules the input must pass before generating.
+    fn validation(&self) -> Validation {
+        Validation::default()
+    }
 
     /// Saves the file set.
     fn save_file_set(&self, file_set: FileSet, folder: &std::path::Path) -> Result<()> {
@@ -36,7 +41,7 @@ pub trait FileGenerator<Input> {
 impl<I, T: FileGenerator<I>> Generator<I, ()> for T {
     fn generate(&self, input: I, config: &Config) -> Result<()> {
         let mut file_set = FileSet::default();
-        self.generate_files(input, &mut file_set)?;
+        self.generate_files(input, &mut file_set, config)?;
         let output_dir = config
             .get("ligen::output-dir")
             .and_then(|l| l.as_string())
@@ -45,4 +50,8 @@ impl<I, T: FileGenerator<I>> Generator<I, ()> for T {
         self.save_file_set(file_set, &output_dir)?;
         Ok(())
     }
+
+    fn validation(&self) -> Validation {
+        FileGenerator::validation(self)
+    }
 }
@@ -68,7 +68,7 @@ pub use template::*;
 //         <Self as TemplateBasedGenerator>::base_path(self)
 //     }
 
-//     fn generate_files(&self, library: &Library, file_set: &mut FileSet) -> Result<()> {
+//     fn generate_files(&self, library: &Library, file_set: &mut FileSet, _config: &Config) -> Result<()> {
 //         let mut template = Template::new();
 //         self.register_templates(&mut template)?;
 //         register_functions!(template, name_from_path, join_path, json);
@@ -4,10 +4,15 @@ use crate::prelude::*;
 
 pub use file_generator::*;
 
-pub use crate::Config;
+pub use crate::{Config, Validation};
 
 pub trait Generator<Input, Output> {
     fn generate(&self, input: Input, config: &Config) -> Result<Output>;
+
+    /// The rules the input must pass before generating.
+    fn validation(&self) -> Validation {
+        Validation::default()
+    }
 }
 
 #[async_trait]
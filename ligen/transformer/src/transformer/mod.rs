@@ -1,5 +1,13 @@
 use crate::prelude::*;
+use ligen_idl::{Library, Registry};
+use ligen_utils::transformers::alias::ReplaceCrateAlias;
+use ligen_utils::transformers::naming::NormalizeNames;
+use ligen_utils::transformers::path::RelativePathToAbsolutePath;
+use ligen_utils::transformers::prune::Prune;
+use ligen_utils::transformers::rules::Rules;
+use ligen_utils::transformers::wire::{InterpretSerde, WireFormats};
 
+/// The transformation step of the pipeline: parsers, generators and the IDL passes.
 pub trait Transformer<Input, Output> {
     fn transform(&self, input: Input, config: &Config) -> Result<Output>;
 
@@ -24,3 +32,36 @@ pub trait AsyncTransformer<Input, Output> {
         Default::default()
     }
 }
+
+/// Runs the IDL passes of `ligen_utils`, which don't fail nor read the config, as `Transformer`s.
+macro_rules! passes {
+    ($($pass:ident: $input:ty => $output:ty),*) => {
+        $(impl<'a> Transformer<&'a $input, $output> for $pass {
+            fn transform(&self, input: &'a $input, _config: &Config) -> Result<$output> {
+                Ok($pass::transform(self, input))
+            }
+
+            fn name(&self) -> &str {
+                stringify!($pass)
+            }
+        })*
+    };
+}
+
+passes!(
+    ReplaceCrateAlias: Library => Library,
+    RelativePathToAbsolutePath: Library => Library,
+    NormalizeNames: Library => Library,
+    Rules: Library => Library,
+    InterpretSerde: Library => WireFormats
+);
+
+impl<'a> Transformer<&'a Registry, Registry> for Prune {
+    fn transform(&self, input: &'a Registry, _config: &Config) -> Result<Registry> {
+        Prune::transform(self, input)
+    }
+
+    fn name(&self) -> &str {
+        "Prune"
+    }
+}
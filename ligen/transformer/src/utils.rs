@@ -13,4 +13,9 @@ impl<T> WithSource<T> {
     pub fn sub<U>(&self, ast: U) -> WithSource<U> {
         WithSource::new(&self.source, ast)
     }
-}
\ No newline at end of file
+
+    /// Source span of the byte range `start..end`.
+    pub fn span(&self, start: usize, end: usize) -> ligen_common::Span {
+        ligen_common::Span::from_offsets(&self.source, start, end)
+    }
+}
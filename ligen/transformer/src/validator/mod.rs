@@ -1,5 +1,84 @@
+mod rules;
+pub use rules::*;
+
 use crate::prelude::*;
+use ligen_idl::{Library, Registry};
 
 pub trait Validator<Input> {
+    fn validate(&self, input: &Input, config: &Config) -> Result<()>;
+}
+
+/// `Validator` as it was before it stopped taking a mutable input.
+#[deprecated(note = "Validators only check their input. Implement `Validator`, or a `Transformer` to rewrite the input.")]
+pub trait ValidatorMut<Input> {
     fn validate(&self, input: &mut Input, config: &Config) -> Result<()>;
-}
\ No newline at end of file
+}
+
+/// A check over a `Library`, like the built-in `DuplicateIdentifiers` or `EmptyEnumerations`.
+pub trait ValidationRule {
+    /// Checks `library`, whose references can point to the other libraries in `registry`,
+    /// reporting the problems found to `diagnostics`.
+    fn check(&self, registry: &Registry, library: &Library, diagnostics: &Diagnostics);
+}
+
+/// A set of `ValidationRule`s.
+///
+/// Generators declare the rules their input must pass with `Generator::validation`. Errors make
+/// the validation fail, while warnings and notes are only reported.
+pub struct Validation {
+    rules: Vec<Box<dyn ValidationRule>>,
+}
+
+impl Default for Validation {
+    /// The rules every generator needs: `UnresolvedTypes`, `DuplicateIdentifiers`,
+    /// `IncompatibleLiterals` and `EmptyEnumerations`.
+    fn default() -> Self {
+        Self::new()
+            .with(UnresolvedTypes)
+            .with(DuplicateIdentifiers)
+            .with(IncompatibleLiterals)
+            .with(EmptyEnumerations)
+    }
+}
+
+impl Validation {
+    /// Creates a validation without rules.
+    pub fn new() -> Self {
+        let rules = Vec::new();
+        Self { rules }
+    }
+
+    /// Adds a rule.
+    pub fn with(mut self, rule: impl ValidationRule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Checks `library` with every rule, returning the diagnostics.
+    pub fn check(&self, registry: &Registry, library: &Library) -> Vec<Diagnostic> {
+        let diagnostics = Diagnostics::new();
+        for rule in &self.rules {
+            rule.check(registry, library, &diagnostics);
+        }
+        diagnostics.take()
+    }
+}
+
+impl Validator<Registry> for Validation {
+    /// Checks every library of the registry, reporting the diagnostics to the `Config`.
+    fn validate(&self, input: &Registry, config: &Config) -> Result<()> {
+        let mut libraries = input.libraries.values().collect::<Vec<_>>();
+        libraries.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+        let diagnostics = libraries
+            .into_iter()
+            .flat_map(|library| self.check(input, library))
+            .collect::<Vec<_>>();
+        let errors = diagnostics.iter().filter(|diagnostic| diagnostic.severity == Severity::Error).count();
+        config.diagnostics().extend(diagnostics);
+        if errors > 0 {
+            Err(Error::Message(format!("Validation failed with {errors} error(s).")))
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,286 @@
+//! Built-in validation rules.
+
+use std::collections::HashSet;
+
+use crate::prelude::*;
+use crate::validator::ValidationRule;
+use ligen_idl::{Identifier, KindDefinition, Library, Literal, Location, Module, Registry, Resolver, Structure, Union};
+use ligen_utils::transformers::naming::NormalizeNames;
+
+/// Reports the type paths that don't resolve, as `Resolver::resolve_library` does.
+pub struct UnresolvedTypes;
+
+impl ValidationRule for UnresolvedTypes {
+    fn check(&self, registry: &Registry, library: &Library, diagnostics: &Diagnostics) {
+        Resolver::new(registry).resolve_library(library, diagnostics);
+    }
+}
+
+/// Reports the items sharing a name in the same namespace as `duplicate-identifier` errors.
+///
+/// Types and modules share a module's type namespace, and functions and objects its value
+/// namespace. Fields, variants, methods and parameters are checked in their own scopes.
+/// Interfaces aren't, because a type can have several `impl` blocks.
+pub struct DuplicateIdentifiers;
+
+impl DuplicateIdentifiers {
+    fn scope<'a>(location: &Location, identifiers: impl IntoIterator<Item = &'a Identifier>, diagnostics: &Diagnostics) {
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        for identifier in identifiers {
+            if !seen.insert(identifier) && reported.insert(identifier) {
+                let diagnostic = Diagnostic::error("duplicate-identifier", format!("`{identifier}` is defined more than once."))
+                    .with_symbol(location.join(identifier.clone()));
+                diagnostics.report(diagnostic);
+            }
+        }
+    }
+}
+
+impl ValidationRule for DuplicateIdentifiers {
+    fn check(&self, _registry: &Registry, library: &Library, diagnostics: &Diagnostics) {
+        modules(library, &mut |location, module| {
+            let types = module.types.iter().map(|definition| &definition.identifier);
+            let modules = module.modules.iter().map(|module| &module.identifier);
+            Self::scope(location, types.chain(modules), diagnostics);
+            let functions = module.functions.iter().map(|function| &function.identifier);
+            let objects = module.objects.iter().map(|object| &object.identifier);
+            Self::scope(location, functions.chain(objects), diagnostics);
+            for function in &module.functions {
+                let location = location.join(function.identifier.clone());
+                Self::scope(&location, function.inputs.iter().map(|parameter| &parameter.identifier), diagnostics);
+            }
+            for definition in &module.types {
+                let location = location.join(definition.identifier.clone());
+                match &definition.definition {
+                    KindDefinition::Structure(Structure { fields }) | KindDefinition::Union(Union { fields }) => Self::scope(&location, fields.iter().filter_map(|field| field.identifier.as_ref()), diagnostics),
+                    KindDefinition::Enumeration(enumeration) => Self::scope(&location, enumeration.variants.iter().map(|variant| &variant.identifier), diagnostics),
+                    KindDefinition::TypeAlias(_) => (),
+                }
+            }
+            for interface in &module.interfaces {
+                let location = location.join(interface.identifier.clone());
+                let methods = interface.methods.iter().map(|method| &method.identifier);
+                let functions = interface.functions.iter().map(|function| &function.identifier);
+                Self::scope(&location, methods.chain(functions), diagnostics);
+                Self::scope(&location, interface.types.iter().map(|definition| &definition.identifier), diagnostics);
+                for method in &interface.methods {
+                    let location = location.join(method.identifier.clone());
+                    Self::scope(&location, method.inputs.iter().map(|parameter| &parameter.identifier), diagnostics);
+                }
+            }
+        });
+    }
+}
+
+/// Reports the identifiers a target language can't use, like keywords, as `invalid-identifier`
+/// errors.
+///
+/// Names escaped the way `NormalizeNames` escapes keywords are valid.
+pub struct InvalidIdentifiers {
+    /// The target language, used in the messages.
+    pub language: String,
+    /// The language's reserved words.
+    pub keywords: HashSet<String>,
+    /// Prefix of escaped keywords, like C#'s `@`.
+    pub keyword_prefix: String,
+}
+
+impl InvalidIdentifiers {
+    /// Creates the rule for `language`, with the keywords of its `NormalizeNames`.
+    pub fn new(language: impl Into<String>, names: NormalizeNames) -> Self {
+        let language = language.into();
+        let keywords = names.keywords;
+        let keyword_prefix = names.keyword_prefix;
+        Self { language, keywords, keyword_prefix }
+    }
+
+    /// Rust's identifiers.
+    pub fn rust() -> Self {
+        Self::new("Rust", NormalizeNames::rust())
+    }
+
+    /// Python's identifiers.
+    pub fn python() -> Self {
+        Self::new("Python", NormalizeNames::python())
+    }
+
+    /// JavaScript's identifiers.
+    pub fn javascript() -> Self {
+        Self::new("JavaScript", NormalizeNames::javascript())
+    }
+
+    /// TypeScript's identifiers.
+    pub fn typescript() -> Self {
+        Self::new("TypeScript", NormalizeNames::typescript())
+    }
+
+    /// C#'s identifiers.
+    pub fn csharp() -> Self {
+        Self::new("C#", NormalizeNames::csharp())
+    }
+
+    /// Whether `name` can be used as an identifier.
+    pub fn is_valid(&self, name: &str) -> bool {
+        let name = match name.strip_prefix(self.keyword_prefix.as_str()) {
+            Some(keyword) if !self.keyword_prefix.is_empty() && self.keywords.contains(keyword) => return true,
+            _ => name,
+        };
+        let mut characters = name.chars();
+        characters.next().is_some_and(|first| first.is_alphabetic() || first == '_')
+            && characters.all(|character| character.is_alphanumeric() || character == '_')
+            && !self.keywords.contains(name)
+    }
+
+    fn report(&self, location: &Location, identifier: &Identifier, diagnostics: &Diagnostics) {
+        if !self.is_valid(&identifier.name) {
+            let diagnostic = Diagnostic::error("invalid-identifier", format!("`{identifier}` isn't a valid {} identifier.", self.language))
+                .with_symbol(location.join(identifier.clone()));
+            diagnostics.report(diagnostic);
+        }
+    }
+}
+
+impl ValidationRule for InvalidIdentifiers {
+    fn check(&self, _registry: &Registry, library: &Library, diagnostics: &Diagnostics) {
+        modules(library, &mut |location, module| {
+            if let Some(parent) = location.parent() {
+                self.report(&parent, &module.identifier, diagnostics);
+            }
+            for object in &module.objects {
+                self.report(location, &object.identifier, diagnostics);
+            }
+            for function in &module.functions {
+                self.report(location, &function.identifier, diagnostics);
+                let location = location.join(function.identifier.clone());
+                function.inputs.iter().for_each(|parameter| self.report(&location, &parameter.identifier, diagnostics));
+            }
+            for definition in &module.types {
+                self.report(location, &definition.identifier, diagnostics);
+                let location = location.join(definition.identifier.clone());
+                match &definition.definition {
+                    KindDefinition::Structure(Structure { fields }) | KindDefinition::Union(Union { fields }) => fields
+                        .iter()
+                        .filter_map(|field| field.identifier.as_ref())
+                        .for_each(|identifier| self.report(&location, identifier, diagnostics)),
+                    KindDefinition::Enumeration(enumeration) => enumeration
+                        .variants
+                        .iter()
+                        .for_each(|variant| self.report(&location, &variant.identifier, diagnostics)),
+                    KindDefinition::TypeAlias(_) => (),
+                }
+            }
+            for interface in &module.interfaces {
+                self.report(location, &interface.identifier, diagnostics);
+                let location = location.join(interface.identifier.clone());
+                interface.objects.iter().for_each(|object| self.report(&location, &object.identifier, diagnostics));
+                interface.types.iter().for_each(|definition| self.report(&location, &definition.identifier, diagnostics));
+                interface.functions.iter().for_each(|function| self.report(&location, &function.identifier, diagnostics));
+                for method in &interface.methods {
+                    self.report(&location, &method.identifier, diagnostics);
+                    let location = location.join(method.identifier.clone());
+                    method.inputs.iter().for_each(|parameter| self.report(&location, &parameter.identifier, diagnostics));
+                }
+            }
+        });
+    }
+}
+
+/// Reports the constants whose literal doesn't fit their type as `incompatible-literal` errors.
+pub struct IncompatibleLiterals;
+
+impl ValidationRule for IncompatibleLiterals {
+    fn check(&self, _registry: &Registry, library: &Library, diagnostics: &Diagnostics) {
+        modules(library, &mut |location, module| {
+            let objects = module
+                .objects
+                .iter()
+                .map(|object| (location.clone(), object))
+                .chain(module.interfaces.iter().flat_map(|interface| {
+                    let location = location.join(interface.identifier.clone());
+                    interface.objects.iter().map(move |object| (location.clone(), object))
+                }));
+            for (location, object) in objects {
+                // Unknown literals are expressions the parser couldn't evaluate.
+                if matches!(object.literal, Literal::Unknown(_)) || object.literal.is_compatible_with(&object.type_) {
+                    continue;
+                }
+                let diagnostic = Diagnostic::error("incompatible-literal", format!("`{}` isn't a valid `{}`.", object.literal, object.type_))
+                    .with_symbol(location.join(object.identifier.clone()));
+                diagnostics.report(diagnostic);
+            }
+        });
+    }
+}
+
+/// Reports the enumerations without variants as `empty-enumeration` warnings, since many
+/// languages can't represent them.
+pub struct EmptyEnumerations;
+
+impl ValidationRule for EmptyEnumerations {
+    fn check(&self, _registry: &Registry, library: &Library, diagnostics: &Diagnostics) {
+        modules(library, &mut |location, module| {
+            for definition in &module.types {
+                if matches!(&definition.definition, KindDefinition::Enumeration(enumeration) if enumeration.variants.is_empty()) {
+                    let diagnostic = Diagnostic::warning("empty-enumeration", format!("`{}` has no variants.", definition.identifier))
+                        .with_symbol(location.join(definition.identifier.clone()));
+                    diagnostics.report(diagnostic);
+                }
+            }
+        });
+    }
+}
+
+/// Calls `visit` with every module of `library` and its location.
+fn modules(library: &Library, visit: &mut dyn FnMut(&Location, &Module)) {
+    fn module(location: &Location, module_: &Module, visit: &mut dyn FnMut(&Location, &Module)) {
+        visit(location, module_);
+        for child in &module_.modules {
+            module(&location.join(child.identifier.clone()), child, visit);
+        }
+    }
+    module(&Location::root(library.identifier.clone()), &library.root_module, visit);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::validator::{Validation, Validator};
+    use ligen_idl::{Enumeration, Function, Object, Parameter, Type, TypeDefinition};
+
+    #[test]
+    fn validation() {
+        let objects = vec![
+            Object { identifier: "MAX".into(), type_: Type::u64(), literal: Literal::String("many".into()), ..Default::default() },
+            Object { identifier: "MIN".into(), type_: Type::u64(), literal: Literal::UnsignedInteger(0), ..Default::default() },
+        ];
+        let parameter = Parameter { identifier: "type".into(), type_: Type::from("Missing"), ..Default::default() };
+        let functions = vec![
+            Function { identifier: "fetch".into(), inputs: vec![parameter], ..Default::default() },
+            Function { identifier: "fetch".into(), ..Default::default() },
+        ];
+        let empty = TypeDefinition { identifier: "Never".into(), definition: KindDefinition::Enumeration(Enumeration::default()), ..Default::default() };
+        let root_module = Module { identifier: "lib".into(), objects, functions, types: vec![empty], ..Default::default() };
+        let library = Library { identifier: "users".into(), root_module, ..Default::default() };
+        let registry = Registry { libraries: HashMap::from([(library.identifier.clone(), library.clone())]) };
+
+        let diagnostics = Validation::default().with(InvalidIdentifiers::rust()).check(&registry, &library);
+        let codes = diagnostics.iter().map(|diagnostic| diagnostic.code.as_str()).collect::<Vec<_>>();
+        assert_eq!(codes, ["unresolved-reference", "duplicate-identifier", "incompatible-literal", "empty-enumeration", "invalid-identifier"]);
+        assert_eq!(diagnostics[1].symbol.as_deref(), Some("users::fetch"));
+        assert_eq!(diagnostics[4].symbol.as_deref(), Some("users::fetch::type"));
+
+        assert!(Validation::default().validate(&registry, &Config::default()).is_err());
+        assert!(Validation::new().with(EmptyEnumerations).validate(&registry, &Config::default()).is_ok());
+    }
+
+    #[test]
+    fn escaped_keywords() {
+        assert!(InvalidIdentifiers::csharp().is_valid("@class"));
+        assert!(!InvalidIdentifiers::csharp().is_valid("class"));
+        assert!(!InvalidIdentifiers::rust().is_valid("2d"));
+        assert!(InvalidIdentifiers::rust().is_valid("type_"));
+    }
+}
@@ -4,6 +4,5 @@
 
 pub mod fs;
 pub mod prelude;
-pub mod visitors;
-pub mod transformers; // FIXME: We have another concept of transformers in the `ligen-transformer crate.
+pub mod transformers;
 pub mod mapper;
\ No newline at end of file
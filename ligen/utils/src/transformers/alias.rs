@@ -1,50 +1,44 @@
-use ligen_idl::{Import, Module, Library};
-use crate::transformers::Transform;
-use crate::visitors::{ImportVisitor, ModuleVisitor, LibraryVisitor};
+use ligen_idl::{Context, ItemMut, Library, visit_library_mut};
 
 // FIXME: Move this to ligen-rust.
 pub struct ReplaceCrateAlias;
 
-// FIXME: This logic is duplicated fro other transformers. This could be somehow generalized.
-
-impl Transform<Library, Library> for ReplaceCrateAlias {
-    fn transform(&self, data: &Library) -> Library {
-        let visitor = LibraryVisitor::from(data.clone());
-        <Self as Transform::<LibraryVisitor, Library>>::transform(self, &visitor)
-    }
-}
-
-impl Transform<LibraryVisitor, Library> for ReplaceCrateAlias {
-    fn transform(&self, data: &LibraryVisitor) -> Library {
-        let mut library = data.current.clone();
-        let visitor = ModuleVisitor::from(&data.child(data.current.root_module.clone()));
-        library.root_module = <Self as Transform::<ModuleVisitor, Module>>::transform(self, &visitor);
+impl ReplaceCrateAlias {
+    /// Replaces `crate` at the start of the imports with the root module's identifier.
+    pub fn transform(&self, data: &Library) -> Library {
+        let mut library = data.clone();
+        let root = library.root_module.identifier.clone();
+        visit_library_mut(&mut |_: &Context, item: ItemMut| {
+            if let ItemMut::Import(import) = item {
+                let first = import.path.first_mut();
+                if *first == "crate".into() {
+                    *first = root.clone().into();
+                }
+            }
+            true
+        }, &mut library);
         library
     }
 }
 
-impl Transform<ModuleVisitor, Module> for ReplaceCrateAlias {
-    fn transform(&self, data: &ModuleVisitor) -> Module {
-        let mut module = data.current.clone();
-        for (index, import) in data.imports.iter().enumerate() {
-            let visitor = data.child(import.clone());
-            module.imports[index] = <Self as Transform::<ImportVisitor, Import>>::transform(self, &visitor);
-        }
-        for (index, child_module) in data.modules.iter().enumerate() {
-            let visitor = ModuleVisitor::from(&data.child(child_module.clone()));
-            module.modules[index] = <Self as Transform::<ModuleVisitor, Module>>::transform(self, &visitor);
-        }
-        module
-    }
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_idl::{Import, Module};
+
+    #[test]
+    fn replace_crate_alias() {
+        let imports = vec![
+            Import { path: "crate::shapes::Point".into(), ..Default::default() },
+            Import { path: "std::fmt::Display".into(), ..Default::default() },
+        ];
+        let shapes = Module { identifier: "shapes".into(), imports, ..Default::default() };
+        let root_module = Module { identifier: "geometry".into(), modules: vec![shapes], ..Default::default() };
+        let library = Library { identifier: "geometry".into(), root_module, ..Default::default() };
 
-impl Transform<ImportVisitor, Import> for ReplaceCrateAlias {
-    fn transform(&self, data: &ImportVisitor) -> Import {
-        let mut import = data.current.clone();
-        let first = import.path.first_mut();
-        if *first == "crate".into() {
-            *first = data.parent_library().root_module.identifier.clone().into();
-        }
-        import
+        let library = ReplaceCrateAlias.transform(&library);
+        let imports = &library.root_module.modules[0].imports;
+        assert_eq!(imports[0].path, "geometry::shapes::Point".into());
+        assert_eq!(imports[1].path, "std::fmt::Display".into());
     }
 }
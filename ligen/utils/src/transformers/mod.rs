@@ -2,11 +2,20 @@ use ligen_idl::Library;
 
 pub mod path;
 pub mod alias;
+pub mod rules;
+pub mod naming;
+pub mod prune;
+pub mod wire;
 
+mod walk;
+
+#[deprecated(note = "The passes have an inherent `transform` method and implement `ligen_transformer::Transformer`.")]
 pub trait Transform<In: Sized, Out: Sized> {
     fn transform(&self, data: &In) -> Out;
 }
 
+#[deprecated(note = "Chain the passes' `transform` methods or run them as `ligen_transformer::Transformer`s.")]
+#[allow(deprecated)]
 pub trait Transformable: Sized + Clone {
     fn transform<T: Transform<Self, Self>>(&self, transform: T) -> Self {
         transform.transform(self)
@@ -21,4 +30,5 @@ pub trait Transformable: Sized + Clone {
     }
 }
 
+#[allow(deprecated)]
 impl Transformable for Library {}
\ No newline at end of file
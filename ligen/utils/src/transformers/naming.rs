@@ -0,0 +1,385 @@
+//! Naming convention normalization.
+
+use std::collections::{HashMap, HashSet};
+
+use ligen_common::{Diagnostic, Diagnostics};
+use ligen_idl::{
+    Attributes, Context, Field, Function, Group, Identifier, Interface, ItemMut, KindDefinition,
+    Library, Location, Method, Module, Named, NamingConvention, Object, Parameter, Path, Structure,
+    TypeDefinition, Union, Variant, VisitMut, visit_library_mut,
+};
+use crate::transformers::walk::rename_references;
+
+/// Rewrites the names of a `Library` to a target language's conventions.
+///
+/// Names that are keywords of the language are escaped. A renamed item gets a
+/// `#[ligen(name = "original")]` attribute, so bindings can still call the real symbol. If a new
+/// name collides with another item's in the same scope, the item keeps its name and a
+/// `naming-collision` warning is reported.
+#[derive(Debug, Clone)]
+pub struct NormalizeNames {
+    /// Convention of module names.
+    pub modules: NamingConvention,
+    /// Convention of type and interface names.
+    pub types: NamingConvention,
+    /// Convention of function names.
+    pub functions: NamingConvention,
+    /// Convention of method names.
+    pub methods: NamingConvention,
+    /// Convention of parameter names.
+    pub parameters: NamingConvention,
+    /// Convention of field names.
+    pub fields: NamingConvention,
+    /// Convention of enumeration variant names.
+    pub variants: NamingConvention,
+    /// Convention of constant and static names.
+    pub objects: NamingConvention,
+    /// The language's reserved words.
+    pub keywords: HashSet<String>,
+    /// Prefix added to names that are keywords.
+    pub keyword_prefix: String,
+    /// Suffix added to names that are keywords.
+    pub keyword_suffix: String,
+    /// Where naming collisions are reported.
+    pub diagnostics: Diagnostics,
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+    "use", "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro", "override",
+    "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in",
+    "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with",
+    "yield",
+];
+
+const JAVASCRIPT_KEYWORDS: &[&str] = &[
+    "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete",
+    "do", "else", "enum", "export", "extends", "false", "finally", "for", "function", "if",
+    "implements", "import", "in", "instanceof", "interface", "let", "new", "null", "package",
+    "private", "protected", "public", "return", "static", "super", "switch", "this", "throw", "true",
+    "try", "typeof", "var", "void", "while", "with", "yield",
+];
+
+const TYPESCRIPT_KEYWORDS: &[&str] = &[
+    "any", "boolean", "declare", "keyof", "module", "namespace", "never", "number", "readonly",
+    "string", "symbol", "type", "unknown",
+];
+
+const CSHARP_KEYWORDS: &[&str] = &[
+    "abstract", "as", "base", "bool", "break", "byte", "case", "catch", "char", "checked", "class",
+    "const", "continue", "decimal", "default", "delegate", "do", "double", "else", "enum", "event",
+    "explicit", "extern", "false", "finally", "fixed", "float", "for", "foreach", "goto", "if",
+    "implicit", "in", "int", "interface", "internal", "is", "lock", "long", "namespace", "new",
+    "null", "object", "operator", "out", "override", "params", "private", "protected", "public",
+    "readonly", "ref", "return", "sbyte", "sealed", "short", "sizeof", "stackalloc", "static",
+    "string", "struct", "switch", "this", "throw", "true", "try", "typeof", "uint", "ulong",
+    "unchecked", "unsafe", "ushort", "using", "virtual", "void", "volatile", "while",
+];
+
+impl NormalizeNames {
+    fn new(conventions: [NamingConvention; 8], keywords: &[&[&str]]) -> Self {
+        let [modules, types, functions, methods, parameters, fields, variants, objects] = conventions;
+        let keywords = keywords.iter().copied().flatten().map(|keyword| keyword.to_string()).collect();
+        let keyword_prefix = String::new();
+        let keyword_suffix = "_".to_string();
+        let diagnostics = Default::default();
+        Self { modules, types, functions, methods, parameters, fields, variants, objects, keywords, keyword_prefix, keyword_suffix, diagnostics }
+    }
+
+    /// snake_case items, PascalCase types and SCREAMING_SNAKE_CASE constants.
+    pub fn rust() -> Self {
+        use NamingConvention::*;
+        Self::new([SnakeCase, PascalCase, SnakeCase, SnakeCase, SnakeCase, SnakeCase, PascalCase, ScreamingSnakeCase], &[RUST_KEYWORDS])
+    }
+
+    /// snake_case items, PascalCase classes and SCREAMING_SNAKE_CASE constants and enum members.
+    pub fn python() -> Self {
+        use NamingConvention::*;
+        Self::new([SnakeCase, PascalCase, SnakeCase, SnakeCase, SnakeCase, SnakeCase, ScreamingSnakeCase, ScreamingSnakeCase], &[PYTHON_KEYWORDS])
+    }
+
+    /// camelCase items, PascalCase types and enum members and SCREAMING_SNAKE_CASE constants.
+    pub fn javascript() -> Self {
+        use NamingConvention::*;
+        Self::new([CamelCase, PascalCase, CamelCase, CamelCase, CamelCase, CamelCase, PascalCase, ScreamingSnakeCase], &[JAVASCRIPT_KEYWORDS])
+    }
+
+    /// JavaScript's conventions, also escaping TypeScript's keywords.
+    pub fn typescript() -> Self {
+        Self { keywords: Self::javascript().keywords.into_iter().chain(TYPESCRIPT_KEYWORDS.iter().map(|keyword| keyword.to_string())).collect(), ..Self::javascript() }
+    }
+
+    /// PascalCase items and camelCase parameters. Keywords are escaped as `@keyword`.
+    pub fn csharp() -> Self {
+        use NamingConvention::*;
+        let mut normalize = Self::new([PascalCase, PascalCase, PascalCase, PascalCase, CamelCase, PascalCase, PascalCase, PascalCase], &[CSHARP_KEYWORDS]);
+        normalize.keyword_prefix = "@".into();
+        normalize.keyword_suffix = String::new();
+        normalize
+    }
+
+    /// Reports the naming collisions to `diagnostics`.
+    pub fn with_diagnostics(mut self, diagnostics: Diagnostics) -> Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+
+    /// `identifier` in the `convention`, escaped if it's a keyword.
+    pub fn name(&self, identifier: &Identifier, convention: &NamingConvention) -> Identifier {
+        let mut identifier = identifier.to_naming_convention(convention);
+        if self.keywords.contains(&identifier.name) {
+            identifier.name = format!("{}{}{}", self.keyword_prefix, identifier.name, self.keyword_suffix);
+        }
+        identifier
+    }
+
+    /// Renames the items of `module`, at `path`, but not the ones of its submodules, returning
+    /// the submodules' identifiers before renaming. `location` is the module's location before
+    /// renaming, which `renamed_types` are keyed by.
+    fn module(&self, path: &Path, location: &Location, module: &mut Module, renamed_types: &mut HashMap<Location, Identifier>) -> Vec<Identifier> {
+        self.scope(path, &mut module.objects, &self.objects);
+        let renamed = self.scope(path, &mut module.types, &self.types);
+        renamed_types.extend(renamed.into_iter().map(|(old, new)| (location.join(old), new)));
+        self.definitions(path, &mut module.types);
+        self.functions(path, &mut module.functions);
+        for interface in &mut module.interfaces {
+            let path = path.clone().join(interface.identifier.clone());
+            self.scope(&path, &mut interface.objects, &self.objects);
+            self.scope(&path, &mut interface.types, &self.types);
+            self.definitions(&path, &mut interface.types);
+            self.functions(&path, &mut interface.functions);
+            self.scope(&path, &mut interface.methods, &self.methods);
+            for method in &mut interface.methods {
+                let path = path.clone().join(method.identifier.clone());
+                self.scope(&path, &mut method.inputs, &self.parameters);
+            }
+        }
+        // Interfaces named after a type were renamed with it.
+        let types = module.types.iter().map(|definition| definition.identifier.clone()).collect::<HashSet<_>>();
+        let mut interfaces = module
+            .interfaces
+            .iter_mut()
+            .filter(|interface| !renamed_types.contains_key(&location.join(interface.identifier.clone())) && !types.contains(&interface.identifier))
+            .collect::<Vec<_>>();
+        self.scope(path, &mut interfaces, &self.types);
+        let originals = module.modules.iter().map(|child| child.identifier.clone()).collect();
+        self.scope(path, &mut module.modules, &self.modules);
+        originals
+    }
+
+    fn definitions(&self, path: &Path, definitions: &mut [TypeDefinition]) {
+        for definition in definitions {
+            let path = path.clone().join(definition.identifier.clone());
+            match &mut definition.definition {
+                KindDefinition::Structure(Structure { fields }) | KindDefinition::Union(Union { fields }) => {
+                    self.scope(&path, fields, &self.fields);
+                },
+                KindDefinition::Enumeration(enumeration) => {
+                    self.scope(&path, &mut enumeration.variants, &self.variants);
+                },
+                KindDefinition::TypeAlias(_) => (),
+            }
+        }
+    }
+
+    fn functions(&self, path: &Path, functions: &mut [Function]) {
+        self.scope(path, functions, &self.functions);
+        for function in functions {
+            let path = path.clone().join(function.identifier.clone());
+            self.scope(&path, &mut function.inputs, &self.parameters);
+        }
+    }
+
+    /// Renames the `items` of a scope, returning the renamed ones as `(old, new)`.
+    fn scope<T: Renameable>(&self, path: &Path, items: &mut [T], convention: &NamingConvention) -> Vec<(Identifier, Identifier)> {
+        let names = items
+            .iter_mut()
+            .map(|item| item.identifier().map(|identifier| {
+                let name = self.name(identifier, convention);
+                (identifier.clone(), name)
+            }))
+            .collect::<Vec<_>>();
+        let mut taken = names
+            .iter()
+            .flatten()
+            .filter(|(old, new)| old == new)
+            .map(|(old, _)| old.clone())
+            .collect::<HashSet<_>>();
+        let mut renamed = Vec::new();
+        for (item, names) in items.iter_mut().zip(names) {
+            let Some((old, new)) = names else { continue };
+            if old == new {
+                continue;
+            }
+            if !taken.insert(new.clone()) {
+                let diagnostic = Diagnostic::warning("naming-collision", format!("`{old}` isn't renamed to `{new}` because the name is taken."))
+                    .with_symbol(path.clone().join(old.clone()));
+                self.diagnostics.report(diagnostic);
+                taken.insert(old);
+                continue;
+            }
+            if let Some(attributes) = item.attributes().filter(|attributes| attributes.get_literal_from_path("ligen::name").is_none()) {
+                attributes.push(Group::new("ligen", Named::new("name", old.name.clone())).into());
+            }
+            if let Some(identifier) = item.identifier() {
+                *identifier = new.clone();
+            }
+            renamed.push((old, new));
+        }
+        renamed
+    }
+}
+
+impl NormalizeNames {
+    /// Renames the items of `data` and updates the references to them.
+    pub fn transform(&self, data: &Library) -> Library {
+        let mut library = data.clone();
+        let root = Location::root(library.identifier.clone());
+        let mut normalize = Normalize { names: self, originals: HashMap::from([(root.clone(), root)]), renamed: HashMap::new() };
+        visit_library_mut(&mut normalize, &mut library);
+        rename_references(data, &mut library, &normalize.renamed);
+        library
+    }
+}
+
+/// Renames the items of each module when it's visited. Modules are renamed by their parent, so
+/// the root module keeps its name.
+struct Normalize<'a> {
+    names: &'a NormalizeNames,
+    /// Locations of the modules before renaming, by their new locations.
+    originals: HashMap<Location, Location>,
+    /// New names of the types and modules, by their locations before renaming.
+    renamed: HashMap<Location, Identifier>,
+}
+
+impl VisitMut for Normalize<'_> {
+    fn visit_item(&mut self, context: &Context, item: ItemMut<'_>) -> bool {
+        if let ItemMut::Module(module) = item {
+            let location = self.originals.get(&context.module).cloned().unwrap_or_else(|| context.module.clone());
+            let originals = self.names.module(&context.path, &location, module, &mut self.renamed);
+            for (child, original) in module.modules.iter().zip(originals) {
+                if child.identifier != original {
+                    self.renamed.insert(location.join(original.clone()), child.identifier.clone());
+                }
+                self.originals.insert(context.module.join(child.identifier.clone()), location.join(original));
+            }
+        }
+        true
+    }
+}
+
+/// An item with a name.
+trait Renameable {
+    fn identifier(&mut self) -> Option<&mut Identifier>;
+    fn attributes(&mut self) -> Option<&mut Attributes>;
+}
+
+impl<T: Renameable> Renameable for &mut T {
+    fn identifier(&mut self) -> Option<&mut Identifier> {
+        (**self).identifier()
+    }
+
+    fn attributes(&mut self) -> Option<&mut Attributes> {
+        (**self).attributes()
+    }
+}
+
+macro_rules! renameable {
+    ($($type_:ty),*) => {
+        $(impl Renameable for $type_ {
+            fn identifier(&mut self) -> Option<&mut Identifier> {
+                Some(&mut self.identifier)
+            }
+
+            fn attributes(&mut self) -> Option<&mut Attributes> {
+                Some(&mut self.attributes)
+            }
+        })*
+    };
+}
+
+renameable!(Module, TypeDefinition, Variant, Object, Function, Method, Parameter, Interface);
+
+impl Renameable for Field {
+    fn identifier(&mut self) -> Option<&mut Identifier> {
+        self.identifier.as_mut()
+    }
+
+    fn attributes(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_idl::{Structure, Type};
+
+    #[test]
+    fn normalize() {
+        let point = TypeDefinition {
+            identifier: "point_2d".into(),
+            definition: KindDefinition::Structure(Structure { fields: vec![Field { identifier: Some("type".into()), type_: Type::f32(), ..Default::default() }] }),
+            ..Default::default()
+        };
+        let functions = vec![
+            Function { identifier: "getPoint".into(), output: Some(Type::from("point_2d")), ..Default::default() },
+            Function { identifier: "get_point".into(), ..Default::default() },
+        ];
+        let root_module = Module { identifier: "lib".into(), types: vec![point], functions, ..Default::default() };
+        let library = Library { identifier: "geometry".into(), root_module, ..Default::default() };
+
+        let normalize = NormalizeNames::rust();
+        let library = normalize.transform(&library);
+        let module = &library.root_module;
+        assert_eq!(module.identifier, "lib".into());
+        assert_eq!(module.types[0].identifier, "Point2d".into());
+        assert_eq!(module.types[0].attributes.get_literal_from_path("ligen::name"), Some(&"point_2d".into()));
+        let KindDefinition::Structure(structure) = &module.types[0].definition else {
+            panic!("Expected a structure.");
+        };
+        assert_eq!(structure.fields[0].identifier, Some("type_".into()));
+        assert_eq!(module.functions[0].identifier, "getPoint".into());
+        assert_eq!(module.functions[0].output, Some(Type::from("Point2d")));
+        assert_eq!(module.functions[1].identifier, "get_point".into());
+        assert_eq!(normalize.diagnostics.to_vec()[0].code, "naming-collision");
+    }
+
+    #[test]
+    fn submodules() {
+        let circle = TypeDefinition { identifier: "circle_shape".into(), ..Default::default() };
+        let kind = TypeDefinition { identifier: "item_kind".into(), ..Default::default() };
+        let interface = Interface { identifier: "circle_shape".into(), types: vec![kind], ..Default::default() };
+        let shapes = Module { identifier: "Shapes".into(), types: vec![circle], interfaces: vec![interface], ..Default::default() };
+        let get = Function { identifier: "get".into(), output: Some(Type::from("Shapes::circle_shape")), ..Default::default() };
+        let root_module = Module { identifier: "lib".into(), modules: vec![shapes], functions: vec![get], ..Default::default() };
+        let library = Library { identifier: "geometry".into(), root_module, ..Default::default() };
+
+        let library = NormalizeNames::rust().transform(&library);
+        let shapes = &library.root_module.modules[0];
+        assert_eq!(shapes.identifier, "shapes".into());
+        assert_eq!(shapes.types[0].identifier, "CircleShape".into());
+        assert_eq!(shapes.interfaces[0].identifier, "CircleShape".into());
+        assert_eq!(shapes.interfaces[0].types[0].identifier, "ItemKind".into());
+        assert_eq!(library.root_module.functions[0].output, Some(Type::from("shapes::CircleShape")));
+    }
+
+    #[test]
+    fn objects() {
+        let objects = vec![Object { identifier: "maxSides".into(), type_: Type::u32(), ..Default::default() }];
+        let root_module = Module { identifier: "lib".into(), objects, ..Default::default() };
+        let library = Library { identifier: "geometry".into(), root_module, ..Default::default() };
+
+        let library = NormalizeNames::rust().transform(&library);
+        let object = &library.root_module.objects[0];
+        assert_eq!(object.identifier, "MAX_SIDES".into());
+        assert_eq!(object.attributes.get_literal_from_path("ligen::name"), Some(&"maxSides".into()));
+    }
+}
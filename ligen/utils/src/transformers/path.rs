@@ -1,73 +1,74 @@
-use ligen_idl::{Function, Import, Module, Library, Type};
-use crate::transformers::Transform;
-use crate::visitors::{FunctionVisitor, ImportVisitor, ModuleVisitor, LibraryVisitor};
+use ligen_idl::{Context, ItemMut, Library, Location, Path, Registry, Resolver, Type, VisitMut, visit_library_mut};
 
 pub struct RelativePathToAbsolutePath;
 
-impl Transform<Library, Library> for RelativePathToAbsolutePath {
-    fn transform(&self, data: &Library) -> Library {
-        let data = data.clone();
-        // TODO: We need to review this process.
-        // data.root_module.guarantee_absolute_paths();
-        let visitor = LibraryVisitor::from(data);
-        <Self as Transform<LibraryVisitor, Library>>::transform(self, &visitor)
+impl RelativePathToAbsolutePath {
+    /// Makes the import and type paths resolving to items of `data` absolute, starting with
+    /// the root module.
+    pub fn transform(&self, data: &Library) -> Library {
+        let mut registry = Registry::new();
+        registry.libraries.insert(data.identifier.clone(), data.clone());
+        let mut library = data.clone();
+        let mut visitor = Absolute { resolver: Resolver::new(&registry), root: data.root_module.identifier.clone().into() };
+        visit_library_mut(&mut visitor, &mut library);
+        library
     }
 }
 
-impl Transform<LibraryVisitor, Library> for RelativePathToAbsolutePath {
-    fn transform(&self, data: &LibraryVisitor) -> Library {
-        let mut library = data.current.clone();
-        let visitor = ModuleVisitor::from(&data.child(data.current.root_module.clone()));
-        library.root_module = <Self as Transform::<ModuleVisitor, Module>>::transform(self, &visitor);
-        library
-    }
+struct Absolute<'a> {
+    resolver: Resolver<'a>,
+    root: Path,
 }
 
-impl Transform<ModuleVisitor, Module> for RelativePathToAbsolutePath {
-    fn transform(&self, data: &ModuleVisitor) -> Module {
-        let mut module = data.current.clone();
-        for (index, import) in data.current.imports.iter().enumerate() {
-            let visitor = data.child(import.clone());
-            module.imports[index] = <Self as Transform::<ImportVisitor, Import>>::transform(self, &visitor);
-        }
-        for (index, function) in data.current.functions.iter().enumerate() {
-            let visitor = data.child(function.clone());
-            module.functions[index] = <Self as Transform::<FunctionVisitor, Function>>::transform(self, &visitor);
-        }
-        for (index, child_module) in data.current.modules.iter().enumerate() {
-            let visitor = ModuleVisitor::from(&data.child(child_module.clone()));
-            module.modules[index] = <Self as Transform::<ModuleVisitor, Module>>::transform(self, &visitor);
-        }
-        module
+impl Absolute<'_> {
+    fn absolute_path(&self, scope: &Location, path: &Path) -> Option<Path> {
+        // External paths are already absolute.
+        let location = self.resolver.resolve(scope, path)?;
+        Some(self.root.clone().join(location.path))
     }
 }
 
-impl Transform<ImportVisitor, Import> for RelativePathToAbsolutePath {
-    fn transform(&self, data: &ImportVisitor) -> Import {
-        let mut import = data.current.clone();
-        if let Some(absolute_path) = data.find_absolute_path() {
-            import.path = absolute_path;
+impl VisitMut for Absolute<'_> {
+    fn visit_item(&mut self, context: &Context, item: ItemMut<'_>) -> bool {
+        if let ItemMut::Import(import) = item {
+            if let Some(path) = self.absolute_path(&context.module, &import.path) {
+                import.path = path;
+            }
         }
-        import
+        true
     }
-}
 
-impl Transform<FunctionVisitor, Function> for RelativePathToAbsolutePath {
-    fn transform(&self, data: &FunctionVisitor) -> Function {
-        let mut function = data.current.clone();
-        if let Some(output) = function.output.as_mut() {
-            type_to_absolute_path(&data.parent, output);
-        }
-        for input in &mut function.inputs {
-            type_to_absolute_path(&data.parent, &mut input.type_);
+    fn visit_type(&mut self, context: &Context, type_: &mut Type) -> bool {
+        if let Some(mut path) = self.absolute_path(&context.module, &type_.path) {
+            if let Some(segment) = path.segments.last_mut() {
+                segment.generics = type_.path.last().generics.clone();
+            }
+            type_.path = path;
         }
-        function
+        true
     }
 }
 
-fn type_to_absolute_path(module_visitor: &ModuleVisitor, type_: &mut Type) {
-    let path = &mut type_.path;
-    if let Some(absolute_path) = module_visitor.find_absolute_path(path) {
-        *path = absolute_path
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_idl::{Function, Import, Module, TypeDefinition};
+
+    #[test]
+    fn absolute_paths() {
+        let point = TypeDefinition { identifier: "Point".into(), ..Default::default() };
+        let shapes = Module { identifier: "shapes".into(), types: vec![point], ..Default::default() };
+        let origin = Function { identifier: "origin".into(), output: Some(Type::option(Type::from("Point"))), ..Default::default() };
+        let now = Function { identifier: "now".into(), output: Some(Type::from("std::time::Instant")), ..Default::default() };
+        let imports = vec![Import { path: "super::shapes::Point".into(), ..Default::default() }];
+        let points = Module { identifier: "points".into(), imports, functions: vec![origin, now], ..Default::default() };
+        let root_module = Module { identifier: "geometry".into(), modules: vec![shapes, points], ..Default::default() };
+        let library = Library { identifier: "geometry".into(), root_module, ..Default::default() };
+
+        let library = RelativePathToAbsolutePath.transform(&library);
+        let points = &library.root_module.modules[1];
+        assert_eq!(points.imports[0].path, "geometry::shapes::Point".into());
+        assert_eq!(points.functions[0].output, Some(Type::option(Type::from("geometry::shapes::Point"))));
+        assert_eq!(points.functions[1].output, Some(Type::from("std::time::Instant")));
     }
-}
\ No newline at end of file
+}
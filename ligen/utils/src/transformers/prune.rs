@@ -0,0 +1,387 @@
+//! Dead-symbol pruning.
+
+use std::collections::HashSet;
+
+use crate::prelude::*;
+use ligen_idl::{
+    Attributes, Function, Identifier, Interface, Library, Location, Method, Module, Object, Path,
+    Registry, Resolver, Type, Visibility,
+};
+
+/// Prunes a `Registry` down to what a library's public API needs.
+///
+/// The public functions, interfaces and constants of the `root` library are kept, along with the
+/// `TypeDefinition`s they transitively reference across the registry, and the interfaces
+/// implementing those types. A kept interface keeps the type it implements and its traits. Public items are only part of the API if their modules are public
+/// too, or if a public module re-exports them with `pub use`. Everything else is removed, as are
+/// the modules and dependency libraries left empty.
+///
+/// With `keep_marked`, items with a `#[ligen]` attribute are kept too, along with what they
+/// reference. `NormalizeNames` adds `#[ligen(name = "...")]` to the items it renames, so pruning
+/// should run before it.
+#[derive(Debug, Clone)]
+pub struct Prune {
+    /// The library whose public API is kept.
+    pub root: Identifier,
+    /// Whether to keep the items with a `#[ligen]` attribute.
+    pub keep_marked: bool,
+}
+
+impl Prune {
+    /// Prunes everything not reachable from the public API of `root`.
+    pub fn new(root: impl Into<Identifier>) -> Self {
+        let root = root.into();
+        let keep_marked = false;
+        Self { root, keep_marked }
+    }
+
+    /// Also keeps the items with a `#[ligen]` attribute.
+    pub fn keep_marked(mut self) -> Self {
+        self.keep_marked = true;
+        self
+    }
+
+    /// Computes the locations of the reachable `TypeDefinition`s.
+    pub fn reachable(&self, registry: &Registry) -> HashSet<Location> {
+        let resolver = Resolver::new(registry);
+        let api = Api::new(&resolver, &self.root);
+        self.reachable_from(registry, &resolver, &api)
+    }
+
+    fn reachable_from(&self, registry: &Registry, resolver: &Resolver, api: &Api) -> HashSet<Location> {
+        let mut reachability = Reachability { resolver, reachable: HashSet::new(), pending: Vec::new() };
+        for library in registry.libraries.values() {
+            modules(&library.root_module, &Location::root(library.identifier.clone()), &mut |location, module| {
+                for definition in &module.types {
+                    // Types are roots only if they are marked. Otherwise, they are kept if they are used.
+                    if self.is_marked(&definition.attributes) {
+                        reachability.pending.push(location.join(definition.identifier.clone()));
+                    }
+                }
+                for object in module.objects.iter().filter(|object| self.is_kept_object(library, location, object, api)) {
+                    reachability.type_(location, &object.type_);
+                }
+                for function in module.functions.iter().filter(|function| self.is_kept_function(library, location, function, api)) {
+                    reachability.function(location, function);
+                }
+                for interface in module.interfaces.iter().filter(|interface| self.is_kept_interface(library, location, interface, api)) {
+                    reachability.interface(location, interface);
+                }
+            });
+        }
+        reachability.run();
+        reachability.reachable
+    }
+
+    fn is_root(&self, library: &Library) -> bool {
+        library.identifier == self.root
+    }
+
+    fn is_marked(&self, attributes: &Attributes) -> bool {
+        self.keep_marked && attributes.get_group("ligen").is_some() && !attributes.has_ignore_attribute()
+    }
+
+    fn is_kept_object(&self, library: &Library, location: &Location, object: &Object, api: &Api) -> bool {
+        (self.is_root(library) && api.contains(location, &object.identifier, &object.visibility)) || self.is_marked(&object.attributes)
+    }
+
+    fn is_kept_function(&self, library: &Library, location: &Location, function: &Function, api: &Api) -> bool {
+        (self.is_root(library) && api.contains(location, &function.identifier, &function.visibility)) || self.is_marked(&function.attributes)
+    }
+
+    fn is_kept_interface(&self, library: &Library, location: &Location, interface: &Interface, api: &Api) -> bool {
+        (self.is_root(library) && api.contains(location, &interface.identifier, &interface.visibility)) || self.is_marked(&interface.attributes)
+    }
+
+    fn module(&self, library: &Library, location: &Location, module: &mut Module, reachable: &HashSet<Location>, api: &Api) {
+        let types = |identifier: &Identifier| reachable.contains(&location.join(identifier.clone()));
+        module.types.retain(|definition| types(&definition.identifier));
+        module.objects.retain(|object| self.is_kept_object(library, location, object, api));
+        module.functions.retain(|function| self.is_kept_function(library, location, function, api));
+        module.interfaces.retain(|interface| types(&interface.identifier) || self.is_kept_interface(library, location, interface, api));
+        for child in &mut module.modules {
+            self.module(library, &location.join(child.identifier.clone()), child, reachable, api);
+        }
+        module.modules.retain(|child| !is_empty(child));
+    }
+}
+
+impl Prune {
+    /// Keeps the items of `data` that the root library's API reaches. Fails if the root library
+    /// isn't in `data`.
+    pub fn transform(&self, data: &Registry) -> Result<Registry> {
+        if !data.libraries.contains_key(&self.root) {
+            return Err(Error::Message(format!("The library `{}` to prune for isn't in the registry.", self.root)));
+        }
+        let resolver = Resolver::new(data);
+        let api = Api::new(&resolver, &self.root);
+        let reachable = self.reachable_from(data, &resolver, &api);
+        let mut registry = data.clone();
+        for library in registry.libraries.values_mut() {
+            let location = Location::root(library.identifier.clone());
+            let mut root_module = std::mem::take(&mut library.root_module);
+            self.module(library, &location, &mut root_module, &reachable, &api);
+            library.root_module = root_module;
+        }
+        registry.libraries.retain(|identifier, library| *identifier == self.root || !is_empty(&library.root_module));
+        Ok(registry)
+    }
+}
+
+/// Where the public API of the root library is.
+struct Api {
+    /// The modules reachable from the library's root through public modules.
+    modules: HashSet<Location>,
+    /// The items re-exported by the imports of those modules.
+    items: HashSet<Location>,
+}
+
+impl Api {
+    fn new(resolver: &Resolver, root: &Identifier) -> Self {
+        let mut api = Self { modules: HashSet::new(), items: HashSet::new() };
+        if let Some(library) = resolver.library(root) {
+            api.module(resolver, &Location::root(library.identifier.clone()), &library.root_module);
+        }
+        api
+    }
+
+    fn module(&mut self, resolver: &Resolver, location: &Location, module: &Module) {
+        self.modules.insert(location.clone());
+        for import in module.imports.iter().filter(|import| import.visibility == Visibility::Public && !import.path.is_empty()) {
+            if import.path.last().identifier == "*" {
+                // Glob re-exports make all the public items of the module part of the API.
+                if let Some(target) = resolver.resolve(location, &import.path.clone().without_last()) {
+                    self.modules.insert(target);
+                }
+            } else if let Some(target) = resolver.resolve(location, &import.path) {
+                self.items.insert(target);
+            }
+        }
+        for child in module.modules.iter().filter(|child| child.visibility == Visibility::Public) {
+            self.module(resolver, &location.join(child.identifier.clone()), child);
+        }
+    }
+
+    /// Whether the item `identifier`, in the module at `module`, is part of the API.
+    fn contains(&self, module: &Location, identifier: &Identifier, visibility: &Visibility) -> bool {
+        *visibility == Visibility::Public && (self.modules.contains(module) || self.items.contains(&module.join(identifier.clone())))
+    }
+}
+
+/// Walks the references from the roots to the `TypeDefinition`s.
+struct Reachability<'a> {
+    resolver: &'a Resolver<'a>,
+    reachable: HashSet<Location>,
+    pending: Vec<Location>,
+}
+
+impl Reachability<'_> {
+    fn run(&mut self) {
+        while let Some(location) = self.pending.pop() {
+            if self.reachable.contains(&location) {
+                continue;
+            }
+            let (Some(scope), Some(segment)) = (location.parent(), location.path.segments.last()) else {
+                continue;
+            };
+            // Interfaces named after a type are its `impl` blocks. The ones without a type are traits.
+            let interfaces = self
+                .resolver
+                .module(&scope)
+                .map(|module| module.interfaces.iter().filter(|interface| interface.identifier == segment.identifier).collect::<Vec<_>>())
+                .unwrap_or_default();
+            let definition = self.resolver.type_definition(&location);
+            if definition.is_none() && interfaces.is_empty() {
+                continue;
+            }
+            self.reachable.insert(location.clone());
+            if let Some(definition) = definition {
+                for type_ in definition.definition.types() {
+                    self.type_(&scope, type_);
+                }
+            }
+            for interface in interfaces {
+                self.interface(&scope, interface);
+            }
+        }
+    }
+
+    /// Where `path` points to from `scope`. The resolver doesn't resolve traits, so paths it can't
+    /// resolve are taken as relative to `scope`.
+    fn locate(&self, scope: &Location, path: &Path) -> Location {
+        self.resolver.resolve(scope, path).unwrap_or_else(|| {
+            path.segments.iter().fold(scope.clone(), |location, segment| location.join(segment.identifier.clone()))
+        })
+    }
+
+    fn type_(&mut self, scope: &Location, type_: &Type) {
+        if let Some(location) = self.resolver.resolve(scope, &type_.path) {
+            self.pending.push(location);
+        }
+        for segment in &type_.path.segments {
+            for type_ in &segment.generics.types {
+                self.type_(scope, type_);
+            }
+        }
+    }
+
+    fn function(&mut self, scope: &Location, function: &Function) {
+        for parameter in &function.inputs {
+            self.type_(scope, &parameter.type_);
+        }
+        if let Some(output) = &function.output {
+            self.type_(scope, output);
+        }
+    }
+
+    fn method(&mut self, scope: &Location, method: &Method) {
+        for parameter in &method.inputs {
+            self.type_(scope, &parameter.type_);
+        }
+        if let Some(output) = &method.output {
+            self.type_(scope, output);
+        }
+    }
+
+    fn interface(&mut self, scope: &Location, interface: &Interface) {
+        // The type an `impl` block implements, and the traits it implements for it.
+        let type_ = self.locate(scope, &interface.identifier.clone().into());
+        self.pending.push(type_);
+        for path in &interface.interfaces {
+            let trait_ = self.locate(scope, path);
+            self.pending.push(trait_);
+        }
+        for object in &interface.objects {
+            self.type_(scope, &object.type_);
+        }
+        for type_ in interface.types.iter().flat_map(|definition| definition.definition.types()) {
+            self.type_(scope, type_);
+        }
+        for function in &interface.functions {
+            self.function(scope, function);
+        }
+        for method in &interface.methods {
+            self.method(scope, method);
+        }
+    }
+}
+
+fn is_empty(module: &Module) -> bool {
+    module.types.is_empty()
+        && module.objects.is_empty()
+        && module.functions.is_empty()
+        && module.interfaces.is_empty()
+        && module.modules.is_empty()
+}
+
+/// Calls `visit` with `module`, its submodules and their locations.
+fn modules(module: &Module, location: &Location, visit: &mut dyn FnMut(&Location, &Module)) {
+    visit(location, module);
+    for child in &module.modules {
+        modules(child, &location.join(child.identifier.clone()), visit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use ligen_idl::{Attribute, Field, Group, Import, KindDefinition, Parameter, Structure, TypeDefinition};
+
+    fn structure(identifier: &str, fields: Vec<Type>) -> TypeDefinition {
+        let fields = fields.into_iter().map(|type_| Field { type_, ..Default::default() }).collect();
+        TypeDefinition {
+            identifier: identifier.into(),
+            visibility: Visibility::Public,
+            definition: KindDefinition::Structure(Structure { fields }),
+            ..Default::default()
+        }
+    }
+
+    fn registry() -> Registry {
+        let id = structure("Id", vec![]);
+        let unused = structure("Unused", vec![]);
+        let mut marked = structure("Marked", vec![]);
+        marked.attributes.push(Attribute::Group(Group::from("ligen")));
+        let uuid = Module { identifier: "uuid".into(), visibility: Visibility::Public, types: vec![id, unused, marked], ..Default::default() };
+        let dependency = Library { identifier: "uuid".into(), root_module: uuid, ..Default::default() };
+        let unrelated = Library { identifier: "regex".into(), root_module: Module { types: vec![structure("Regex", vec![])], ..Default::default() }, ..Default::default() };
+
+        let user = structure("User", vec![Type::vector(Type::from("uuid::Id"))]);
+        let secret = structure("Secret", vec![]);
+        let parameter = Parameter { identifier: "user".into(), type_: Type::from("User"), ..Default::default() };
+        let save = Function { identifier: "save".into(), visibility: Visibility::Public, inputs: vec![parameter], ..Default::default() };
+        let hash = Function { identifier: "hash".into(), output: Some(Type::from("Secret")), ..Default::default() };
+        let imports = vec![Import { path: "uuid::Id".into(), ..Default::default() }];
+        let root_module = Module { identifier: "lib".into(), imports, types: vec![user, secret], functions: vec![save, hash], ..Default::default() };
+        let library = Library { identifier: "users".into(), root_module, ..Default::default() };
+        let libraries = [library, dependency, unrelated].into_iter().map(|library| (library.identifier.clone(), library)).collect::<HashMap<_, _>>();
+        Registry { libraries }
+    }
+
+    #[test]
+    fn prune() {
+        let registry = Prune::new("users").transform(&registry()).unwrap();
+        assert_eq!(registry.libraries.len(), 2);
+        let users = &registry.libraries[&Identifier::from("users")].root_module;
+        assert_eq!(users.functions.iter().map(|function| function.identifier.name.as_str()).collect::<Vec<_>>(), ["save"]);
+        assert_eq!(users.types.iter().map(|definition| definition.identifier.name.as_str()).collect::<Vec<_>>(), ["User"]);
+        let uuid = &registry.libraries[&Identifier::from("uuid")].root_module;
+        assert_eq!(uuid.types.iter().map(|definition| definition.identifier.name.as_str()).collect::<Vec<_>>(), ["Id"]);
+
+        let registry = Prune::new("users").keep_marked().transform(&self::registry()).unwrap();
+        let uuid = &registry.libraries[&Identifier::from("uuid")].root_module;
+        assert_eq!(uuid.types.iter().map(|definition| definition.identifier.name.as_str()).collect::<Vec<_>>(), ["Id", "Marked"]);
+    }
+
+    #[test]
+    fn private_modules() {
+        let function = |identifier: &str, output: &str| Function { identifier: identifier.into(), visibility: Visibility::Public, output: Some(Type::from(output)), ..Default::default() };
+        let internal = Module {
+            identifier: "internal".into(),
+            visibility: Visibility::Private,
+            types: vec![structure("Token", vec![]), structure("Key", vec![])],
+            functions: vec![function("token", "Token"), function("key", "Key")],
+            ..Default::default()
+        };
+        let imports = vec![Import { path: "internal::key".into(), visibility: Visibility::Public, ..Default::default() }];
+        let root_module = Module { identifier: "lib".into(), imports, modules: vec![internal], ..Default::default() };
+        let library = Library { identifier: "auth".into(), root_module, ..Default::default() };
+        let registry = Registry { libraries: HashMap::from([(library.identifier.clone(), library)]) };
+
+        let pruned = Prune::new("auth").transform(&registry).unwrap();
+        let internal = &pruned.libraries[&Identifier::from("auth")].root_module.modules[0];
+        assert_eq!(internal.functions.iter().map(|function| function.identifier.name.as_str()).collect::<Vec<_>>(), ["key"]);
+        assert_eq!(internal.types.iter().map(|definition| definition.identifier.name.as_str()).collect::<Vec<_>>(), ["Key"]);
+        assert!(Prune::new("users").transform(&registry).is_err());
+    }
+
+    #[test]
+    fn receiver_methods() {
+        let increment = Method { identifier: "increment".into(), receiver: ligen_idl::Receiver::Mutable, ..Default::default() };
+        let counter = Interface {
+            identifier: "Counter".into(),
+            visibility: Visibility::Public,
+            interfaces: vec![Path::from("traits::Step")],
+            methods: vec![increment],
+            ..Default::default()
+        };
+        let step = Interface { identifier: "Step".into(), ..Default::default() };
+        let traits = Module { identifier: "traits".into(), visibility: Visibility::Private, interfaces: vec![step], ..Default::default() };
+        let root_module = Module {
+            identifier: "lib".into(),
+            types: vec![structure("Counter", vec![]), structure("Unused", vec![])],
+            interfaces: vec![counter],
+            modules: vec![traits],
+            ..Default::default()
+        };
+        let library = Library { identifier: "counter".into(), root_module, ..Default::default() };
+        let registry = Registry { libraries: HashMap::from([(library.identifier.clone(), library)]) };
+
+        let pruned = Prune::new("counter").transform(&registry).unwrap();
+        let module = &pruned.libraries[&Identifier::from("counter")].root_module;
+        assert_eq!(module.types.iter().map(|definition| definition.identifier.name.as_str()).collect::<Vec<_>>(), ["Counter"]);
+        assert_eq!(module.interfaces.iter().map(|interface| interface.identifier.name.as_str()).collect::<Vec<_>>(), ["Counter"]);
+        assert_eq!(module.modules[0].interfaces[0].identifier, "Step".into());
+    }
+}
@@ -0,0 +1,259 @@
+//! Declarative rewrite rules.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Deserializer, Serialize};
+use ligen_idl::query::{Query, TypePattern};
+use ligen_idl::{Attribute, Context, Identifier, ItemMut, Library, Literal, Named, NamingConvention, Path, Type, VisitMut, visit_library_mut};
+use crate::transformers::walk::rename_references;
+
+/// Rewrite rules, usually read from the `[rules]` section of a TOML config:
+///
+/// ```toml
+/// [[rules.hide]]
+/// select = "* where private or attribute doc::hidden"
+///
+/// [[rules.replace]]
+/// from = "std::time::Instant"
+/// to = "u64"
+///
+/// [[rules.inject]]
+/// select = "structure lib::models::*"
+/// attribute = "serde::rename_all"
+/// value = "camelCase"
+///
+/// [[rules.rename]]
+/// select = "function"
+/// convention = "CamelCase"
+/// ```
+///
+/// Items are selected with a `Query`. The rules run in this order: hide, replace, inject and
+/// rename, so every selection refers to the original names.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Items to remove.
+    pub hide: Vec<Hide>,
+    /// Types to replace everywhere.
+    pub replace: Vec<Replace>,
+    /// Attributes to add.
+    pub inject: Vec<Inject>,
+    /// Items to rename.
+    pub rename: Vec<Rename>,
+}
+
+/// Removes the selected items.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hide {
+    /// The items to remove.
+    pub select: Query,
+}
+
+/// Replaces the types matching `from` with `to`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replace {
+    /// The types to replace.
+    pub from: TypePattern,
+    /// The replacement. Replacements with wildcards are rejected when deserialized.
+    #[serde(deserialize_with = "without_wildcards")]
+    pub to: TypePattern,
+}
+
+fn without_wildcards<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TypePattern, D::Error> {
+    let pattern = TypePattern::deserialize(deserializer)?;
+    match pattern.to_type() {
+        Some(_) => Ok(pattern),
+        None => Err(serde::de::Error::custom(format!("the replacement `{pattern}` can't have wildcards"))),
+    }
+}
+
+/// Adds an attribute to the selected items.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Inject {
+    /// The items to add the attribute to.
+    pub select: Query,
+    /// The attribute path.
+    pub attribute: Path,
+    /// The attribute value, making it `path = value`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Literal>,
+}
+
+/// Renames the selected items.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rename {
+    /// The items to rename.
+    pub select: Query,
+    /// The new name.
+    #[serde(flatten)]
+    pub to: Renaming,
+}
+
+/// How to rename an item.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Renaming {
+    /// Converts the name to a naming convention.
+    Convention(NamingConvention),
+    /// Replaces the name.
+    Name(String),
+}
+
+impl Renaming {
+    /// The new name of `identifier`.
+    pub fn rename(&self, identifier: &Identifier) -> Identifier {
+        match self {
+            Self::Convention(convention) => identifier.to_naming_convention(convention),
+            Self::Name(name) => Identifier::from(name.as_str()),
+        }
+    }
+}
+
+impl Rules {
+    /// Returns `true` if there are no rules.
+    pub fn is_empty(&self) -> bool {
+        self.hide.is_empty() && self.replace.is_empty() && self.inject.is_empty() && self.rename.is_empty()
+    }
+}
+
+impl Rules {
+    /// Applies the rules to `data`.
+    pub fn transform(&self, data: &Library) -> Library {
+        let mut library = data.clone();
+        for rule in &self.hide {
+            let selection = Selection::new(&rule.select, &library);
+            visit_library_mut(&mut |context: &Context, item: ItemMut| !selection.contains(context, &item), &mut library);
+        }
+        for rule in &self.replace {
+            if let Some(to) = rule.to.to_type() {
+                visit_library_mut(&mut ReplaceType { from: &rule.from, to }, &mut library);
+            }
+        }
+        for rule in &self.inject {
+            let selection = Selection::new(&rule.select, &library);
+            let attribute = match &rule.value {
+                Some(value) => Attribute::from(Named::new(rule.attribute.clone(), value.clone())),
+                None => Attribute::Group(rule.attribute.clone().into()),
+            };
+            visit_library_mut(&mut |context: &Context, item: ItemMut| {
+                if selection.contains(context, &item) {
+                    if let Some(attributes) = item.attributes().filter(|attributes| !attributes.contains(attribute.clone())) {
+                        attributes.push(attribute.clone());
+                    }
+                }
+                true
+            }, &mut library);
+        }
+        for rule in &self.rename {
+            let selection = Selection::new(&rule.select, &library);
+            // The types and modules are located before renaming anything, as their parents may be
+            // renamed too.
+            let original = library.clone();
+            let mut renamed = HashMap::new();
+            visit_library_mut(&mut |context: &Context, mut item: ItemMut| {
+                if selection.contains(context, &item) {
+                    let location = match &item {
+                        ItemMut::Module(_) => Some(context.module.clone()),
+                        ItemMut::Type(definition) => Some(context.module.join(definition.identifier.clone())),
+                        _ => None,
+                    };
+                    if let (Some(location), Some(identifier)) = (location, item.identifier()) {
+                        renamed.insert(location, rule.to.rename(identifier));
+                    }
+                }
+                true
+            }, &mut original.clone());
+            visit_library_mut(&mut |context: &Context, mut item: ItemMut| {
+                if !selection.contains(context, &item) {
+                    return true;
+                }
+                // Renaming an import renames the imported item, so it's imported `as` the new name.
+                if let ItemMut::Import(import) = &mut item {
+                    if import.renaming.is_none() {
+                        import.renaming = Some(import.path.last().identifier.clone());
+                    }
+                }
+                if let Some(identifier) = item.identifier() {
+                    *identifier = rule.to.rename(identifier);
+                }
+                true
+            }, &mut library);
+            rename_references(&original, &mut library, &renamed);
+        }
+        library
+    }
+}
+
+/// The paths and kinds of the items selected by a query.
+struct Selection(HashSet<(String, &'static str)>);
+
+impl Selection {
+    fn new(query: &Query, library: &Library) -> Self {
+        let items = query
+            .find(library)
+            .into_iter()
+            .map(|found| (found.path.to_string(), found.item.kind_name()))
+            .collect();
+        Self(items)
+    }
+
+    fn contains(&self, context: &Context, item: &ItemMut) -> bool {
+        self.0.contains(&(context.path.to_string(), item.kind_name()))
+    }
+}
+
+/// Replaces the types matching `from`, but not their generic arguments, with `to`.
+struct ReplaceType<'a> {
+    from: &'a TypePattern,
+    to: Type,
+}
+
+impl VisitMut for ReplaceType<'_> {
+    fn visit_type(&mut self, _context: &Context, type_: &mut Type) -> bool {
+        if self.from.matches(type_) {
+            *type_ = self.to.clone();
+            false
+        } else {
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_idl::{Field, Function, KindDefinition, Module, Structure, Type, TypeDefinition, Visibility};
+
+    fn library() -> Library {
+        let instant = Type::from("std::time::Instant");
+        let user = TypeDefinition {
+            identifier: "user_record".into(),
+            definition: KindDefinition::Structure(Structure { fields: vec![Field { identifier: Some("created".into()), type_: instant.clone(), ..Default::default() }] }),
+            ..Default::default()
+        };
+        let fetch = Function { identifier: "fetch".into(), output: Some(Type::option(Type::from("user_record"))), ..Default::default() };
+        let cache = Function { identifier: "cache".into(), visibility: Visibility::Private, ..Default::default() };
+        let root_module = Module { identifier: "lib".into(), types: vec![user], functions: vec![fetch, cache], ..Default::default() };
+        Library { identifier: "users".into(), root_module, ..Default::default() }
+    }
+
+    #[test]
+    fn rules() {
+        let rules = Rules {
+            hide: vec![Hide { select: "function where private".parse().unwrap() }],
+            replace: vec![Replace { from: "Instant".parse().unwrap(), to: "u64".parse().unwrap() }],
+            inject: vec![Inject { select: "structure".parse().unwrap(), attribute: "serde::rename_all".into(), value: Some("camelCase".into()) }],
+            rename: vec![Rename { select: "type".parse().unwrap(), to: Renaming::Convention(NamingConvention::PascalCase) }],
+        };
+        let library = rules.transform(&library());
+        let module = &library.root_module;
+        assert_eq!(module.functions.len(), 1);
+        assert_eq!(module.functions[0].output, Some(Type::option(Type::from("UserRecord"))));
+        assert_eq!(module.types[0].identifier, "UserRecord".into());
+        assert_eq!(module.types[0].attributes.len(), 1);
+        let KindDefinition::Structure(structure) = &module.types[0].definition else {
+            panic!("Expected a structure.");
+        };
+        assert_eq!(structure.fields[0].type_, Type::u64());
+    }
+}
@@ -0,0 +1,130 @@
+//! Visitors shared by the transformers.
+
+use std::collections::HashMap;
+
+use ligen_idl::{Context, Identifier, ItemMut, Library, Location, Path, Registry, Resolver, Type, VisitMut, visit_library_mut};
+
+/// Updates the references to renamed types and modules, and the interfaces implementing the
+/// types.
+///
+/// `renamed` maps the locations of the items in `original` to their new names. References are
+/// resolved in `original`, so a reference to another item with the same name, in another module,
+/// is kept.
+pub(crate) fn rename_references(original: &Library, library: &mut Library, renamed: &HashMap<Location, Identifier>) {
+    if renamed.is_empty() {
+        return;
+    }
+    let mut registry = Registry::new();
+    registry.libraries.insert(original.identifier.clone(), original.clone());
+    let mut references = References { resolver: Resolver::new(&registry), renamed, names: Vec::new() };
+    visit_library_mut(&mut references, &mut original.clone());
+    // Renaming doesn't add or remove items, so both libraries are visited in the same order.
+    visit_library_mut(&mut RenameReferences(references.names.into_iter()), library);
+}
+
+/// The new names of the segments of each reference in a library, in visiting order.
+struct References<'a> {
+    resolver: Resolver<'a>,
+    renamed: &'a HashMap<Location, Identifier>,
+    names: Vec<Vec<Option<Identifier>>>,
+}
+
+impl References<'_> {
+    /// The new name of each segment of `path`, if the path up to it refers to a renamed item.
+    /// References through an import renamed `as` another name are kept.
+    fn names(&self, context: &Context, path: &Path) -> Vec<Option<Identifier>> {
+        (1..=path.segments.len())
+            .map(|length| {
+                let prefix = Path { segments: path.segments[..length].to_vec() };
+                let location = self.resolver.resolve(&context.module, &prefix)?;
+                let name = self.renamed.get(&location)?;
+                let is_named = location.path.segments.last().is_some_and(|segment| segment.identifier == prefix.last().identifier);
+                is_named.then(|| name.clone())
+            })
+            .collect()
+    }
+}
+
+impl VisitMut for References<'_> {
+    fn visit_item(&mut self, context: &Context, item: ItemMut<'_>) -> bool {
+        match item {
+            ItemMut::Import(import) => {
+                let names = self.names(context, &import.path);
+                self.names.push(names);
+            },
+            ItemMut::Interface(interface) => {
+                let names = self.names(context, &interface.identifier.clone().into());
+                self.names.push(names);
+            },
+            _ => (),
+        }
+        true
+    }
+
+    fn visit_type(&mut self, context: &Context, type_: &mut Type) -> bool {
+        let names = self.names(context, &type_.path);
+        self.names.push(names);
+        true
+    }
+}
+
+struct RenameReferences<I>(I);
+
+impl<I: Iterator<Item = Vec<Option<Identifier>>>> RenameReferences<I> {
+    /// Renames the segments of `path` with the names of the next reference.
+    fn rename(&mut self, path: &mut Path) {
+        let names = self.0.next().unwrap_or_default();
+        for (segment, name) in path.segments.iter_mut().zip(names) {
+            if let Some(name) = name {
+                segment.identifier = name;
+            }
+        }
+    }
+}
+
+impl<I: Iterator<Item = Vec<Option<Identifier>>>> VisitMut for RenameReferences<I> {
+    fn visit_item(&mut self, _context: &Context, item: ItemMut<'_>) -> bool {
+        match item {
+            ItemMut::Import(import) => self.rename(&mut import.path),
+            ItemMut::Interface(interface) => {
+                let mut path = Path::from(interface.identifier.clone());
+                self.rename(&mut path);
+                interface.identifier = path.last().identifier.clone();
+            },
+            _ => (),
+        }
+        true
+    }
+
+    fn visit_type(&mut self, _context: &Context, type_: &mut Type) -> bool {
+        self.rename(&mut type_.path);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_idl::{Function, Module, TypeDefinition};
+
+    #[test]
+    fn same_names() {
+        let module = |identifier: &str| {
+            let foo = TypeDefinition { identifier: "Foo".into(), ..Default::default() };
+            let get = Function { identifier: "get".into(), output: Some(Type::from("Foo")), ..Default::default() };
+            Module { identifier: identifier.into(), types: vec![foo], functions: vec![get], ..Default::default() }
+        };
+        let both = Function { identifier: "both".into(), output: Some(Type::tuple(vec![Type::from("a::Foo"), Type::from("b::Foo")])), ..Default::default() };
+        let root_module = Module { identifier: "lib".into(), modules: vec![module("a"), module("b")], functions: vec![both], ..Default::default() };
+        let original = Library { identifier: "library".into(), root_module, ..Default::default() };
+
+        let mut library = original.clone();
+        library.root_module.modules[0].types[0].identifier = "Bar".into();
+        let renamed = HashMap::from([(Location::new("library", "a::Foo"), Identifier::from("Bar"))]);
+        rename_references(&original, &mut library, &renamed);
+        let module = &library.root_module;
+        assert_eq!(module.functions[0].output, Some(Type::tuple(vec![Type::from("a::Bar"), Type::from("b::Foo")])));
+        assert_eq!(module.modules[0].functions[0].output, Some(Type::from("Bar")));
+        assert_eq!(module.modules[1].functions[0].output, Some(Type::from("Foo")));
+    }
+}
@@ -0,0 +1,665 @@
+//! Wire format of type definitions, interpreted from their `serde` attributes.
+
+use std::collections::HashMap;
+
+use ligen_common::{Diagnostic, Diagnostics};
+use ligen_idl::{Attribute, Attributes, Field, Identifier, KindDefinition, Library, Literal, Module, Path, TypeDefinition, Variant};
+
+/// Interprets the `serde` attributes of a `Library`'s type definitions, fields and variants into
+/// their `WireFormats`, so generators can use the names and shapes `serde` actually reads and writes.
+///
+/// Names start from the Rust ones, including the original names `NormalizeNames` records, and
+/// then have `rename` and `rename_all` applied. Fields and variants also accept their `alias`es
+/// when deserializing. Malformed or conflicting `serde` attributes are
+/// reported as `serde-attribute` warnings and ignored. Attributes changing the format in ways
+/// that aren't modeled, such as `with`, are ignored.
+#[derive(Debug, Default, Clone)]
+pub struct InterpretSerde {
+    /// Where malformed attributes are reported.
+    pub diagnostics: Diagnostics,
+}
+
+/// The wire formats of a library's type definitions, by their path from the root module.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WireFormats {
+    /// Wire formats.
+    pub types: HashMap<Path, WireFormat>,
+}
+
+impl WireFormats {
+    /// The wire format of the type definition at `path`, from the root module.
+    pub fn get(&self, path: impl Into<Path>) -> Option<&WireFormat> {
+        self.types.get(&path.into())
+    }
+}
+
+/// How a type definition is serialized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WireFormat {
+    /// Name of the type.
+    pub name: WireName,
+    /// How the variants of an enumeration are told apart.
+    pub tagging: Tagging,
+    /// Where missing fields are taken from, as a whole.
+    pub default: Option<DefaultValue>,
+    /// Fields of a structure, in declaration order.
+    pub fields: Vec<WireField>,
+    /// Variants of an enumeration, in declaration order.
+    pub variants: Vec<WireVariant>,
+}
+
+/// The names of an item when serializing and when deserializing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WireName {
+    /// Name written when serializing.
+    pub serialize: String,
+    /// Name read when deserializing.
+    pub deserialize: String,
+    /// Other names accepted when deserializing.
+    pub aliases: Vec<String>,
+}
+
+impl WireName {
+    /// The same name in both directions.
+    pub fn new(name: impl Into<String>) -> Self {
+        let serialize = name.into();
+        let deserialize = serialize.clone();
+        let aliases = Vec::new();
+        Self { serialize, deserialize, aliases }
+    }
+
+    /// Also accepts `aliases` when deserializing.
+    pub fn with_aliases(mut self, aliases: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.aliases = aliases.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// How the variants of an enumeration are represented.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum Tagging {
+    /// `{"Variant": content}`.
+    #[default]
+    External,
+    /// `{"tag": "Variant", ...fields}`.
+    Internal {
+        /// Name of the tag field.
+        tag: String,
+    },
+    /// `{"tag": "Variant", "content": content}`.
+    Adjacent {
+        /// Name of the tag field.
+        tag: String,
+        /// Name of the content field.
+        content: String,
+    },
+    /// The content alone, matched against each variant in order.
+    Untagged,
+}
+
+/// Where a missing value is taken from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DefaultValue {
+    /// The type's `Default` implementation.
+    Default,
+    /// A function returning the value.
+    Function(Path),
+}
+
+/// When an item is skipped.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Skip {
+    /// It's never serialized.
+    pub serializing: bool,
+    /// It's never deserialized, taking its default value.
+    pub deserializing: bool,
+    /// A predicate on the value that skips serializing it when true.
+    pub serializing_if: Option<Path>,
+}
+
+/// How a field is serialized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WireField {
+    /// The field's identifier, if it's named.
+    pub identifier: Option<Identifier>,
+    /// Name of the field. Unnamed fields are named after their position.
+    pub name: WireName,
+    /// Whether its own fields are serialized in place of it.
+    pub flatten: bool,
+    /// When it's skipped.
+    pub skip: Skip,
+    /// Where it's taken from when missing.
+    pub default: Option<DefaultValue>,
+}
+
+/// How a variant is serialized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WireVariant {
+    /// The variant's identifier.
+    pub identifier: Identifier,
+    /// Name of the variant.
+    pub name: WireName,
+    /// When it's skipped.
+    pub skip: Skip,
+    /// Whether it's untagged while the other variants aren't.
+    pub untagged: bool,
+}
+
+/// The case conversions of `rename_all`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameRule {
+    /// `lowercase`.
+    LowerCase,
+    /// `UPPERCASE`.
+    UpperCase,
+    /// `PascalCase`.
+    PascalCase,
+    /// `camelCase`.
+    CamelCase,
+    /// `snake_case`.
+    SnakeCase,
+    /// `SCREAMING_SNAKE_CASE`.
+    ScreamingSnakeCase,
+    /// `kebab-case`.
+    KebabCase,
+    /// `SCREAMING-KEBAB-CASE`.
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    /// The rule named `name`, as written in `rename_all`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "lowercase" => Some(Self::LowerCase),
+            "UPPERCASE" => Some(Self::UpperCase),
+            "PascalCase" => Some(Self::PascalCase),
+            "camelCase" => Some(Self::CamelCase),
+            "snake_case" => Some(Self::SnakeCase),
+            "SCREAMING_SNAKE_CASE" => Some(Self::ScreamingSnakeCase),
+            "kebab-case" => Some(Self::KebabCase),
+            "SCREAMING-KEBAB-CASE" => Some(Self::ScreamingKebabCase),
+            _ => None,
+        }
+    }
+
+    /// Converts a PascalCase variant name, the way `serde` does.
+    pub fn apply_to_variant(&self, variant: &str) -> String {
+        match self {
+            Self::PascalCase => variant.to_string(),
+            Self::LowerCase => variant.to_ascii_lowercase(),
+            Self::UpperCase => variant.to_ascii_uppercase(),
+            Self::CamelCase => variant[..1].to_ascii_lowercase() + &variant[1..],
+            Self::SnakeCase => {
+                let mut snake = String::new();
+                for (index, character) in variant.char_indices() {
+                    if index > 0 && character.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(character.to_ascii_lowercase());
+                }
+                snake
+            },
+            Self::ScreamingSnakeCase => Self::SnakeCase.apply_to_variant(variant).to_ascii_uppercase(),
+            Self::KebabCase => Self::SnakeCase.apply_to_variant(variant).replace('_', "-"),
+            Self::ScreamingKebabCase => Self::ScreamingSnakeCase.apply_to_variant(variant).replace('_', "-"),
+        }
+    }
+
+    /// Converts a snake_case field name, the way `serde` does.
+    pub fn apply_to_field(&self, field: &str) -> String {
+        match self {
+            Self::LowerCase | Self::SnakeCase => field.to_string(),
+            Self::UpperCase | Self::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            Self::PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for character in field.chars() {
+                    if character == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(character.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(character);
+                    }
+                }
+                pascal
+            },
+            Self::CamelCase => {
+                let pascal = Self::PascalCase.apply_to_field(field);
+                pascal[..1].to_ascii_lowercase() + &pascal[1..]
+            },
+            Self::KebabCase => field.replace('_', "-"),
+            Self::ScreamingKebabCase => Self::ScreamingSnakeCase.apply_to_field(field).replace('_', "-"),
+        }
+    }
+}
+
+/// A value for serializing and one for deserializing.
+#[derive(Debug)]
+struct Directions<T> {
+    serialize: Option<T>,
+    deserialize: Option<T>,
+}
+
+impl<T> Default for Directions<T> {
+    fn default() -> Self {
+        Self { serialize: None, deserialize: None }
+    }
+}
+
+impl<T: Clone> Directions<T> {
+    fn both(value: T) -> Self {
+        Self { serialize: Some(value.clone()), deserialize: Some(value) }
+    }
+}
+
+/// The `serde` attributes of an item.
+#[derive(Debug, Default)]
+struct Serde {
+    rename: Directions<String>,
+    rename_all: Directions<RenameRule>,
+    aliases: Vec<String>,
+    tag: Option<String>,
+    content: Option<String>,
+    untagged: bool,
+    flatten: bool,
+    skip: Skip,
+    default: Option<DefaultValue>,
+}
+
+impl InterpretSerde {
+    /// Reports malformed attributes to `diagnostics`.
+    pub fn with_diagnostics(mut self, diagnostics: Diagnostics) -> Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+
+    fn report(&self, symbol: &Path, message: String) {
+        self.diagnostics.report(Diagnostic::warning("serde-attribute", message).with_symbol(symbol));
+    }
+
+    fn string(&self, symbol: &Path, key: &str, literal: &Literal) -> Option<String> {
+        match literal {
+            Literal::String(string) => Some(string.clone()),
+            literal => {
+                self.report(symbol, format!("`{key}` should be a string, not `{literal}`."));
+                None
+            }
+        }
+    }
+
+    fn rule(&self, symbol: &Path, literal: &Literal) -> Option<RenameRule> {
+        let name = self.string(symbol, "rename_all", literal)?;
+        let rule = RenameRule::from_name(&name);
+        if rule.is_none() {
+            self.report(symbol, format!("`{name}` isn't a `rename_all` rule."));
+        }
+        rule
+    }
+
+    /// Interprets the `serde` attributes among `attributes`, of the item at `symbol`.
+    fn serde(&self, symbol: &Path, attributes: &Attributes) -> Serde {
+        let mut serde = Serde::default();
+        let attributes = attributes
+            .iter()
+            .filter_map(|attribute| attribute.as_group())
+            .filter(|group| group.path == Path::from("serde"))
+            .flat_map(|group| group.attributes.iter());
+        for attribute in attributes {
+            match attribute {
+                Attribute::Named(named) => match named.path.to_string().as_str() {
+                    "rename" => if let Some(name) = self.string(symbol, "rename", &named.literal) {
+                        serde.rename = Directions::both(name);
+                    },
+                    "rename_all" => if let Some(rule) = self.rule(symbol, &named.literal) {
+                        serde.rename_all = Directions::both(rule);
+                    },
+                    "alias" => serde.aliases.extend(self.string(symbol, "alias", &named.literal)),
+                    "tag" => serde.tag = self.string(symbol, "tag", &named.literal),
+                    "content" => serde.content = self.string(symbol, "content", &named.literal),
+                    "default" => serde.default = self.string(symbol, "default", &named.literal).map(|function| DefaultValue::Function(function.into())),
+                    "skip_serializing_if" => serde.skip.serializing_if = self.string(symbol, "skip_serializing_if", &named.literal).map(Path::from),
+                    _ => (),
+                },
+                Attribute::Group(group) if group.attributes.is_empty() => match group.path.to_string().as_str() {
+                    "untagged" => serde.untagged = true,
+                    "flatten" => serde.flatten = true,
+                    "skip" => {
+                        serde.skip.serializing = true;
+                        serde.skip.deserializing = true;
+                    },
+                    "skip_serializing" => serde.skip.serializing = true,
+                    "skip_deserializing" => serde.skip.deserializing = true,
+                    "default" => serde.default = Some(DefaultValue::Default),
+                    _ => (),
+                },
+                Attribute::Group(group) => match group.path.to_string().as_str() {
+                    "rename" => {
+                        if let Some(literal) = group.attributes.get_named("serialize") {
+                            serde.rename.serialize = self.string(symbol, "rename", literal);
+                        }
+                        if let Some(literal) = group.attributes.get_named("deserialize") {
+                            serde.rename.deserialize = self.string(symbol, "rename", literal);
+                        }
+                    },
+                    "rename_all" => {
+                        if let Some(literal) = group.attributes.get_named("serialize") {
+                            serde.rename_all.serialize = self.rule(symbol, literal);
+                        }
+                        if let Some(literal) = group.attributes.get_named("deserialize") {
+                            serde.rename_all.deserialize = self.rule(symbol, literal);
+                        }
+                    },
+                    _ => (),
+                },
+                Attribute::Literal(_) => (),
+            }
+        }
+        serde
+    }
+
+    fn tagging(&self, symbol: &Path, serde: &Serde) -> Tagging {
+        match (serde.untagged, serde.tag.clone(), serde.content.clone()) {
+            (false, None, None) => Tagging::External,
+            (false, Some(tag), None) => Tagging::Internal { tag },
+            (false, Some(tag), Some(content)) => Tagging::Adjacent { tag, content },
+            (true, None, None) => Tagging::Untagged,
+            (false, None, Some(_)) => {
+                self.report(symbol, "`content` requires a `tag`.".into());
+                Tagging::External
+            },
+            (true, _, _) => {
+                self.report(symbol, "`untagged` can't have a `tag` or `content`.".into());
+                Tagging::Untagged
+            },
+        }
+    }
+
+    fn field(&self, path: &Path, index: usize, field: &Field, rename_all: &Directions<RenameRule>) -> WireField {
+        let name = field.identifier.as_ref().map_or(index.to_string(), |identifier| original(identifier, &field.attributes));
+        let serde = self.serde(&path.clone().join(name.clone()), &field.attributes);
+        let name = match field.identifier {
+            Some(_) => wire_name(&name, &serde.rename, rename_all, RenameRule::apply_to_field).with_aliases(serde.aliases),
+            None => WireName::new(name),
+        };
+        let identifier = field.identifier.clone();
+        WireField { identifier, name, flatten: serde.flatten, skip: serde.skip, default: serde.default }
+    }
+
+    fn variant(&self, path: &Path, variant: &Variant, rename_all: &Directions<RenameRule>) -> WireVariant {
+        let name = original(&variant.identifier, &variant.attributes);
+        let serde = self.serde(&path.clone().join(name.clone()), &variant.attributes);
+        let name = wire_name(&name, &serde.rename, rename_all, RenameRule::apply_to_variant).with_aliases(serde.aliases);
+        let identifier = variant.identifier.clone();
+        WireVariant { identifier, name, skip: serde.skip, untagged: serde.untagged }
+    }
+
+    /// The wire format of the type definition at `path`.
+    pub fn type_definition(&self, path: &Path, definition: &TypeDefinition) -> WireFormat {
+        let serde = self.serde(path, &definition.attributes);
+        let name = original(&definition.identifier, &definition.attributes);
+        let name = wire_name(&name, &serde.rename, &Default::default(), RenameRule::apply_to_variant);
+        let tagging = self.tagging(path, &serde);
+        let fields = match &definition.definition {
+            KindDefinition::Structure(structure) => structure
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| self.field(path, index, field, &serde.rename_all))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let variants = match &definition.definition {
+            KindDefinition::Enumeration(enumeration) => enumeration
+                .variants
+                .iter()
+                .map(|variant| self.variant(path, variant, &serde.rename_all))
+                .collect(),
+            _ => Vec::new(),
+        };
+        WireFormat { name, tagging, default: serde.default, fields, variants }
+    }
+
+    fn module(&self, path: &Path, module: &Module, formats: &mut WireFormats) {
+        for definition in &module.types {
+            let path = path.clone().join(definition.identifier.clone());
+            let format = self.type_definition(&path, definition);
+            formats.types.insert(path, format);
+        }
+        for child in &module.modules {
+            self.module(&path.clone().join(child.identifier.clone()), child, formats);
+        }
+    }
+}
+
+/// The item's name in Rust, before `NormalizeNames` renamed it.
+fn original(identifier: &Identifier, attributes: &Attributes) -> String {
+    attributes
+        .get_literal_from_path("ligen::name")
+        .and_then(|name| name.as_string())
+        .cloned()
+        .unwrap_or_else(|| identifier.name.clone())
+}
+
+/// `name`, renamed or else converted by the rule, in each direction.
+fn wire_name(name: &str, rename: &Directions<String>, rename_all: &Directions<RenameRule>, apply: fn(&RenameRule, &str) -> String) -> WireName {
+    let direction = |rename: &Option<String>, rule: &Option<RenameRule>| {
+        rename
+            .clone()
+            .unwrap_or_else(|| rule.map_or(name.to_string(), |rule| apply(&rule, name)))
+    };
+    let serialize = direction(&rename.serialize, &rename_all.serialize);
+    let deserialize = direction(&rename.deserialize, &rename_all.deserialize);
+    let aliases = Vec::new();
+    WireName { serialize, deserialize, aliases }
+}
+
+impl InterpretSerde {
+    /// Interprets the serde attributes of the types of `data`.
+    pub fn transform(&self, data: &Library) -> WireFormats {
+        let mut formats = WireFormats::default();
+        // The root module isn't part of the paths.
+        self.module(&Path::default(), &data.root_module, &mut formats);
+        formats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ligen_idl::{Enumeration, Group, Named, Structure, Type};
+
+    fn serde(attributes: Vec<Attribute>) -> Attributes {
+        Group::new("serde", attributes).into()
+    }
+
+    fn field(identifier: &str, attributes: Attributes) -> Field {
+        Field { identifier: Some(identifier.into()), type_: Type::f32(), attributes, ..Default::default() }
+    }
+
+    fn variant(identifier: &str, attributes: Attributes) -> Variant {
+        Variant { identifier: identifier.into(), attributes }
+    }
+
+    fn structure(identifier: &str, attributes: Attributes, fields: Vec<Field>) -> TypeDefinition {
+        let definition = KindDefinition::Structure(Structure { fields });
+        TypeDefinition { identifier: identifier.into(), attributes, definition, ..Default::default() }
+    }
+
+    fn enumeration(identifier: &str, attributes: Attributes, variants: Vec<Variant>) -> TypeDefinition {
+        let definition = KindDefinition::Enumeration(Enumeration { variants });
+        TypeDefinition { identifier: identifier.into(), attributes, definition, ..Default::default() }
+    }
+
+    /// The wire format of `definition`, at the root module, and the diagnostics reported.
+    fn interpret(definition: &TypeDefinition) -> (WireFormat, Vec<Diagnostic>) {
+        let interpret = InterpretSerde::default();
+        let format = interpret.type_definition(&definition.identifier.clone().into(), definition);
+        (format, interpret.diagnostics.to_vec())
+    }
+
+    fn warning(symbol: &str, message: &str) -> Diagnostic {
+        Diagnostic::warning("serde-attribute", message).with_symbol(symbol)
+    }
+
+    #[test]
+    fn rename() {
+        let shape = enumeration("Shape", serde(vec![Named::new("rename", "shape").into(), Named::new("rename_all", "snake_case").into()]), vec![
+            variant("RightTriangle", Default::default()),
+            variant("Circle", serde(vec![Named::new("rename", "round").into()])),
+        ]);
+        let (shape, diagnostics) = interpret(&shape);
+        assert_eq!(shape.name, WireName::new("shape"));
+        assert_eq!(shape.variants[0].name, WireName::new("right_triangle"));
+        assert_eq!(shape.variants[1].name, WireName::new("round"));
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn original_names() {
+        let point = structure("Point", serde(vec![Named::new("rename_all", "camelCase").into()]), vec![
+            field("y", Group::new("ligen", Named::new("name", "y_axis")).into()),
+        ]);
+        let (point, _) = interpret(&point);
+        assert_eq!(point.fields[0].identifier, Some("y".into()));
+        assert_eq!(point.fields[0].name, WireName::new("yAxis"));
+    }
+
+    #[test]
+    fn directions() {
+        let rename_all = Group::new("rename_all", vec![
+            Named::new("serialize", "camelCase").into(),
+            Named::new("deserialize", "SCREAMING_SNAKE_CASE").into(),
+        ]);
+        let point = structure("Point", serde(vec![rename_all.into()]), vec![
+            field("x_axis", Default::default()),
+            field("id", serde(vec![Group::new("rename", Named::new("deserialize", "identifier")).into()])),
+        ]);
+        let (point, diagnostics) = interpret(&point);
+        assert_eq!((point.fields[0].name.serialize.as_str(), point.fields[0].name.deserialize.as_str()), ("xAxis", "X_AXIS"));
+        assert_eq!((point.fields[1].name.serialize.as_str(), point.fields[1].name.deserialize.as_str()), ("id", "identifier"));
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn aliases() {
+        let point = structure("Point", Default::default(), vec![
+            field("x", serde(vec![Named::new("alias", "horizontal").into(), Named::new("alias", "abscissa").into()])),
+        ]);
+        let (point, _) = interpret(&point);
+        assert_eq!(point.fields[0].name, WireName::new("x").with_aliases(["horizontal", "abscissa"]));
+
+        let shape = enumeration("Shape", serde(vec![Named::new("rename_all", "lowercase").into()]), vec![
+            variant("Circle", serde(vec![Named::new("alias", "Round").into()])),
+        ]);
+        let (shape, _) = interpret(&shape);
+        assert_eq!(shape.variants[0].name, WireName::new("circle").with_aliases(["Round"]));
+    }
+
+    #[test]
+    fn tagging() {
+        let tagging = |attributes: Vec<Attribute>| interpret(&enumeration("Shape", serde(attributes), Vec::new())).0.tagging;
+        assert_eq!(tagging(Vec::new()), Tagging::External);
+        assert_eq!(tagging(vec![Named::new("tag", "kind").into()]), Tagging::Internal { tag: "kind".into() });
+        assert_eq!(
+            tagging(vec![Named::new("tag", "kind").into(), Named::new("content", "value").into()]),
+            Tagging::Adjacent { tag: "kind".into(), content: "value".into() }
+        );
+        assert_eq!(tagging(vec![Group::from("untagged").into()]), Tagging::Untagged);
+    }
+
+    #[test]
+    fn untagged_variants() {
+        let value = enumeration("Value", Default::default(), vec![
+            variant("Number", Default::default()),
+            variant("Other", serde(vec![Group::from("untagged").into()])),
+        ]);
+        let (value, _) = interpret(&value);
+        assert_eq!(value.tagging, Tagging::External);
+        assert!(!value.variants[0].untagged);
+        assert!(value.variants[1].untagged);
+    }
+
+    #[test]
+    fn skip() {
+        let point = structure("Point", Default::default(), vec![
+            field("cache", serde(vec![Group::from("skip").into()])),
+            field("written", serde(vec![Group::from("skip_deserializing").into()])),
+            field("read", serde(vec![Group::from("skip_serializing").into()])),
+            field("label", serde(vec![Named::new("skip_serializing_if", "Option::is_none").into()])),
+        ]);
+        let (point, _) = interpret(&point);
+        let skip = |serializing, deserializing, serializing_if: Option<&str>| Skip { serializing, deserializing, serializing_if: serializing_if.map(Path::from) };
+        assert_eq!(point.fields[0].skip, skip(true, true, None));
+        assert_eq!(point.fields[1].skip, skip(false, true, None));
+        assert_eq!(point.fields[2].skip, skip(true, false, None));
+        assert_eq!(point.fields[3].skip, skip(false, false, Some("Option::is_none")));
+
+        let shape = enumeration("Shape", Default::default(), vec![variant("Unknown", serde(vec![Group::from("skip").into()]))]);
+        let (shape, _) = interpret(&shape);
+        assert_eq!(shape.variants[0].skip, skip(true, true, None));
+    }
+
+    #[test]
+    fn flatten() {
+        let point = structure("Point", Default::default(), vec![
+            field("x", Default::default()),
+            field("extra", serde(vec![Group::from("flatten").into()])),
+        ]);
+        let (point, _) = interpret(&point);
+        assert!(!point.fields[0].flatten);
+        assert!(point.fields[1].flatten);
+    }
+
+    #[test]
+    fn defaults() {
+        let point = structure("Point", serde(vec![Group::from("default").into()]), vec![
+            field("x", Default::default()),
+            field("y", serde(vec![Group::from("default").into()])),
+            field("z", serde(vec![Named::new("default", "Point::origin_z").into()])),
+        ]);
+        let (point, _) = interpret(&point);
+        assert_eq!(point.default, Some(DefaultValue::Default));
+        assert_eq!(point.fields[0].default, None);
+        assert_eq!(point.fields[1].default, Some(DefaultValue::Default));
+        assert_eq!(point.fields[2].default, Some(DefaultValue::Function("Point::origin_z".into())));
+
+        let origin = structure("Origin", serde(vec![Named::new("default", "Origin::new").into()]), Vec::new());
+        let (origin, _) = interpret(&origin);
+        assert_eq!(origin.default, Some(DefaultValue::Function("Origin::new".into())));
+    }
+
+    #[test]
+    fn paths() {
+        let point = structure("Point", Default::default(), Vec::new());
+        let shape = enumeration("Shape", Default::default(), Vec::new());
+        let geometry = Module { identifier: "geometry".into(), types: vec![point], ..Default::default() };
+        let root_module = Module { identifier: "shapes".into(), types: vec![shape], modules: vec![geometry], ..Default::default() };
+        let library = Library { identifier: "shapes".into(), root_module, ..Default::default() };
+        let formats = InterpretSerde::default().transform(&library);
+        assert_eq!(formats.types.len(), 2);
+        assert_eq!(formats.get("Shape").map(|format| &format.name), Some(&WireName::new("Shape")));
+        assert_eq!(formats.get("geometry::Point").map(|format| &format.name), Some(&WireName::new("Point")));
+    }
+
+    #[test]
+    fn malformed() {
+        let broken = enumeration("Broken", serde(vec![Named::new("rename_all", "Title Case").into(), Named::new("content", "value").into()]), vec![
+            variant("First", serde(vec![Named::new("alias", 1).into()])),
+        ]);
+        let (broken, diagnostics) = interpret(&broken);
+        assert_eq!(broken.tagging, Tagging::External);
+        assert_eq!(broken.variants[0].name, WireName::new("First"));
+        assert_eq!(diagnostics, [
+            warning("Broken", "`Title Case` isn't a `rename_all` rule."),
+            warning("Broken", "`content` requires a `tag`."),
+            warning("Broken::First", "`alias` should be a string, not `1`."),
+        ]);
+
+        let conflicting = enumeration("Conflicting", serde(vec![Group::from("untagged").into(), Named::new("tag", "kind").into()]), Vec::new());
+        let (conflicting, diagnostics) = interpret(&conflicting);
+        assert_eq!(conflicting.tagging, Tagging::Untagged);
+        assert_eq!(diagnostics, [warning("Conflicting", "`untagged` can't have a `tag` or `content`.")]);
+    }
+}
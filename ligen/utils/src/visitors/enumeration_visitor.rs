@@ -1,17 +0,0 @@
-use super::{Visitor, ModuleVisitor};
-use ligen_idl::{Enumeration, Path};
-
-/// Enumeration visitor.
-pub type EnumerationVisitor = Visitor<ModuleVisitor, Enumeration>;
-
-impl EnumerationVisitor {
-    /// Returns the enumeration path.
-    pub fn path(&self) -> Path {
-        self.parent_module().path().join(self.parent.identifier.clone())
-    }
-
-    /// Get the parent module.
-    pub fn parent_module(&self) -> &ModuleVisitor {
-        &self.parent
-    }
-}
\ No newline at end of file
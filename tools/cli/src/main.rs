@@ -1,85 +1,248 @@
 use std::path::{Path, PathBuf};
-use clap::Parser as Clap;
+use clap::{Parser as Clap, Subcommand, ValueEnum};
 use ligen_idl::prelude::*;
-use ligen_idl::Registry;
-use ligen_transformer::{Config, ConfigSet, Transformer};
+use ligen_idl::diff::{Diff, SemverCheck};
+use ligen_idl::{Library, Query, Registry};
+use ligen_transformer::{Config, ConfigSet, Transformer, Validator};
 use ligen_transformer::generator::Generator;
+use ligen_utils::transformers::prune::Prune;
 
 #[derive(Clap, Debug)]
+#[command(subcommand_negates_reqs = true)]
 pub struct Args {
-    #[arg(short, long)]
-    parser: String,
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    #[arg(short, long)]
-    generator: String,
+    #[arg(short, long, required = true)]
+    parser: Option<String>,
 
-    #[arg(short, long)]
-    input: PathBuf,
+    #[arg(short, long, required = true)]
+    generator: Option<String>,
 
+    #[arg(short, long, required = true)]
+    input: Option<PathBuf>,
+
+    #[arg(short, long, required = true)]
+    output: Option<PathBuf>,
+
+    /// A TOML config, such as one with the rewrite `rules` to apply before generating.
     #[arg(short, long)]
-    output: PathBuf,
+    config: Option<PathBuf>,
+
+    /// Keeps only the items the public API of this library needs, removing the rest of its dependencies.
+    #[arg(long)]
+    prune: Option<String>,
+
+    /// Also keeps the items with a `#[ligen]` attribute when pruning.
+    #[arg(long, requires = "prune")]
+    keep_marked: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Lists the API changes between two versions of a library.
+    Diff {
+        old: PathBuf,
+        new: PathBuf,
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Fails if the API changes between two versions of a library need a bigger version bump.
+    Semver {
+        old: PathBuf,
+        new: PathBuf,
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Lists the items of a library matching a query, such as `function where async and returns Option<_>`.
+    Query {
+        input: PathBuf,
+        query: String,
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum OutputFormat {
+    Text,
+    Json,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    
+
+    if let Some(command) = args.command {
+        return match command {
+            Command::Diff { old, new, format } => diff(&old, &new, format),
+            Command::Semver { old, new, format } => semver(&old, &new, format),
+            Command::Query { input, query: source, format } => query(&input, &source, format),
+        };
+    }
+
+    let (Some(parser), Some(generator), Some(input), Some(output)) = (args.parser, args.generator, args.input, args.output) else {
+        unreachable!("clap requires these arguments when there's no subcommand.")
+    };
+
+    let config = match args.config {
+        Some(path) => Config::try_from(std::fs::read_to_string(path)?.as_str()).map_err(|error| Error::Message(error.to_string()))?,
+        None => Config::default(),
+    };
+
     // 1. Select Parser and Parse
-    let registry = parse(&args.parser, &args.input)?;
+    let registry = parse(&parser, &input, &config)?;
+    let registry = match args.prune {
+        Some(root) => {
+            let prune = Prune { root: root.as_str().into(), keep_marked: args.keep_marked };
+            prune.transform(&registry)?
+        },
+        None => registry,
+    };
     
     // 2. Select Generator and Generate
-    generate(&args.generator, registry, &args.output)?;
+    generate(&generator, registry, &output, config)?;
     
     Ok(())
 }
 
-fn parse(parser_name: &str, input: &Path) -> Result<Registry> {
-    let config = Config::default();
-    match parser_name.to_lowercase().as_str() {
+/// Loads a saved IDL document or a `.lidl` file.
+fn load(path: &Path) -> Result<Library> {
+    if path.extension().is_some_and(|extension| extension == "lidl") {
+        let config = Config::default();
+        let library = ligen_lidl::LidlParser::new().transform(path, &config);
+        report(&config);
+        library
+    } else {
+        Library::load(path)
+    }
+}
+
+fn diff(old: &Path, new: &Path, format: OutputFormat) -> Result<()> {
+    let diff = Diff::new(&load(old)?, &load(new)?);
+    match format {
+        OutputFormat::Text => print!("{diff}"),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+    }
+    Ok(())
+}
+
+fn semver(old: &Path, new: &Path, format: OutputFormat) -> Result<()> {
+    let check = SemverCheck::new(&load(old)?, &load(new)?);
+    match format {
+        OutputFormat::Text if check.is_ok() => println!("{check}"),
+        OutputFormat::Text => eprint!("{check}"),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&check)?),
+    }
+    if !check.is_ok() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn query(input: &Path, source: &str, format: OutputFormat) -> Result<()> {
+    let query = Query::parse(source)?;
+    let matches = query.find(&load(input)?);
+    match format {
+        OutputFormat::Text => {
+            for found in &matches {
+                println!("{} {}", found.item.kind_name(), found.path);
+            }
+        },
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&matches)?),
+    }
+    Ok(())
+}
+
+fn report(config: &Config) {
+    for diagnostic in config.diagnostics().take() {
+        eprintln!("{diagnostic}");
+    }
+}
+
+fn parse(parser_name: &str, input: &Path, config: &Config) -> Result<Registry> {
+    let registry = match parser_name.to_lowercase().as_str() {
         "rust" => {
             let parser = ligen_rust_parser::RustRegistryParser::new();
-            parser.transform(input, &config)
+            parser.transform(input, config)
         }
         "python" => {
             let parser = ligen_python_parser::PythonParser::default();
-            parser.transform(input, &config)
+            parser.transform(input, config)
         }
         "openapi" => {
             let parser = ligen_openapi_parser::OpenAPILibraryParser::new();
-            let library = parser.transform(input, &config)?;
+            let library = parser.transform(input, config)?;
             Ok(Registry { libraries: std::collections::HashMap::from([(library.identifier.clone(), library)]) })
         }
         "anchor" => {
             let parser = ligen_anchor_parser::library::LibraryParser::default();
-            let library = parser.transform(input, &config)?;
+            let library = parser.transform(input, config)?;
+            Ok(Registry { libraries: std::collections::HashMap::from([(library.identifier.clone(), library)]) })
+        }
+        "lidl" => {
+            let parser = ligen_lidl::LidlParser::new();
+            let library = parser.transform(input, config)?;
             Ok(Registry { libraries: std::collections::HashMap::from([(library.identifier.clone(), library)]) })
         }
         _ => Err(Error::Message(format!("Parser not found: {}", parser_name)))
-    }
+    };
+    report(config);
+    registry
 }
 
-fn generate(generator_name: &str, registry: Registry, output: &Path) -> Result<()> {
-    let mut config = Config::default();
+fn generate(generator_name: &str, registry: Registry, output: &Path, mut config: Config) -> Result<()> {
     config.set("ligen::output-dir", output.to_string_lossy().to_string());
-    
-    for library in registry.libraries.into_values() {
+
+    let libraries = registry
+        .libraries
+        .into_iter()
+        .map(|(identifier, library)| (identifier, config.rules().transform(&library)))
+        .collect();
+    let registry = Registry { libraries };
+    let validation = match generator_name.to_lowercase().as_str() {
+        "rust" => ligen_rust_generator::RustLibraryGenerator::default().validation(),
+        "rust-client" => ligen_rust_client_generator::RustClientGenerator::default().validation(),
+        "pyo3" => ligen_rust_pyo3_importer::LibraryGenerator::default().validation(),
+        "anchor" => ligen_anchor_generator::AnchorGenerator::new().validation(),
+        "lidl" => ligen_lidl::LidlGenerator::new().validation(),
+        _ => return Err(Error::Message(format!("Generator not found: {}", generator_name)))
+    };
+    let validated = validation.validate(&registry, &config);
+    report(&config);
+    validated?;
+    let mut libraries = registry.libraries.values().collect::<Vec<_>>();
+    libraries.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+
+    for library in libraries {
         match generator_name.to_lowercase().as_str() {
+            "rust" => {
+                let generator = ligen_rust_generator::RustLibraryGenerator::default();
+                generator.generate(library, &config)?;
+            }
             "rust-client" => {
                 let generator = ligen_rust_client_generator::RustClientGenerator::default();
-                generator.generate(&library, &config)?;
+                generator.generate(library, &config)?;
             }
             "pyo3" => {
                 let generator = ligen_rust_pyo3_importer::LibraryGenerator::default();
-                generator.generate(&library, &config)?;
+                generator.generate(library, &config)?;
             }
             "anchor" => {
                 let generator = ligen_anchor_generator::AnchorGenerator::new();
-                let idl = generator.generate(&library, &config)?;
+                let idl = generator.generate(library, &config)?;
                 let file_path = output.join(format!("{}.json", library.identifier));
                 let json = serde_json::to_string_pretty(&idl)?;
                 std::fs::write(file_path, json)?;
             }
+            "lidl" => {
+                let generator = ligen_lidl::LidlGenerator::new();
+                let text = generator.generate(library, &config)?;
+                let file_path = output.join(format!("{}.lidl", library.identifier));
+                std::fs::write(file_path, text)?;
+            }
             _ => return Err(Error::Message(format!("Generator not found: {}", generator_name)))
         }
+        report(&config);
     }
     Ok(())
 }
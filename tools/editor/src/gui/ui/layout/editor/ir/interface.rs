@@ -33,6 +33,7 @@ impl Widget for Interface {
                     });
                 }
                 SubWidgetsWithSymbols::new("Object").show(settings, ui, &mut interface.objects);
+                SubWidgetsWithSymbols::new("Type").show(settings, ui, &mut interface.types);
                 SubWidgetsWithSymbols::new("Function").show(settings, ui, &mut interface.functions);
                 SubWidgetsWithSymbols::new("Method").show(settings, ui, &mut interface.methods);
                 SubWidgets::new("Interface").show(settings, ui, &mut interface.interfaces);
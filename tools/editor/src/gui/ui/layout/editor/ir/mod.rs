@@ -27,6 +27,7 @@ pub use module::*;
 pub use literal::*;
 pub use interface::*;
 use ligen_idl::symbols::Symbols;
+use ligen_transformer::generator::Generator;
 use crate::gui::ui::List;
 
 use crate::gui::ui::panes::{Pane, PaneManager};
@@ -65,13 +66,23 @@ impl Pane for Editor {
             ui.menu_button("File", |ui| {
                 if ui.button("Save").clicked() {
                     let file = rfd::FileDialog::new()
-                        .add_filter("ligen-idl", &["lir"])
+                        .add_filter("ligen-idl", &["lir", "json"])
+                        .add_filter("ligen-idl (binary)", &["cbor"])
+                        .add_filter("ligen-idl (compressed)", &["zst"])
+                        .add_filter("ligen-idl (text)", &["lidl"])
                         .save_file();
                     if let Some(file) = file {
-                        self
-                            .library
-                            .save(file)
-                            .ok();
+                        if file.extension().is_some_and(|extension| extension == "lidl") {
+                            ligen_lidl::LidlGenerator::new()
+                                .generate(&self.library, &Default::default())
+                                .and_then(|text| Ok(std::fs::write(file, text)?))
+                                .ok();
+                        } else {
+                            self
+                                .library
+                                .save(file)
+                                .ok();
+                        }
                     }
                     ui.close_menu();
                 }
@@ -3,10 +3,12 @@ use ligen_gui_runtime::egui::ComboBox;
 mod structure;
 mod enumeration;
 mod type_alias;
+mod union;
 
 pub use structure::*;
 pub use enumeration::*;
 pub use type_alias::*;
+pub use union::*;
 use crate::gui::ui::TextPrinter;
 use crate::gui::ui::editor::settings::Settings;
 use crate::prelude::*;
@@ -33,6 +35,7 @@ impl KindDefinition {
                 .show_ui(ui, |ui| {
                     ui.selectable_value(definition, ligen_idl::KindDefinition::Structure(Default::default()), "Structure");
                     ui.selectable_value(definition, ligen_idl::KindDefinition::Enumeration(Default::default()), "Enumeration");
+                    ui.selectable_value(definition, ligen_idl::KindDefinition::Union(Default::default()), "Union");
                 });
         } else {
             ui.label(variant_name);
@@ -47,6 +50,7 @@ impl Widget for KindDefinition {
             ligen_idl::KindDefinition::Structure(structure) => Structure::new().show(settings, ui, structure),
             ligen_idl::KindDefinition::Enumeration(enumeration) => Enumeration::new().show(settings, ui, enumeration),
             ligen_idl::KindDefinition::TypeAlias(type_alias) => TypeAlias::new().show(settings, ui, type_alias),
+            ligen_idl::KindDefinition::Union(union) => Union::new().show(settings, ui, union),
         }
     }
 }
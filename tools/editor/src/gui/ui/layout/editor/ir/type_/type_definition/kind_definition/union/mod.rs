@@ -0,0 +1,23 @@
+pub use crate::prelude::*;
+
+use crate::gui::ui::{EditableList, editor::{widget::Widget, settings::Settings}};
+
+use super::Field;
+
+#[derive(Default)]
+pub struct Union;
+
+impl Union {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Widget for Union {
+    type Input = ligen_idl::Union;
+    fn show(&mut self, settings: &Settings, ui: &mut egui::Ui, union: &mut ligen_idl::Union) {
+        EditableList::new("Fields", "Add field").show(settings, ui, &mut union.fields, |ui, field| {
+            Field::new().show(settings, ui, field);
+        });
+    }
+}
@@ -15,10 +15,15 @@ impl MenuButton for EditorMenuButton {
     fn show_button(&self, ui: &mut egui::Ui, panes: &mut Panes) {
         if ui.button("Open").clicked() {
             let file = rfd::FileDialog::new()
-                .add_filter("ligen-idl", &["lir"])
+                .add_filter("ligen-idl", &["lir", "json", "cbor", "zst", "lidl"])
                 .pick_file();
             if let Some(file) = file {
-                if let Ok(library) = ligen_idl::Library::load(file) {
+                let library = if file.extension().is_some_and(|extension| extension == "lidl") {
+                    ligen_lidl::LidlParser::new().transform(file.as_path(), &Config::default())
+                } else {
+                    ligen_idl::Library::load(file)
+                };
+                if let Ok(library) = library {
                     panes.new_pane(Box::new(Editor::new(library)));
                 }
             }
@@ -11,7 +11,8 @@ use ligen_python_parser::{PythonParser, PythonParserConfig};
 pub struct Parser {
     parser: Box<dyn for<'a> Transformer<&'a Path, ligen_idl::Library>>,
     config: ligen_transformer::prelude::Config,
-    result: String
+    result: String,
+    diagnostics: Vec<Diagnostic>
 }
 
 impl Parser {
@@ -21,7 +22,8 @@ impl Parser {
         let config = parser.config();
         let parser = Box::new(parser);
         let result = Default::default();
-        Self { parser, config, result }
+        let diagnostics = Default::default();
+        Self { parser, config, result, diagnostics }
     }
 }
 
@@ -37,18 +39,35 @@ impl Widget for Parser {
                         .pick_folder();
                     if let Some(entry) = entry {
                         stacker::grow(1024 * 1024 * 10, || {
+                            self.result.clear();
                             match self.parser.transform(entry.as_path(), &self.config) {
                                 Ok(library) => pane_manager.new_pane(Box::new(Editor::new(library))),
                                 Err(error) => {
                                     self.result = format!("{error:?}");
                                 }
                             }
+                            self.diagnostics = self.config.diagnostics().take();
                         });
                     }
                 }
                 if !self.result.is_empty() {
                     ui.colored_label(Color32::from_rgb(255, 0, 0), &self.result);
                 }
+                if !self.diagnostics.is_empty() {
+                    CollapsingHeader::new(format!("Diagnostics ({})", self.diagnostics.len()))
+                        .id_source("diagnostics")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            for diagnostic in &self.diagnostics {
+                                let color = match diagnostic.severity {
+                                    Severity::Error => Color32::from_rgb(255, 0, 0),
+                                    Severity::Warning => Color32::from_rgb(255, 200, 0),
+                                    Severity::Note => ui.visuals().text_color(),
+                                };
+                                ui.colored_label(color, diagnostic.to_string());
+                            }
+                        });
+                }
             });
     }
 }
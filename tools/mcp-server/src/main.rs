@@ -1,3 +1,4 @@
+use ligen_idl::{Document, Library, Query};
 use ligen_mcp_server::Registry;
 use rmcp::handler::server::router::tool::ToolRouter;
 use rmcp::handler::server::wrapper::Parameters;
@@ -16,6 +17,8 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing_subscriber::EnvFilter;
 
+const LIBRARY_SCHEMA_URI: &str = "ligen://schema/library";
+
 #[derive(Deserialize, Serialize, JsonSchema)]
 #[schemars(crate = "rmcp::schemars")]
 pub struct AddProjectArgs {
@@ -28,6 +31,14 @@ pub struct RemoveProjectArgs {
     pub name: String,
 }
 
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[schemars(crate = "rmcp::schemars")]
+pub struct QueryArgs {
+    /// The project to search. All projects are searched if it's omitted.
+    pub project: Option<String>,
+    pub query: String,
+}
+
 #[derive(Clone)]
 pub struct McpServer {
     pub registry: Arc<Mutex<Registry>>,
@@ -51,7 +62,9 @@ impl McpServer {
 
 #[tool_router]
 impl McpServer {
-    #[tool(description = "Add a project to the registry by its filesystem path")]
+    #[tool(
+        description = "Add a project to the registry by its filesystem path, or by a saved IDL document (.json, .cbor or .zst)"
+    )]
     async fn add_project(&self, Parameters(args): Parameters<AddProjectArgs>) -> String {
         let mut registry = self.registry.lock().await;
         match registry.add_project(args.path) {
@@ -80,6 +93,33 @@ impl McpServer {
             format!("Registered projects: {}", projects.join(", "))
         }
     }
+
+    #[tool(
+        description = "Find the items of a project matching a query, such as `function lib::** where public and async and returns Option<_>` or `structure where field Address`. Kinds: module, import, object, type, structure, enumeration, union, alias, field, variant, function, method, parameter, interface, *. Predicates: public, private, async, sync, name <glob>, attribute <glob>, implements <glob>, returns <type>, field <type>, parameter <type>, type <type>, combined with and, or, not and parentheses"
+    )]
+    async fn query(&self, Parameters(args): Parameters<QueryArgs>) -> String {
+        let query = match Query::parse(&args.query) {
+            Ok(query) => query,
+            Err(e) => return format!("Invalid query: {}", e),
+        };
+        let registry = self.registry.lock().await;
+        let mut names = match args.project {
+            Some(name) => vec![name],
+            None => registry.list_projects(),
+        };
+        names.sort();
+        let mut matches = Vec::new();
+        for name in names {
+            match registry.get_project(&name) {
+                Some(project) => matches.extend(query.find(&project.library)),
+                None => return format!("Project '{}' not found", name),
+            }
+        }
+        match serde_json::to_string_pretty(&matches) {
+            Ok(json) => json,
+            Err(e) => format!("Failed to serialize the matches: {}", e),
+        }
+    }
 }
 
 #[tool_handler]
@@ -111,6 +151,10 @@ impl ServerHandler for McpServer {
     ) -> Result<ListResourcesResult, ErrorData> {
         let registry = self.registry.lock().await;
         let mut resources = Vec::new();
+        let mut schema = RawResource::new(LIBRARY_SCHEMA_URI, "Library IDL schema");
+        schema.description = Some("JSON schema of saved library IDL documents".to_string());
+        schema.mime_type = Some("application/json".to_string());
+        resources.push(schema.no_annotation());
         for (name, _project) in registry.projects.iter() {
             let mut raw =
                 RawResource::new(format!("ligen://{}/idl", name), format!("{} IDL", name));
@@ -129,6 +173,19 @@ impl ServerHandler for McpServer {
         let registry = self.registry.lock().await;
         let uri = &request.uri;
 
+        if uri == LIBRARY_SCHEMA_URI
+            && let Ok(schema_json) = serde_json::to_string_pretty(&Library::schema())
+        {
+            return Ok(ReadResourceResult {
+                contents: vec![ResourceContents::TextResourceContents {
+                    uri: uri.clone(),
+                    mime_type: Some("application/json".to_string()),
+                    text: schema_json,
+                    meta: None,
+                }],
+            });
+        }
+
         if let Some(part) = uri.strip_prefix("ligen://")
             && let Some(slash_idx) = part.find('/')
         {
@@ -137,7 +194,7 @@ impl ServerHandler for McpServer {
 
             if path == "/idl"
                 && let Some(project) = registry.get_project(project_name)
-                && let Ok(idl_json) = serde_json::to_string_pretty(&project.library)
+                && let Ok(idl_json) = Document::to_json(&project.library)
             {
                 return Ok(ReadResourceResult {
                     contents: vec![ResourceContents::TextResourceContents {
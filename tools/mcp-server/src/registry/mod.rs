@@ -32,12 +32,16 @@ impl Registry {
 
     pub fn add_project(&mut self, path: impl AsRef<Path>) -> Result<String> {
         let path = path.as_ref().to_path_buf();
-        let parser = RustRegistryParser;
+        let registry = if path.is_file() {
+            Self::load_document(path.as_path())?
+        } else {
+            let parser = RustRegistryParser;
 
-        // I need to check RustParser transform signature.
-        let transformer: &dyn Transformer<&Path, ligen_idl::Registry> = &parser;
-        let config = transformer.config();
-        let registry = transformer.transform(path.as_path(), &config)?;
+            // I need to check RustParser transform signature.
+            let transformer: &dyn Transformer<&Path, ligen_idl::Registry> = &parser;
+            let config = transformer.config();
+            transformer.transform(path.as_path(), &config)?
+        };
 
         if let Some(library) = registry.libraries.values().next() {
             let name = library.identifier.to_string();
@@ -53,6 +57,19 @@ impl Registry {
         }
     }
 
+    /// Loads a saved library or registry document in any of the supported formats.
+    fn load_document(path: &Path) -> Result<ligen_idl::Registry> {
+        let registry_error = match ligen_idl::Registry::load(path) {
+            Ok(registry) => return Ok(registry),
+            Err(error) => error,
+        };
+        let library = Library::load(path).map_err(|library_error| {
+            format!("{} is neither a registry ({registry_error}) nor a library ({library_error}).", path.display())
+        })?;
+        let libraries = HashMap::from([(library.identifier.clone(), library)]);
+        Ok(ligen_idl::Registry { libraries })
+    }
+
     pub fn remove_project(&mut self, name: &str) -> bool {
         self.projects.remove(name).is_some()
     }